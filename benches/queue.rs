@@ -0,0 +1,97 @@
+// 全局队列基准测试：比较加锁队列和无锁队列在大量微小任务下的吞吐量
+// Global queue benchmark: compares the locked and the lock-free queue under many tiny tasks
+// 运行方式：cargo bench --bench queue
+// Run with: cargo bench --bench queue
+
+// 导入 Arc（原子引用计数）
+// Import Arc (atomic reference counting)
+use std::sync::Arc;
+
+// 导入 AtomicUsize（原子无符号整数）和 Ordering（内存排序）
+// Import AtomicUsize (atomic unsigned integer) and Ordering (memory ordering)
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// 导入线程模块
+// Import thread module
+use std::thread;
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 导入线程池及队列实现方式
+// Import thread pool and queue implementations
+use rust_dynamic_thread_pool::{QueueKind, ThreadPool};
+
+// 每个生产者提交的任务数
+// Number of tasks submitted by each producer
+const TASKS_PER_PRODUCER: usize = 200_000;
+
+// 每种配置重复的次数，取中位数
+// Repetitions per configuration, the median is reported
+const ROUNDS: usize = 5;
+
+// 多个生产者同时提交空任务，返回全部执行完毕所用的时间
+// Several producers submit empty tasks concurrently, returns the time until all of them have run
+fn run(kind: QueueKind, producers: usize, workers: usize) -> Duration {
+    let pool = Arc::new(ThreadPool::builder().max_threads(workers).core_threads(workers).prestart(true).queue_kind(kind).build());
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let start = Instant::now();
+    let handles: Vec<_> = (0..producers)
+        .map(|_| {
+            let pool = Arc::clone(&pool);
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..TASKS_PER_PRODUCER {
+                    let counter = Arc::clone(&counter);
+                    pool.submit(move || {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    })
+                    .expect("Submit failed");
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().expect("Producer panicked");
+    }
+    pool.wait_for_completion();
+    let elapsed = start.elapsed();
+
+    assert_eq!(counter.load(Ordering::Relaxed), producers * TASKS_PER_PRODUCER);
+    elapsed
+}
+
+// 重复运行并返回中位数
+// Run repeatedly and return the median
+fn median(kind: QueueKind, producers: usize, workers: usize) -> Duration {
+    let mut samples: Vec<Duration> = (0..ROUNDS).map(|_| run(kind, producers, workers)).collect();
+    samples.sort();
+    samples[ROUNDS / 2]
+}
+
+fn main() {
+    let cpus = thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+    println!("{:>10} {:>9} {:>8} {:>12} {:>14}", "queue", "producers", "workers", "median", "tasks/sec");
+
+    // 核心数较少时部分配置相同，只运行一次
+    // Some configurations coincide on machines with few cores, run each only once
+    let mut configs = vec![(1, 1), (1, cpus), (cpus, cpus), (4 * cpus, cpus), (4 * cpus, 4 * cpus)];
+    configs.dedup();
+
+    for (producers, workers) in configs {
+        for kind in [QueueKind::Locked, QueueKind::LockFree] {
+            let elapsed = median(kind, producers, workers);
+            let throughput = (producers * TASKS_PER_PRODUCER) as f64 / elapsed.as_secs_f64();
+            println!(
+                "{:>10} {:>9} {:>8} {:>10.1}ms {:>14.0}",
+                format!("{:?}", kind),
+                producers,
+                workers,
+                elapsed.as_secs_f64() * 1000.0,
+                throughput
+            );
+        }
+    }
+}
@@ -0,0 +1,364 @@
+// 导入 Any（任意类型，用于 panic 载荷）
+// Import Any (arbitrary type, used for panic payloads)
+use std::any::Any;
+
+// 导入 Arc（原子引用计数）
+// Import Arc (atomic reference counting)
+use std::sync::Arc;
+
+// 导入 Duration（时间段）
+// Import Duration (time duration)
+use std::time::Duration;
+
+// 导入线程池及 panic 处理器相关类型
+// Import thread pool and panic handler related types
+use crate::thread_pool::{get_cpu_count, PanicHandler, TaskInfo, ThreadPool};
+
+// 导入拒绝策略
+// Import rejection policy
+use crate::rejection::RejectionPolicy;
+
+// 导入调度模式
+// Import scheduling mode
+use crate::work_stealing::SchedulerMode;
+
+// 导入全局队列的实现方式
+// Import global queue implementation
+use crate::lock_free::QueueKind;
+
+// 导入线程池事件观察者
+// Import pool event observer
+use crate::observer::PoolObserver;
+
+// 默认的空闲线程存活时间（10秒）
+// Default idle thread keep-alive time (10 seconds)
+const DEFAULT_KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+// 默认的延迟直方图滑动窗口（60秒）
+// Default sliding window of the latency histograms (60 seconds)
+const DEFAULT_LATENCY_WINDOW: Duration = Duration::from_secs(60);
+
+// 线程池构建器，用于在创建前配置线程池参数
+// Thread pool builder, used to configure pool parameters before creation
+pub struct ThreadPoolBuilder {
+    // 核心线程数，空闲时不会被回收，默认为0
+    // Core thread count, never reclaimed when idle, defaults to 0
+    pub(crate) core_threads: usize,
+
+    // 最大线程数，为 None 时使用CPU核心数
+    // Maximum thread count, CPU core count is used when None
+    pub(crate) max_threads: Option<usize>,
+
+    // 非核心线程的空闲存活时间
+    // Idle keep-alive time of non-core threads
+    pub(crate) keep_alive: Duration,
+
+    // 线程名前缀
+    // Thread name prefix
+    pub(crate) thread_name_prefix: Option<String>,
+
+    // 线程栈大小（字节）
+    // Thread stack size (bytes)
+    pub(crate) stack_size: Option<usize>,
+
+    // 是否在创建线程池时预启动所有核心线程
+    // Whether to prestart all core threads when the pool is created
+    pub(crate) prestart: bool,
+
+    // 任务队列容量，为 None 时队列无界
+    // Task queue capacity, the queue is unbounded when None
+    pub(crate) queue_capacity: Option<usize>,
+
+    // 任务在队列中的最长存活时间，为 None 时不限制
+    // Maximum time a task may live in the queue, unlimited when None
+    pub(crate) queue_ttl: Option<Duration>,
+
+    // 优先级老化间隔，为 None 时不老化
+    // Priority aging interval, no aging when None
+    pub(crate) priority_aging: Option<Duration>,
+
+    // 队列已满时使用的拒绝策略
+    // Rejection policy used when the queue is full
+    pub(crate) rejection_policy: Option<Arc<dyn RejectionPolicy>>,
+
+    // 调度模式，默认所有工作线程共享全局队列
+    // Scheduling mode, all workers share the global queue by default
+    pub(crate) scheduler: SchedulerMode,
+
+    // 全局队列的实现方式，默认使用加锁队列
+    // Implementation of the global queue, the locked queue by default
+    pub(crate) queue_kind: QueueKind,
+
+    // 延迟直方图覆盖的滑动窗口
+    // Sliding window covered by the latency histograms
+    pub(crate) latency_window: Duration,
+
+    // 任务 panic 时调用的处理器
+    // Handler called when a task panics
+    pub(crate) panic_handler: Option<PanicHandler>,
+
+    // 事件观察者，按注册顺序调用
+    // Event observers, called in registration order
+    pub(crate) observers: Vec<Arc<dyn PoolObserver>>,
+}
+
+impl ThreadPoolBuilder {
+    // 创建使用默认配置的构建器
+    // Create a builder with default configuration
+    pub fn new() -> Self {
+        ThreadPoolBuilder {
+            core_threads: 0,
+            max_threads: None,
+            keep_alive: DEFAULT_KEEP_ALIVE,
+            thread_name_prefix: None,
+            stack_size: None,
+            prestart: false,
+            queue_capacity: None,
+            queue_ttl: None,
+            priority_aging: None,
+            rejection_policy: None,
+            scheduler: SchedulerMode::Global,
+            queue_kind: QueueKind::Locked,
+            latency_window: DEFAULT_LATENCY_WINDOW,
+            panic_handler: None,
+            observers: Vec::new(),
+        }
+    }
+
+    // 设置核心线程数，核心线程空闲超时后也不会被回收
+    // Set core thread count, core threads are not reclaimed even after idle timeout
+    pub fn core_threads(mut self, core_threads: usize) -> Self {
+        self.core_threads = core_threads;
+        self
+    }
+
+    // 设置最大线程数
+    // Set maximum thread count
+    pub fn max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = Some(max_threads);
+        self
+    }
+
+    // 设置非核心线程的空闲存活时间
+    // Set idle keep-alive time of non-core threads
+    pub fn keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    // 设置线程名前缀，线程名为 "前缀-线程ID"
+    // Set thread name prefix, thread names are "prefix-threadID"
+    pub fn thread_name_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.thread_name_prefix = Some(prefix.into());
+        self
+    }
+
+    // 设置线程栈大小（字节）
+    // Set thread stack size (bytes)
+    pub fn stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
+    // 设置是否在创建时预启动所有核心线程
+    // Set whether all core threads are started eagerly on creation
+    pub fn prestart(mut self, prestart: bool) -> Self {
+        self.prestart = prestart;
+        self
+    }
+
+    // 设置任务队列容量，队列满时 submit 阻塞、try_submit 返回任务
+    // Set task queue capacity, when full submit blocks and try_submit returns the task
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        // 容量为0时任何任务都无法入队
+        // With zero capacity no task could ever be queued
+        assert!(capacity > 0, "Queue capacity must be greater than 0");
+        self.queue_capacity = Some(capacity);
+        self
+    }
+
+    // 设置任务在队列中的最长存活时间：没有显式截止时间的任务入队超过该时间后，出队时被丢弃并计入过期任务数
+    // Set the maximum time a task may live in the queue: tasks without an explicit deadline that waited longer are discarded at dequeue and counted as expired
+    pub fn queue_ttl(mut self, ttl: Duration) -> Self {
+        self.queue_ttl = Some(ttl);
+        self
+    }
+
+    // 设置优先级老化间隔：任务在某一级别等待超过该时间后提升一级，防止低优先级任务饿死
+    // Set priority aging interval: a task waiting longer than this at a level is promoted one level, preventing starvation
+    pub fn priority_aging(mut self, interval: Duration) -> Self {
+        self.priority_aging = Some(interval);
+        self
+    }
+
+    // 设置拒绝策略，线程池达到最大线程数且队列已满时由该策略处理新任务
+    // Set the rejection policy, which handles new tasks when the pool is at max threads and the queue is full
+    pub fn rejection_policy<P>(mut self, policy: P) -> Self
+    where
+        P: RejectionPolicy + 'static,
+    {
+        self.rejection_policy = Some(Arc::new(policy));
+        self
+    }
+
+    // 设置调度模式；工作窃取模式下工作线程内提交的任务进入其本地队列，本地队列不受队列容量限制，也不按优先级排序
+    // Set the scheduling mode; in work-stealing mode tasks submitted from a worker go to its local deque, which ignores queue capacity and priority ordering
+    pub fn scheduler(mut self, mode: SchedulerMode) -> Self {
+        self.scheduler = mode;
+        self
+    }
+
+    // 设置全局队列的实现方式；无锁队列避免提交和取任务时争用同一把锁，但不支持优先级老化，与 priority_aging 同时使用时 build 会 panic
+    // Set the implementation of the global queue; the lock-free queue avoids contending on one lock when submitting and taking tasks, but does not support priority aging, and build panics when it is combined with priority_aging
+    pub fn queue_kind(mut self, kind: QueueKind) -> Self {
+        self.queue_kind = kind;
+        self
+    }
+
+    // 设置延迟直方图的滑动窗口，stats 中的排队等待时间和执行时间分位数只统计最近这段时间内的任务
+    // Set the sliding window of the latency histograms, the queue wait and run time percentiles in stats only cover tasks from this recent period
+    pub fn latency_window(mut self, window: Duration) -> Self {
+        // 窗口为0时无法划分时间片
+        // A zero window cannot be split into slices
+        assert!(!window.is_zero(), "Latency window must be greater than 0");
+        self.latency_window = window;
+        self
+    }
+
+    // 设置任务 panic 时调用的处理器
+    // Set the handler called when a task panics
+    pub fn panic_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&(dyn Any + Send), &TaskInfo) + Send + Sync + 'static,
+    {
+        self.panic_handler = Some(Arc::new(handler));
+        self
+    }
+
+    // 注册事件观察者，可多次调用注册多个
+    // Register an event observer, call repeatedly to register several
+    pub fn observer<O>(mut self, observer: O) -> Self
+    where
+        O: PoolObserver + 'static,
+    {
+        self.observers.push(Arc::new(observer));
+        self
+    }
+
+    // 校验配置并创建线程池
+    // Validate configuration and create the thread pool
+    pub fn build(mut self) -> ThreadPool {
+        // 未设置最大线程数时使用CPU核心数
+        // Use CPU core count when maximum thread count is not set
+        let max_threads = *self.max_threads.get_or_insert_with(get_cpu_count);
+
+        // 验证线程数必须大于0
+        // Validate that thread count must be greater than 0
+        assert!(max_threads > 0, "Thread pool size must be greater than 0");
+
+        // 核心线程数不能超过最大线程数
+        // Core thread count must not exceed maximum thread count
+        assert!(
+            self.core_threads <= max_threads,
+            "Core thread count must not exceed maximum thread count"
+        );
+
+        // 无锁队列的各级别只能先进先出，无法提升等待过久的任务
+        // The lock-free queue's levels are strictly FIFO and cannot promote tasks that waited too long
+        assert!(
+            !(self.queue_kind == QueueKind::LockFree && self.priority_aging.is_some()),
+            "Priority aging is not supported by the lock-free queue"
+        );
+
+        ThreadPool::from_builder(self)
+    }
+}
+
+impl Default for ThreadPoolBuilder {
+    fn default() -> Self {
+        ThreadPoolBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{mpsc, Mutex};
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    #[should_panic(expected = "Thread pool size must be greater than 0")]
+    fn zero_max_threads_panics() {
+        ThreadPoolBuilder::new().max_threads(0).build();
+    }
+
+    #[test]
+    #[should_panic(expected = "Core thread count must not exceed maximum thread count")]
+    fn core_threads_above_max_threads_panics() {
+        ThreadPoolBuilder::new().core_threads(3).max_threads(2).build();
+    }
+
+    #[test]
+    fn prestart_spawns_core_threads_on_build() {
+        let pool = ThreadPoolBuilder::new().core_threads(2).max_threads(4).prestart(true).build();
+        assert_eq!(pool.threads_num(), 2);
+
+        let lazy = ThreadPoolBuilder::new().core_threads(2).max_threads(4).build();
+        assert_eq!(lazy.threads_num(), 0);
+    }
+
+    #[test]
+    fn idle_threads_above_core_are_retired_after_keep_alive() {
+        let pool = ThreadPoolBuilder::new()
+            .core_threads(1)
+            .max_threads(3)
+            .keep_alive(Duration::from_millis(50))
+            .build();
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Arc::new(Mutex::new(release_rx));
+
+        // 三个任务同时阻塞，迫使线程池扩展到最大线程数
+        // Three tasks block at the same time, forcing the pool up to its maximum thread count
+        for _ in 0..3 {
+            let started = started_tx.clone();
+            let release = Arc::clone(&release_rx);
+            pool.submit(move || {
+                started.send(()).expect("Failed to signal start");
+                let _ = release.lock().expect("Failed to lock release receiver").recv();
+            })
+            .expect("Failed to submit task");
+        }
+        for _ in 0..3 {
+            started_rx.recv_timeout(Duration::from_secs(10)).expect("Task did not start");
+        }
+        assert_eq!(pool.threads_num(), 3);
+
+        drop(release_tx);
+
+        // 只有超出核心线程数的线程被回收
+        // Only the threads above the core count are reclaimed
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while pool.threads_num() > 1 {
+            assert!(Instant::now() < deadline, "Idle threads were not retired");
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_millis(150));
+        assert_eq!(pool.threads_num(), 1);
+    }
+
+    #[test]
+    fn threads_are_named_after_the_prefix() {
+        let pool = ThreadPoolBuilder::new().max_threads(1).thread_name_prefix("builder-test").build();
+        let (name_tx, name_rx) = mpsc::channel();
+
+        pool.submit(move || {
+            name_tx.send(thread::current().name().map(str::to_owned)).expect("Failed to send name");
+        })
+        .expect("Failed to submit task");
+
+        let name = name_rx.recv_timeout(Duration::from_secs(10)).expect("Task did not run");
+        assert!(name.is_some_and(|name| name.starts_with("builder-test-")));
+    }
+}
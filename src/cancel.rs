@@ -0,0 +1,259 @@
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入 panic 模块和 AssertUnwindSafe（捕获回调中的 panic）
+// Import panic module and AssertUnwindSafe (catch panics inside callbacks)
+use std::panic::{self, AssertUnwindSafe};
+
+// 导入 Arc（原子引用计数）、Weak（弱引用）和 Mutex（互斥锁）
+// Import Arc (atomic reference counting), Weak (weak reference), and Mutex (mutual exclusion lock)
+use std::sync::{Arc, Mutex, Weak};
+
+// 导入 AtomicBool（原子布尔）和 Ordering（内存排序）
+// Import AtomicBool (atomic boolean) and Ordering (memory ordering)
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 取消时执行的回调
+// Callback executed on cancellation
+type Callback = Box<dyn FnOnce() + Send + 'static>;
+
+// 令牌内部状态
+// Internal token state
+struct TokenInner {
+    // 是否已取消，原子读取，供任务快速轮询
+    // Whether cancelled, read atomically so tasks can poll cheaply
+    cancelled: AtomicBool,
+
+    // 回调和子令牌，取消时一次性取出
+    // Callbacks and child tokens, taken out once on cancellation
+    state: Mutex<TokenState>,
+}
+
+// 受互斥锁保护的令牌状态
+// Token state protected by the mutex
+struct TokenState {
+    // 取消时执行的回调
+    // Callbacks executed on cancellation
+    callbacks: Vec<Callback>,
+
+    // 子令牌，使用弱引用，子令牌被丢弃后不会被父令牌保留
+    // Child tokens, held weakly so dropped children are not kept alive by the parent
+    children: Vec<Weak<TokenInner>>,
+}
+
+// 协作式取消令牌：取消后尚未开始的任务被跳过，运行中的任务可轮询状态或注册回调；
+// 令牌组成父子树，取消父令牌会取消所有子令牌
+// Cooperative cancellation token: once cancelled, tasks that have not started are skipped and running tasks can poll or register callbacks;
+// tokens form a parent/child tree, cancelling a parent cancels all of its children
+#[derive(Clone)]
+pub struct CancellationToken {
+    // 共享的令牌状态
+    // Shared token state
+    inner: Arc<TokenInner>,
+}
+
+impl CancellationToken {
+    // 创建未取消的根令牌
+    // Create an uncancelled root token
+    pub fn new() -> Self {
+        CancellationToken {
+            inner: Arc::new(TokenInner {
+                cancelled: AtomicBool::new(false),
+                state: Mutex::new(TokenState {
+                    callbacks: Vec::new(),
+                    children: Vec::new(),
+                }),
+            }),
+        }
+    }
+
+    // 创建子令牌：父令牌取消时子令牌随之取消，取消子令牌不影响父令牌
+    // Create a child token: it is cancelled when the parent is, cancelling the child does not affect the parent
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+
+        let mut state = self.inner.state.lock().expect("Failed to lock token mutex");
+
+        // 在锁内检查，保证与 cancel 取出子令牌互斥
+        // Checked under the lock, mutually exclusive with cancel taking out the children
+        if self.is_cancelled() {
+            drop(state);
+            child.cancel();
+            return child;
+        }
+
+        // 顺便清理已被丢弃的子令牌，避免长期存在的父令牌无限增长
+        // Prune dropped children along the way, so long-lived parents do not grow forever
+        state.children.retain(|child| child.strong_count() > 0);
+        state.children.push(Arc::downgrade(&child.inner));
+        drop(state);
+
+        child
+    }
+
+    // 取消令牌：执行所有回调并取消所有子令牌；重复取消没有效果；
+    // 某个回调 panic 时其余回调和子令牌照常执行和取消，全部完成后再重新抛出第一个 panic
+    // Cancel the token: runs all callbacks and cancels all children; cancelling again has no effect;
+    // a panicking callback does not stop the remaining callbacks and children, the first panic is re-raised once all of them are done
+    pub fn cancel(&self) {
+        // 取出回调和子令牌后释放锁，回调中可以安全地访问令牌
+        // Release the lock after taking callbacks and children out, so callbacks may safely access the token
+        let (callbacks, children) = {
+            let mut state = self.inner.state.lock().expect("Failed to lock token mutex");
+            if self.inner.cancelled.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            (
+                std::mem::take(&mut state.callbacks),
+                std::mem::take(&mut state.children),
+            )
+        };
+
+        let mut first_panic = None;
+        for callback in callbacks {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(callback)) {
+                first_panic.get_or_insert(payload);
+            }
+        }
+
+        for child in children.iter().filter_map(Weak::upgrade) {
+            let child = CancellationToken { inner: child };
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| child.cancel())) {
+                first_panic.get_or_insert(payload);
+            }
+        }
+
+        if let Some(payload) = first_panic {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    // 令牌是否已被取消
+    // Whether the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    // 注册取消回调，在调用 cancel 的线程上执行；令牌已取消时立即在当前线程执行
+    // Register a cancellation callback, run on the thread calling cancel; runs immediately on the current thread if already cancelled
+    pub fn on_cancel<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut state = self.inner.state.lock().expect("Failed to lock token mutex");
+        if self.is_cancelled() {
+            drop(state);
+            callback();
+            return;
+        }
+        state.callbacks.push(Box::new(callback));
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        CancellationToken::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::blocked_pool;
+    use crate::thread_pool::ThreadPool;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn cancelling_a_parent_cancels_children_but_not_the_reverse() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let grandchild = child.child_token();
+
+        // 取消子令牌不影响父令牌
+        // Cancelling a child leaves the parent alone
+        let sibling = parent.child_token();
+        sibling.cancel();
+        assert!(!parent.is_cancelled());
+        assert!(!child.is_cancelled());
+
+        parent.cancel();
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+    }
+
+    #[test]
+    fn tokens_created_or_registered_after_cancel_see_it_at_once() {
+        let parent = CancellationToken::new();
+        parent.cancel();
+
+        // 已取消的令牌创建的子令牌立即处于取消状态
+        // A child created from a cancelled token starts out cancelled
+        assert!(parent.child_token().is_cancelled());
+
+        // 取消之后注册的回调在当前线程立即执行
+        // A callback registered after cancellation runs immediately on the current thread
+        let ran = Arc::new(AtomicUsize::new(0));
+        let callback_ran = Arc::clone(&ran);
+        parent.on_cancel(move || {
+            callback_ran.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+
+        // 重复取消不会再次执行回调
+        // Cancelling again does not run callbacks a second time
+        parent.cancel();
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn panicking_callback_does_not_stop_the_rest_of_the_cancel() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let ran = Arc::new(AtomicUsize::new(0));
+
+        parent.on_cancel(|| panic!("first callback failed"));
+        let callback_ran = Arc::clone(&ran);
+        parent.on_cancel(move || {
+            callback_ran.fetch_add(1, Ordering::SeqCst);
+        });
+        child.on_cancel(|| panic!("child callback failed"));
+
+        // 其余回调照常执行、子令牌照常取消，之后重新抛出第一个 panic
+        // The other callbacks still run and the child is still cancelled, then the first panic is re-raised
+        let payload = panic::catch_unwind(AssertUnwindSafe(|| parent.cancel())).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"first callback failed"));
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+        assert!(child.is_cancelled());
+    }
+
+    #[test]
+    fn queued_task_is_skipped_once_its_token_is_cancelled() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        let token = CancellationToken::new();
+        let ran = Arc::new(AtomicUsize::new(0));
+
+        let task_ran = Arc::clone(&ran);
+        pool.submit_cancellable(&token.child_token(), move |_| {
+            task_ran.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        // 任务仍在队列中时取消父令牌，任务出队后被跳过
+        // Cancel the parent while the task is still queued, it is skipped at dequeue
+        token.cancel();
+        drop(release);
+        pool.wait_for_completion();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        assert_eq!(pool.cancelled_tasks(), 1);
+        assert_eq!(pool.completed_tasks(), 1);
+    }
+}
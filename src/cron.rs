@@ -0,0 +1,1001 @@
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入 FromStr（从字符串解析）
+// Import FromStr (parse from string)
+use std::str::FromStr;
+
+// 导入 Arc（原子引用计数）、Mutex（互斥锁）和 Condvar（条件变量）
+// Import Arc (atomic reference counting), Mutex (mutual exclusion lock), and Condvar (condition variable)
+use std::sync::{Arc, Condvar, Mutex};
+
+// 导入 AtomicUsize（原子计数）和 Ordering（内存排序）
+// Import AtomicUsize (atomic counter) and Ordering (memory ordering)
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// 导入线程模块和 JoinHandle（用于调度线程句柄）
+// Import thread module and JoinHandle (for the scheduler thread handle)
+use std::thread::{self, JoinHandle};
+
+// 导入 Duration（时间段）、SystemTime（系统时间）和 UNIX_EPOCH（Unix 纪元）
+// Import Duration (time duration), SystemTime (system time), and UNIX_EPOCH (Unix epoch)
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// 导入线程池
+// Import thread pool
+use crate::thread_pool::ThreadPool;
+
+// 调度线程单次最长等待时间，使系统时钟被调整后也能及时重新计算
+// Maximum single wait of the scheduler thread, so fire times are recomputed soon after the system clock is adjusted
+const MAX_WAIT: Duration = Duration::from_secs(1);
+
+// 触发时间晚于计划超过该阈值时视为错过
+// A fire running later than planned by more than this threshold counts as missed
+const MISFIRE_THRESHOLD: Duration = Duration::from_secs(1);
+
+// 补执行策略下一个任务一次最多补执行的次数，超出的错过触发被跳过，避免长时间停顿后一次提交海量任务
+// Default cap on how many runs one job catches up at once under the catch-up policy, further missed fires are skipped so a long stall does not flood the pool
+const DEFAULT_MAX_CATCH_UP_RUNS: usize = 100;
+
+// 计算下次触发时间时最多向后搜索的年数（覆盖 2月29日 跨越世纪非闰年的情况）
+// Maximum number of years searched ahead for the next fire time (covers Feb 29 across non-leap century years)
+const SEARCH_YEARS: i64 = 8;
+
+// 每天的秒数
+// Seconds per day
+const SECS_PER_DAY: i64 = 86_400;
+
+// cron 表达式解析错误
+// Cron expression parse error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CronError {
+    // 字段数量不是5或6
+    // Field count is neither 5 nor 6
+    FieldCount(usize),
+
+    // 某个字段的取值无效
+    // A field has an invalid value
+    InvalidField {
+        // 字段名
+        // Field name
+        field: &'static str,
+
+        // 无效的原始文本
+        // Invalid original text
+        value: String,
+    },
+}
+
+impl fmt::Display for CronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CronError::FieldCount(count) => {
+                write!(f, "cron expression must have 5 or 6 fields, found {}", count)
+            }
+            CronError::InvalidField { field, value } => {
+                write!(f, "invalid value '{}' in cron {} field", value, field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CronError {}
+
+// 相对 UTC 的固定时区偏移
+// Fixed time zone offset from UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UtcOffset {
+    // 偏移秒数，东区为正
+    // Offset in seconds, positive east of UTC
+    seconds: i64,
+}
+
+impl UtcOffset {
+    // UTC 本身
+    // UTC itself
+    pub const UTC: UtcOffset = UtcOffset { seconds: 0 };
+
+    // 按小时创建偏移，例如东八区为 8
+    // Create an offset in hours, e.g. 8 for UTC+8
+    pub fn from_hours(hours: i32) -> Self {
+        UtcOffset::from_seconds(hours * 3600)
+    }
+
+    // 按秒创建偏移，用于非整小时时区
+    // Create an offset in seconds, for time zones that are not whole hours
+    pub fn from_seconds(seconds: i32) -> Self {
+        // 实际时区偏移都在 ±18 小时以内
+        // Real time zone offsets are all within ±18 hours
+        assert!(seconds.abs() <= 18 * 3600, "UTC offset must be within ±18 hours");
+        UtcOffset { seconds: seconds as i64 }
+    }
+
+    // 返回偏移秒数
+    // Return the offset in seconds
+    pub fn as_seconds(&self) -> i32 {
+        self.seconds as i32
+    }
+}
+
+// 字段的取值范围和名称
+// Value range and name of a field
+struct FieldSpec {
+    // 字段名，用于错误信息
+    // Field name, used in error messages
+    name: &'static str,
+
+    // 最小值
+    // Minimum value
+    min: u32,
+
+    // 最大值
+    // Maximum value
+    max: u32,
+
+    // 可用的名称别名（如 JAN、MON），下标加最小值即对应数值
+    // Available name aliases (e.g. JAN, MON), index plus the minimum is the matching value
+    names: &'static [&'static str],
+}
+
+const SECOND: FieldSpec = FieldSpec { name: "second", min: 0, max: 59, names: &[] };
+const MINUTE: FieldSpec = FieldSpec { name: "minute", min: 0, max: 59, names: &[] };
+const HOUR: FieldSpec = FieldSpec { name: "hour", min: 0, max: 23, names: &[] };
+const DAY_OF_MONTH: FieldSpec = FieldSpec { name: "day-of-month", min: 1, max: 31, names: &[] };
+const MONTH: FieldSpec = FieldSpec {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"],
+};
+
+// 星期字段允许 0-7，0 和 7 都表示星期日
+// Day-of-week field accepts 0-7, both 0 and 7 mean Sunday
+const DAY_OF_WEEK: FieldSpec = FieldSpec {
+    name: "day-of-week",
+    min: 0,
+    max: 7,
+    names: &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"],
+};
+
+impl FieldSpec {
+    // 解析单个数值或名称别名
+    // Parse a single number or name alias
+    fn parse_value(&self, text: &str) -> Option<u32> {
+        let value = match text.parse::<u32>() {
+            Ok(value) => value,
+            Err(_) => {
+                let upper = text.to_ascii_uppercase();
+                self.names.iter().position(|name| *name == upper)? as u32 + self.min
+            }
+        };
+        (self.min..=self.max).contains(&value).then_some(value)
+    }
+
+    // 将字段解析为位掩码，第 n 位表示取值 n；同时返回字段是否为通配符
+    // Parse the field into a bitmask where bit n means value n; also returns whether the field is a wildcard
+    fn parse(&self, field: &str) -> Result<(u64, bool), CronError> {
+        let invalid = || CronError::InvalidField { field: self.name, value: field.to_string() };
+        let wildcard = field == "*" || field == "?";
+        let mut mask = 0u64;
+
+        // 逗号分隔的每一项都可以是 *、单值、范围，并可带 /步长
+        // Every comma-separated item may be *, a value or a range, optionally with a /step
+        for item in field.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => {
+                    let step = step.parse::<u32>().ok().filter(|&step| step > 0).ok_or_else(invalid)?;
+                    (range, Some(step))
+                }
+                None => (item, None),
+            };
+
+            let (start, end) = if range == "*" || range == "?" {
+                (self.min, self.max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                let start = self.parse_value(start).ok_or_else(invalid)?;
+                let end = self.parse_value(end).ok_or_else(invalid)?;
+                if start > end {
+                    return Err(invalid());
+                }
+                (start, end)
+            } else {
+                let start = self.parse_value(range).ok_or_else(invalid)?;
+
+                // "a/n" 表示从 a 开始到最大值，每隔 n 一次
+                // "a/n" means from a up to the maximum, every n
+                (start, if step.is_some() { self.max } else { start })
+            };
+
+            for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+                mask |= 1 << value;
+            }
+        }
+
+        Ok((mask, wildcard))
+    }
+}
+
+// 解析后的 cron 表达式，支持 5 字段（分 时 日 月 周）和 6 字段（秒 分 时 日 月 周）格式
+// Parsed cron expression, supports the 5-field (min hour dom month dow) and 6-field (sec min hour dom month dow) formats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    // 原始表达式文本
+    // Original expression text
+    source: String,
+
+    // 各字段的位掩码
+    // Bitmask of each field
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+
+    // 日和星期字段是否为通配符：两者都受限时满足其一即可（与标准 cron 一致）
+    // Whether the day-of-month and day-of-week fields are wildcards: when both are restricted either may match (as in standard cron)
+    dom_wildcard: bool,
+    dow_wildcard: bool,
+}
+
+impl CronExpr {
+    // 解析 cron 表达式，也支持 @yearly、@monthly、@weekly、@daily、@hourly 简写
+    // Parse a cron expression, the @yearly, @monthly, @weekly, @daily and @hourly shorthands are supported too
+    pub fn parse(expression: &str) -> Result<CronExpr, CronError> {
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+
+        // 5 字段格式的秒固定为0
+        // The 5-field format always fires at second 0
+        let (second, rest) = match fields.len() {
+            5 => ("0", &fields[..]),
+            6 => (fields[0], &fields[1..]),
+            count => return Err(CronError::FieldCount(count)),
+        };
+
+        let (days_of_month, dom_wildcard) = DAY_OF_MONTH.parse(rest[2])?;
+        let (mut days_of_week, dow_wildcard) = DAY_OF_WEEK.parse(rest[4])?;
+
+        // 7 与 0 同为星期日
+        // 7 is Sunday just like 0
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(CronExpr {
+            source: expression.trim().to_string(),
+            seconds: SECOND.parse(second)?.0,
+            minutes: MINUTE.parse(rest[0])?.0,
+            hours: HOUR.parse(rest[1])?.0,
+            days_of_month,
+            months: MONTH.parse(rest[3])?.0,
+            days_of_week,
+            dom_wildcard,
+            dow_wildcard,
+        })
+    }
+
+    // 返回原始表达式文本
+    // Return the original expression text
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    // 计算严格晚于给定时间的下一次触发时间（按给定时区解释表达式），找不到时返回 None（例如 2月30日）
+    // Compute the next fire time strictly after the given time (interpreting the expression in the given time zone), None if there is none (e.g. Feb 30)
+    pub fn next_after(&self, after: SystemTime, offset: UtcOffset) -> Option<SystemTime> {
+        let unix = after.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+
+        // 在本地时间上搜索，从下一整秒开始
+        // Search in local time, starting from the next whole second
+        let mut local = unix + offset.seconds + 1;
+        let (start_year, _, _) = civil_from_days(local.div_euclid(SECS_PER_DAY));
+
+        loop {
+            let days = local.div_euclid(SECS_PER_DAY);
+            let secs_of_day = local.rem_euclid(SECS_PER_DAY);
+            let (year, month, day) = civil_from_days(days);
+            if year > start_year + SEARCH_YEARS {
+                return None;
+            }
+
+            // 月份不匹配，跳到下个月1日零点
+            // Month does not match, jump to midnight on the 1st of next month
+            if !bit(self.months, month) {
+                let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+                local = days_from_civil(next_year, next_month, 1) * SECS_PER_DAY;
+                continue;
+            }
+
+            // 日期不匹配，跳到次日零点
+            // Day does not match, jump to midnight of the next day
+            if !self.day_matches(day, weekday(days)) {
+                local = (days + 1) * SECS_PER_DAY;
+                continue;
+            }
+
+            let hour = (secs_of_day / 3600) as u32;
+            let minute = (secs_of_day % 3600 / 60) as u32;
+            let second = (secs_of_day % 60) as u32;
+
+            // 小时不匹配，跳到下一小时整点
+            // Hour does not match, jump to the top of the next hour
+            if !bit(self.hours, hour) {
+                local = days * SECS_PER_DAY + (hour as i64 + 1) * 3600;
+                continue;
+            }
+
+            // 分钟不匹配，跳到下一分钟
+            // Minute does not match, jump to the next minute
+            if !bit(self.minutes, minute) {
+                local = days * SECS_PER_DAY + hour as i64 * 3600 + (minute as i64 + 1) * 60;
+                continue;
+            }
+
+            // 秒不匹配，跳到下一秒
+            // Second does not match, jump to the next second
+            if !bit(self.seconds, second) {
+                local += 1;
+                continue;
+            }
+
+            let utc = local - offset.seconds;
+            return u64::try_from(utc).ok().map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        }
+    }
+
+    // 日期是否匹配：日和星期都受限时满足其一即可，否则两者都要满足
+    // Whether the date matches: either may match when both day fields are restricted, otherwise both must match
+    fn day_matches(&self, day: u32, weekday: u32) -> bool {
+        let dom = bit(self.days_of_month, day);
+        let dow = bit(self.days_of_week, weekday);
+        if self.dom_wildcard || self.dow_wildcard {
+            dom && dow
+        } else {
+            dom || dow
+        }
+    }
+}
+
+impl FromStr for CronExpr {
+    type Err = CronError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CronExpr::parse(s)
+    }
+}
+
+impl fmt::Display for CronExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+// 位掩码中是否包含某个值
+// Whether the bitmask contains a value
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+// 1970-01-01 之后的天数对应的星期（0 为星期日），1970-01-01 是星期四
+// Weekday (0 is Sunday) of a day count since 1970-01-01, which was a Thursday
+fn weekday(days: i64) -> u32 {
+    (days + 4).rem_euclid(7) as u32
+}
+
+// 公历日期转换为 1970-01-01 之后的天数（Howard Hinnant 的 days_from_civil 算法）
+// Convert a Gregorian date into days since 1970-01-01 (Howard Hinnant's days_from_civil algorithm)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// 1970-01-01 之后的天数转换为公历日期（Howard Hinnant 的 civil_from_days 算法）
+// Convert days since 1970-01-01 into a Gregorian date (Howard Hinnant's civil_from_days algorithm)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// 错过触发时间（例如系统休眠或时钟被向前调整）时的处理策略
+// Policy for missed fire times (e.g. after system sleep or the clock being moved forward)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissedFirePolicy {
+    // 跳过所有错过的触发，等待下一次正常触发
+    // Skip all missed fires and wait for the next regular one
+    #[default]
+    Skip,
+
+    // 为每一次错过的触发都补执行一次，一次最多补执行 max_catch_up_runs 次
+    // Run once for every missed fire, at most max_catch_up_runs at once
+    CatchUp,
+}
+
+// 已注册任务的标识
+// Identifier of a registered job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CronJobId(usize);
+
+// 已注册任务的信息快照
+// Snapshot of a registered job
+#[derive(Debug, Clone)]
+pub struct CronJobInfo {
+    // 任务标识
+    // Job identifier
+    pub id: CronJobId,
+
+    // 任务名
+    // Job name
+    pub name: String,
+
+    // cron 表达式
+    // Cron expression
+    pub expression: CronExpr,
+
+    // 下一次触发时间，为 None 时不会再触发
+    // Next fire time, None when it will never fire again
+    pub next_run: Option<SystemTime>,
+}
+
+// 调度器中的任务
+// Job inside the scheduler
+struct CronJob {
+    // 任务标识
+    // Job identifier
+    id: CronJobId,
+
+    // 任务名
+    // Job name
+    name: String,
+
+    // cron 表达式
+    // Cron expression
+    expression: CronExpr,
+
+    // 下一次触发时间
+    // Next fire time
+    next_run: Option<SystemTime>,
+
+    // 每次触发时提交到线程池的闭包
+    // Closure submitted to the thread pool on every fire
+    job: Arc<dyn Fn() + Send + Sync + 'static>,
+}
+
+// 调度器状态，由互斥锁保护
+// Scheduler state, protected by the mutex
+struct CronState {
+    // 已注册的任务
+    // Registered jobs
+    jobs: Vec<CronJob>,
+
+    // 下一个任务标识
+    // Next job identifier
+    next_id: usize,
+
+    // 停止标志
+    // Stop flag
+    stopped: bool,
+}
+
+// 调度器线程与句柄共享的状态
+// State shared between the scheduler thread and the handle
+struct CronShared {
+    // 执行任务的线程池
+    // Thread pool executing the jobs
+    pool: Arc<ThreadPool>,
+
+    // 任务时区
+    // Time zone of the jobs
+    offset: UtcOffset,
+
+    // 错过触发时间的处理策略
+    // Policy for missed fire times
+    missed_fire_policy: MissedFirePolicy,
+
+    // 补执行策略下一个任务一次最多补执行的次数
+    // Maximum number of runs one job catches up at once under the catch-up policy
+    max_catch_up_runs: AtomicUsize,
+
+    // 调度器状态
+    // Scheduler state
+    state: Mutex<CronState>,
+
+    // 任务增删或停止时唤醒调度线程
+    // Wakes the scheduler thread when jobs are added or removed, or on stop
+    wakeup: Condvar,
+}
+
+// cron 调度器：由单独的调度线程计算触发时间，到期时将任务提交到线程池执行
+// Cron scheduler: a dedicated scheduler thread computes fire times and submits jobs into the thread pool when due
+pub struct CronScheduler {
+    // 共享状态
+    // Shared state
+    shared: Arc<CronShared>,
+
+    // 调度线程句柄
+    // Scheduler thread handle
+    thread: Option<JoinHandle<()>>,
+}
+
+impl CronScheduler {
+    // 创建使用 UTC 时区、错过即跳过的调度器
+    // Create a scheduler using UTC and skipping missed fires
+    pub fn new(pool: Arc<ThreadPool>) -> Self {
+        CronScheduler::with_options(pool, UtcOffset::UTC, MissedFirePolicy::Skip)
+    }
+
+    // 创建使用指定时区和错过策略的调度器
+    // Create a scheduler with the given time zone and missed fire policy
+    pub fn with_options(pool: Arc<ThreadPool>, offset: UtcOffset, missed_fire_policy: MissedFirePolicy) -> Self {
+        let shared = Arc::new(CronShared {
+            pool,
+            offset,
+            missed_fire_policy,
+            max_catch_up_runs: AtomicUsize::new(DEFAULT_MAX_CATCH_UP_RUNS),
+            state: Mutex::new(CronState {
+                jobs: Vec::new(),
+                next_id: 0,
+                stopped: false,
+            }),
+            wakeup: Condvar::new(),
+        });
+
+        // 与定时器线程一样，线程池设置了线程名前缀时以其命名调度线程
+        // Like the timer thread, the scheduler thread is named after the pool's thread name prefix when one is set
+        let mut builder = thread::Builder::new();
+        if let Some(prefix) = &shared.pool.inner.thread_name_prefix {
+            builder = builder.name(format!("{}-cron", prefix));
+        }
+        let thread_shared = Arc::clone(&shared);
+        let thread = builder
+            .spawn(move || thread_shared.run())
+            .expect("Failed to spawn cron scheduler thread");
+
+        CronScheduler { shared, thread: Some(thread) }
+    }
+
+    // 注册任务，表达式无效时返回错误
+    // Register a job, returns an error when the expression is invalid
+    pub fn schedule<F>(&self, name: &str, expression: &str, job: F) -> Result<CronJobId, CronError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let expression = CronExpr::parse(expression)?;
+        let next_run = expression.next_after(SystemTime::now(), self.shared.offset);
+
+        let mut state = self.shared.state.lock().expect("Failed to lock cron mutex");
+        let id = CronJobId(state.next_id);
+        state.next_id += 1;
+        state.jobs.push(CronJob {
+            id,
+            name: name.to_string(),
+            expression,
+            next_run,
+            job: Arc::new(job),
+        });
+        drop(state);
+
+        // 新任务可能比当前等待的触发时间更早
+        // The new job may fire earlier than the time currently waited for
+        self.shared.wakeup.notify_one();
+        Ok(id)
+    }
+
+    // 移除任务，返回任务是否存在；已提交到线程池的执行不受影响
+    // Remove a job, returns whether it existed; runs already submitted to the pool are not affected
+    pub fn remove(&self, id: CronJobId) -> bool {
+        let mut state = self.shared.state.lock().expect("Failed to lock cron mutex");
+        let before = state.jobs.len();
+        state.jobs.retain(|job| job.id != id);
+        before != state.jobs.len()
+    }
+
+    // 返回所有已注册任务的快照
+    // Return a snapshot of all registered jobs
+    pub fn jobs(&self) -> Vec<CronJobInfo> {
+        let state = self.shared.state.lock().expect("Failed to lock cron mutex");
+        state
+            .jobs
+            .iter()
+            .map(|job| CronJobInfo {
+                id: job.id,
+                name: job.name.clone(),
+                expression: job.expression.clone(),
+                next_run: job.next_run,
+            })
+            .collect()
+    }
+
+    // 返回任务的下一次触发时间，任务不存在或不会再触发时返回 None
+    // Return the next fire time of a job, None when the job does not exist or will never fire again
+    pub fn next_run(&self, id: CronJobId) -> Option<SystemTime> {
+        let state = self.shared.state.lock().expect("Failed to lock cron mutex");
+        state.jobs.iter().find(|job| job.id == id)?.next_run
+    }
+
+    // 返回调度器使用的时区
+    // Return the time zone used by the scheduler
+    pub fn offset(&self) -> UtcOffset {
+        self.shared.offset
+    }
+
+    // 返回错过触发时间的处理策略
+    // Return the missed fire policy
+    pub fn missed_fire_policy(&self) -> MissedFirePolicy {
+        self.shared.missed_fire_policy
+    }
+
+    // 设置补执行策略下一个任务一次最多补执行的次数（默认100次），超出的错过触发被跳过
+    // Set how many runs one job catches up at once under the catch-up policy (100 by default), further missed fires are skipped
+    pub fn set_max_catch_up_runs(&self, max: usize) {
+        // 为0时连按时的触发都不会执行
+        // With 0 not even on-time fires would run
+        assert!(max > 0, "Max catch-up runs must be greater than 0");
+        self.shared.max_catch_up_runs.store(max, Ordering::SeqCst);
+    }
+
+    // 返回补执行策略下一个任务一次最多补执行的次数
+    // Return how many runs one job catches up at once under the catch-up policy
+    pub fn max_catch_up_runs(&self) -> usize {
+        self.shared.max_catch_up_runs.load(Ordering::SeqCst)
+    }
+}
+
+impl CronJob {
+    // 将下一次触发时间推进到给定时间之后，返回需要执行的次数：按时的触发执行一次，错过的触发按策略跳过或逐次补执行，补执行最多 max_catch_up 次
+    // Advance the next fire time past the given time and return how many runs are due: an on-time fire runs once, missed fires are skipped or each run once depending on the policy, catching up at most max_catch_up runs
+    fn take_due(&mut self, now: SystemTime, offset: UtcOffset, policy: MissedFirePolicy, max_catch_up: usize) -> usize {
+        let mut runs = 0;
+        while let Some(next) = self.next_run.filter(|next| *next <= now) {
+            let late = now.duration_since(next).unwrap_or_default();
+            if late <= MISFIRE_THRESHOLD || policy == MissedFirePolicy::CatchUp {
+                runs += 1;
+            }
+
+            // 跳过时或补执行达到上限后直接从当前时间计算，避免逐个遍历其余错过的触发时间
+            // When skipping, or once catching up hits the cap, compute from now directly instead of walking the remaining missed fire times
+            let from = if policy == MissedFirePolicy::Skip || runs >= max_catch_up { now } else { next };
+            self.next_run = self.expression.next_after(from, offset);
+        }
+        runs
+    }
+}
+
+impl CronShared {
+    // 调度线程主循环
+    // Scheduler thread main loop
+    fn run(&self) {
+        let mut state = self.state.lock().expect("Failed to lock cron mutex");
+        loop {
+            if state.stopped {
+                return;
+            }
+
+            let now = SystemTime::now();
+            let max_catch_up = self.max_catch_up_runs.load(Ordering::SeqCst);
+            let mut due = Vec::new();
+
+            // 收集所有到期的任务及其执行次数，并推进它们的下一次触发时间
+            // Collect all due jobs with their run counts and advance their next fire times
+            for job in state.jobs.iter_mut() {
+                let runs = job.take_due(now, self.offset, self.missed_fire_policy, max_catch_up);
+                if runs > 0 {
+                    due.push((Arc::clone(&job.job), runs));
+                }
+            }
+
+            // 提交任务前释放锁，队列已满时提交可能阻塞
+            // Release the lock before submitting, submission may block when the queue is full
+            if !due.is_empty() {
+                drop(state);
+                for (job, runs) in due {
+                    for _ in 0..runs {
+                        // 线程池关闭后的触发被丢弃
+                        // Fires after the pool has shut down are dropped
+                        let job = Arc::clone(&job);
+                        let _ = self.pool.submit(move || job());
+                    }
+                }
+                state = self.state.lock().expect("Failed to lock cron mutex");
+                continue;
+            }
+
+            // 等待到最早的触发时间，但不超过 MAX_WAIT，以便察觉系统时钟调整
+            // Wait until the earliest fire time, but no longer than MAX_WAIT so system clock adjustments are noticed
+            let wait = state
+                .jobs
+                .iter()
+                .filter_map(|job| job.next_run)
+                .min()
+                .map(|next| next.duration_since(now).unwrap_or_default())
+                .map_or(MAX_WAIT, |until| until.min(MAX_WAIT));
+            state = self.wakeup.wait_timeout(state, wait).expect("Condvar wait failed").0;
+        }
+    }
+}
+
+impl Drop for CronScheduler {
+    fn drop(&mut self) {
+        // 设置停止标志并唤醒调度线程，已提交到线程池的执行不受影响
+        // Set the stop flag and wake the scheduler thread, runs already submitted to the pool are not affected
+        self.shared.state.lock().expect("Failed to lock cron mutex").stopped = true;
+        self.shared.wakeup.notify_all();
+
+        if let Some(thread) = self.thread.take() {
+            thread.join().expect("Cron scheduler thread join failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按 UTC 日期时间构造时间点
+    // Build a point in time from a UTC date and time
+    fn utc(year: i64, month: u32, day: u32, hour: u64, minute: u64, second: u64) -> SystemTime {
+        let days = days_from_civil(year, month, day) as u64;
+        UNIX_EPOCH + Duration::from_secs(days * 86_400 + hour * 3600 + minute * 60 + second)
+    }
+
+    // 表达式在给定时间之后的下一次触发时间
+    // Next fire time of an expression after the given time
+    fn next(expression: &str, from: SystemTime, offset: UtcOffset) -> Option<SystemTime> {
+        CronExpr::parse(expression).unwrap().next_after(from, offset)
+    }
+
+    // UTC 日期的零点
+    // UTC midnight of a date
+    fn date(year: i64, month: u32, day: u32) -> SystemTime {
+        utc(year, month, day, 0, 0, 0)
+    }
+
+    #[test]
+    fn civil_conversion_matches_known_dates() {
+        let cases = [
+            ((1970, 1, 1), 0),
+            ((1969, 12, 31), -1),
+            ((2000, 3, 1), 11_017),
+            ((2024, 1, 1), 19_723),
+            ((2024, 2, 29), 19_782),
+            ((2104, 2, 29), 49_001),
+        ];
+        for ((year, month, day), days) in cases {
+            assert_eq!(days_from_civil(year, month, day), days, "{year}-{month}-{day}");
+            assert_eq!(civil_from_days(days), (year, month, day));
+        }
+
+        // 往返转换覆盖 1900 到 2400 年的每一天
+        // Round trip covers every day from 1900 to 2400
+        for days in days_from_civil(1900, 1, 1)..days_from_civil(2400, 1, 1) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+
+        assert_eq!(weekday(0), 4);
+        assert_eq!(weekday(days_from_civil(2024, 9, 1)), 0);
+    }
+
+    #[test]
+    fn feb_29_fires_only_in_leap_years() {
+        let utc0 = UtcOffset::UTC;
+        assert_eq!(next("0 0 29 2 *", date(2023, 3, 1), utc0), Some(date(2024, 2, 29)));
+        assert_eq!(next("0 0 29 2 *", date(2024, 2, 29), utc0), Some(date(2028, 2, 29)));
+
+        // 2100 年不是闰年，下一个 2月29日 在 8 年之后
+        // 2100 is not a leap year, the next Feb 29 is 8 years away
+        assert_eq!(next("0 0 29 2 *", date(2096, 3, 1), utc0), Some(date(2104, 2, 29)));
+    }
+
+    #[test]
+    fn impossible_dates_never_fire() {
+        for expression in ["0 0 31 2 *", "0 0 30 2 *", "0 0 31 4,6,9,11 *"] {
+            assert_eq!(next(expression, date(2024, 1, 1), UtcOffset::UTC), None, "{expression}");
+        }
+    }
+
+    #[test]
+    fn day_of_month_and_day_of_week_match_either_when_both_restricted() {
+        let utc0 = UtcOffset::UTC;
+
+        // 2024-09-01 是星期日，09-06 是星期五，09-13 是星期五且为13日
+        // 2024-09-01 is a Sunday, 09-06 a Friday, 09-13 a Friday the 13th
+        assert_eq!(next("0 0 13 * FRI", date(2024, 9, 1), utc0), Some(date(2024, 9, 6)));
+        assert_eq!(next("0 0 13 * FRI", date(2024, 9, 6), utc0), Some(date(2024, 9, 13)));
+        assert_eq!(next("0 0 13 * FRI", date(2024, 9, 13), utc0), Some(date(2024, 9, 20)));
+        assert_eq!(next("0 0 1 * MON", date(2024, 9, 1), utc0), Some(date(2024, 9, 2)));
+
+        // 只有一个字段受限时只按该字段匹配
+        // With only one field restricted, only that field matters
+        assert_eq!(next("0 0 13 * *", date(2024, 9, 1), utc0), Some(date(2024, 9, 13)));
+        assert_eq!(next("0 0 * * FRI", date(2024, 9, 1), utc0), Some(date(2024, 9, 6)));
+        assert_eq!(next("0 0 ? * FRI", date(2024, 9, 1), utc0), Some(date(2024, 9, 6)));
+    }
+
+    #[test]
+    fn day_of_week_seven_is_sunday() {
+        let sunday = CronExpr::parse("0 0 * * 0").unwrap();
+        for expression in ["0 0 * * 7", "0 0 * * SUN", "0 0 * * sun"] {
+            assert_eq!(CronExpr::parse(expression).unwrap().days_of_week, sunday.days_of_week, "{expression}");
+        }
+
+        // 2024-09-02 是星期一
+        // 2024-09-02 is a Monday
+        assert_eq!(next("0 0 * * 7", date(2024, 9, 2), UtcOffset::UTC), Some(date(2024, 9, 8)));
+        assert_eq!(next("0 0 * * 6-7", date(2024, 9, 2), UtcOffset::UTC), Some(date(2024, 9, 7)));
+        assert_eq!(next("0 0 * * 6-7", date(2024, 9, 7), UtcOffset::UTC), Some(date(2024, 9, 8)));
+    }
+
+    #[test]
+    fn names_ranges_and_steps() {
+        let utc0 = UtcOffset::UTC;
+        assert_eq!(next("0 0 1 JAN-DEC/3 *", date(2024, 2, 1), utc0), Some(date(2024, 4, 1)));
+        assert_eq!(next("0 0 1 feb,aug *", date(2024, 3, 1), utc0), Some(date(2024, 8, 1)));
+        assert_eq!(next("0 0 * * mon-fri", date(2024, 9, 7), utc0), Some(date(2024, 9, 9)));
+
+        // 小时 9、13、17，分钟 0、20、40
+        // Hours 9, 13, 17 and minutes 0, 20, 40
+        let every = "*/20 9-17/4 * * *";
+        assert_eq!(next(every, utc(2024, 1, 1, 9, 5, 0), utc0), Some(utc(2024, 1, 1, 9, 20, 0)));
+        assert_eq!(next(every, utc(2024, 1, 1, 9, 40, 0), utc0), Some(utc(2024, 1, 1, 13, 0, 0)));
+        assert_eq!(next(every, utc(2024, 1, 1, 17, 45, 0), utc0), Some(utc(2024, 1, 2, 9, 0, 0)));
+
+        // "a/n" 从 a 开始直到最大值，6 字段格式带秒
+        // "a/n" runs from a up to the maximum, the 6-field format has seconds
+        assert_eq!(next("50/5 * * * * *", utc(2024, 1, 1, 0, 0, 56), utc0), Some(utc(2024, 1, 1, 0, 1, 50)));
+    }
+
+    #[test]
+    fn shorthands_expand_to_standard_expressions() {
+        let pairs = [
+            ("@yearly", "0 0 1 1 *"),
+            ("@annually", "0 0 1 1 *"),
+            ("@monthly", "0 0 1 * *"),
+            ("@weekly", "0 0 * * 0"),
+            ("@daily", "0 0 * * *"),
+            ("@midnight", "0 0 * * *"),
+            ("@hourly", "0 * * * *"),
+        ];
+        for (shorthand, expression) in pairs {
+            let from = utc(2024, 5, 17, 13, 30, 0);
+            assert_eq!(next(shorthand, from, UtcOffset::UTC), next(expression, from, UtcOffset::UTC), "{shorthand}");
+        }
+        assert_eq!(CronExpr::parse("@weekly").unwrap().as_str(), "@weekly");
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        assert_eq!(CronExpr::parse("* * * *"), Err(CronError::FieldCount(4)));
+        assert_eq!(CronExpr::parse("* * * * * * *"), Err(CronError::FieldCount(7)));
+        for (expression, field) in [
+            ("60 * * * *", "minute"),
+            ("* 24 * * *", "hour"),
+            ("* * 0 * *", "day-of-month"),
+            ("* * * 13 *", "month"),
+            ("* * * * 8", "day-of-week"),
+            ("*/0 * * * *", "minute"),
+            ("5-1 * * * *", "minute"),
+            ("* * * FOO *", "month"),
+        ] {
+            match CronExpr::parse(expression) {
+                Err(CronError::InvalidField { field: actual, .. }) => assert_eq!(actual, field, "{expression}"),
+                other => panic!("{expression} parsed as {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn utc_offset_rolls_over_day_boundaries() {
+        // 东八区 01:00 是前一天 UTC 17:00
+        // 01:00 at UTC+8 is 17:00 UTC on the previous day
+        let east = UtcOffset::from_hours(8);
+        assert_eq!(next("0 1 * * *", date(2024, 1, 1), east), Some(utc(2024, 1, 1, 17, 0, 0)));
+
+        // 西五区 23:00 是次日 UTC 04:00，本地日期仍是 2023-12-31
+        // 23:00 at UTC-5 is 04:00 UTC on the next day, the local date is still 2023-12-31
+        let west = UtcOffset::from_hours(-5);
+        assert_eq!(next("0 23 31 12 *", date(2024, 1, 1), west), Some(utc(2024, 1, 1, 4, 0, 0)));
+
+        // 星期按本地日期计算：UTC 2024-09-01 15:00 是东九区星期一零点
+        // The weekday follows the local date: 2024-09-01 15:00 UTC is Monday midnight at UTC+9
+        let tokyo = UtcOffset::from_hours(9);
+        assert_eq!(next("0 0 * * MON", utc(2024, 9, 1, 12, 0, 0), tokyo), Some(utc(2024, 9, 1, 15, 0, 0)));
+
+        // 非整小时偏移
+        // Offsets that are not whole hours
+        let india = UtcOffset::from_seconds(5 * 3600 + 1800);
+        assert_eq!(next("0 0 * * *", date(2024, 1, 1), india), Some(utc(2024, 1, 1, 18, 30, 0)));
+    }
+
+    // 每秒触发一次的任务，上一次计划触发时间为 now 之前 stall 秒
+    // Job firing every second whose previous planned fire was stall seconds before now
+    fn stalled_job(now: SystemTime, stall: u64) -> CronJob {
+        CronJob {
+            id: CronJobId(0),
+            name: String::from("every-second"),
+            expression: CronExpr::parse("* * * * * *").unwrap(),
+            next_run: Some(now - Duration::from_secs(stall)),
+            job: Arc::new(|| {}),
+        }
+    }
+
+    #[test]
+    fn skip_drops_missed_fires_after_a_stall() {
+        let now = utc(2024, 1, 1, 12, 0, 0);
+        let mut job = stalled_job(now, 10);
+
+        assert_eq!(job.take_due(now, UtcOffset::UTC, MissedFirePolicy::Skip, DEFAULT_MAX_CATCH_UP_RUNS), 0);
+        assert_eq!(job.next_run, Some(now + Duration::from_secs(1)));
+
+        // 按时的触发照常执行一次
+        // An on-time fire still runs once
+        let mut on_time = stalled_job(now, 0);
+        assert_eq!(on_time.take_due(now, UtcOffset::UTC, MissedFirePolicy::Skip, DEFAULT_MAX_CATCH_UP_RUNS), 1);
+        assert_eq!(on_time.next_run, Some(now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn catch_up_runs_every_missed_fire_after_a_stall() {
+        let now = utc(2024, 1, 1, 12, 0, 0);
+        let mut job = stalled_job(now, 10);
+
+        // now-10s 到 now 之间每秒一次，共 11 次
+        // Once per second from now-10s through now, 11 runs in total
+        assert_eq!(job.take_due(now, UtcOffset::UTC, MissedFirePolicy::CatchUp, DEFAULT_MAX_CATCH_UP_RUNS), 11);
+        assert_eq!(job.next_run, Some(now + Duration::from_secs(1)));
+        assert_eq!(job.take_due(now, UtcOffset::UTC, MissedFirePolicy::CatchUp, DEFAULT_MAX_CATCH_UP_RUNS), 0);
+    }
+
+    #[test]
+    fn catch_up_is_capped_after_a_long_stall() {
+        let now = utc(2024, 1, 1, 12, 0, 0);
+        let mut job = stalled_job(now, 86_400);
+
+        // 停顿一整天后只补执行上限次数，其余错过的触发被跳过
+        // After a day-long stall only the capped number of runs is caught up, the remaining missed fires are skipped
+        assert_eq!(job.take_due(now, UtcOffset::UTC, MissedFirePolicy::CatchUp, 5), 5);
+        assert_eq!(job.next_run, Some(now + Duration::from_secs(1)));
+        assert_eq!(job.take_due(now, UtcOffset::UTC, MissedFirePolicy::CatchUp, DEFAULT_MAX_CATCH_UP_RUNS), 0);
+    }
+
+    #[test]
+    fn scheduler_thread_is_named_after_the_pool_prefix() {
+        let pool = Arc::new(ThreadPool::builder().max_threads(1).thread_name_prefix("jobs").build());
+        let scheduler = CronScheduler::new(Arc::clone(&pool));
+        let name = scheduler.thread.as_ref().and_then(|thread| thread.thread().name().map(str::to_string));
+        assert_eq!(name.as_deref(), Some("jobs-cron"));
+        assert_eq!(scheduler.max_catch_up_runs(), DEFAULT_MAX_CATCH_UP_RUNS);
+    }
+
+    #[test]
+    fn job_that_can_never_fire_has_no_next_run() {
+        let now = utc(2024, 1, 1, 12, 0, 0);
+        let mut job = stalled_job(now, 0);
+        job.expression = CronExpr::parse("0 0 31 2 *").unwrap();
+
+        assert_eq!(job.take_due(now, UtcOffset::UTC, MissedFirePolicy::CatchUp, DEFAULT_MAX_CATCH_UP_RUNS), 1);
+        assert_eq!(job.next_run, None);
+    }
+}
@@ -0,0 +1,505 @@
+// 导入 Future（异步计算）
+// Import Future (asynchronous computation)
+use std::future::Future;
+
+// 导入 panic 模块和 AssertUnwindSafe（用于捕获 poll 中的 panic）
+// Import panic module and AssertUnwindSafe (used to catch panics inside poll)
+use std::panic::{self, AssertUnwindSafe};
+
+// 导入 Pin（固定内存位置）
+// Import Pin (pinned memory location)
+use std::pin::{pin, Pin};
+
+// 导入原子类型和内存排序
+// Import atomic types and memory orderings
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// 导入 Arc（原子引用计数）、Weak（弱引用）和 Mutex（互斥锁）
+// Import Arc (atomic reference counting), Weak (weak reference), and Mutex (mutual exclusion lock)
+use std::sync::{Arc, Mutex, Weak};
+
+// 导入异步上下文、轮询结果、Wake trait 和 Waker
+// Import async context, poll result, Wake trait and Waker
+use std::task::{Context, Poll, Wake, Waker};
+
+// 导入线程模块（block_on 使用线程挂起和唤醒）
+// Import thread module (block_on uses thread park and unpark)
+use std::thread::{self, Thread};
+
+// 导入任务错误类型和 panic 信息提取函数
+// Import task error type and panic message helper
+use crate::task_handle::{self, TaskError};
+
+// 导入线程池及其内部状态
+// Import thread pool and its internal state
+use crate::thread_pool::{Inner, ThreadPool};
+
+// Future 任务状态：空闲，等待唤醒
+// Future task state: idle, waiting to be woken
+const IDLE: u8 = 0;
+
+// Future 任务状态：已放入任务队列，等待轮询
+// Future task state: queued, waiting to be polled
+const SCHEDULED: u8 = 1;
+
+// Future 任务状态：正在轮询
+// Future task state: being polled
+const RUNNING: u8 = 2;
+
+// Future 任务状态：轮询期间被唤醒，轮询结束后需要重新放入队列
+// Future task state: woken while being polled, must be queued again once the poll returns
+const NOTIFIED: u8 = 3;
+
+// Future 任务状态：已完成或已被放弃
+// Future task state: completed or abandoned
+const DONE: u8 = 4;
+
+// 句柄端与 Future 任务共享的结果状态
+// Result state shared between the handle and the future task
+struct JoinState<T> {
+    // Future 的结果，取走后为 None
+    // Result of the future, None once taken
+    result: Option<Result<T, TaskError>>,
+
+    // 结果是否已被句柄取走
+    // Whether the handle has already taken the result
+    taken: bool,
+
+    // 等待结果的句柄所在任务的 Waker
+    // Waker of the task awaiting the handle
+    waker: Option<Waker>,
+}
+
+// Future 任务的句柄，本身是一个 Future，完成时得到 Future 的输出；
+// Future panic 时得到 TaskError::Panicked，未完成就被丢弃（例如线程池关闭）时得到 TaskError::Cancelled
+// Handle of a future task, itself a Future resolving to the future's output;
+// resolves to TaskError::Panicked if the future panicked and TaskError::Cancelled if it was dropped before completing (e.g. pool shut down)
+pub struct JoinHandle<T> {
+    // 与 Future 任务共享的结果状态
+    // Result state shared with the future task
+    shared: Arc<Mutex<JoinState<T>>>,
+}
+
+// Future 任务持有的结果写入器，被丢弃而未写入时自动报告取消
+// Result writer held by the future task, reports cancellation automatically if dropped without writing
+struct JoinCompleter<T> {
+    // 写入后置为 None，避免 Drop 时重复写入
+    // Set to None after writing, avoiding a second write on Drop
+    shared: Option<Arc<Mutex<JoinState<T>>>>,
+}
+
+impl<T> JoinCompleter<T> {
+    // 写入结果并唤醒等待句柄的任务
+    // Write the result and wake the task awaiting the handle
+    fn complete(mut self, result: Result<T, TaskError>) {
+        if let Some(shared) = self.shared.take() {
+            Self::store(&shared, result);
+        }
+    }
+
+    // 将结果存入共享状态，在锁外唤醒等待者
+    // Store the result into the shared state and wake the waiter outside the lock
+    fn store(shared: &Mutex<JoinState<T>>, result: Result<T, TaskError>) {
+        let waker = {
+            let mut state = shared.lock().expect("Failed to lock join handle mutex");
+            state.result = Some(result);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for JoinCompleter<T> {
+    fn drop(&mut self) {
+        // Future 未完成就被丢弃，报告取消，避免句柄永远等待
+        // Future dropped before completing, report cancellation so the handle never waits forever
+        if let Some(shared) = self.shared.take() {
+            Self::store(&shared, Err(TaskError::Cancelled));
+        }
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, TaskError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock().expect("Failed to lock join handle mutex");
+
+        // 完成后再次轮询属于调用方的使用错误
+        // Polling again after completion is a usage error by the caller
+        assert!(!state.taken, "JoinHandle polled after completion");
+
+        match state.result.take() {
+            Some(result) => {
+                state.taken = true;
+                Poll::Ready(result)
+            }
+            None => {
+                // 只在 Waker 变化时替换，避免不必要的克隆
+                // Only replace the waker when it changed, avoiding needless clones
+                if !state.waker.as_ref().is_some_and(|waker| waker.will_wake(cx.waker())) {
+                    state.waker = Some(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> JoinHandle<T> {
+    // Future 任务是否已经结束（完成、panic 或被取消）
+    // Whether the future task has finished (completed, panicked, or was cancelled)
+    pub fn is_finished(&self) -> bool {
+        let state = self.shared.lock().expect("Failed to lock join handle mutex");
+        state.taken || state.result.is_some()
+    }
+}
+
+// 包装提交的 Future，完成时把输出写入句柄，poll 发生 panic 时报告给句柄后继续传播
+// Wraps a spawned future, writing its output to the handle on completion, and reporting a panic in poll to the handle before propagating it
+struct Spawned<F: Future> {
+    // 用户提交的 Future
+    // Future submitted by the user
+    future: Pin<Box<F>>,
+
+    // 结果写入器，完成后为 None
+    // Result writer, None after completion
+    completer: Option<JoinCompleter<F::Output>>,
+}
+
+impl<F: Future> Future for Spawned<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Spawned 中的字段都是 Unpin 的，可以直接取得可变引用
+        // Every field of Spawned is Unpin, so a mutable reference can be taken directly
+        let this = &mut *self;
+        match panic::catch_unwind(AssertUnwindSafe(|| this.future.as_mut().poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(value)) => {
+                if let Some(completer) = this.completer.take() {
+                    completer.complete(Ok(value));
+                }
+                Poll::Ready(())
+            }
+            Err(payload) => {
+                if let Some(completer) = this.completer.take() {
+                    completer.complete(Err(TaskError::Panicked(task_handle::panic_message(&*payload))));
+                }
+
+                // 继续传播 panic，交由工作线程统计并调用 panic 处理器
+                // Keep propagating the panic so the worker counts it and calls the panic handler
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+}
+
+// 在线程池上运行的 Future 任务，同时也是它自己的 Waker：被唤醒时重新放入线程池的任务队列
+// Future task running on the pool, which is also its own waker: waking it puts it back into the pool's task queue
+struct FutureTask {
+    // 待轮询的 Future，完成或被放弃后为 None
+    // Future to poll, None once completed or abandoned
+    future: Mutex<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+
+    // 调度状态（IDLE / SCHEDULED / RUNNING / NOTIFIED / DONE）
+    // Scheduling state (IDLE / SCHEDULED / RUNNING / NOTIFIED / DONE)
+    state: AtomicU8,
+
+    // 线程池内部状态的弱引用，Waker 不会使线程池无法释放
+    // Weak reference to the pool internals, so wakers never keep the pool alive
+    pool: Weak<Inner>,
+
+    // 首次轮询的任务编号，再次轮询沿用该编号，观察者看到的是同一个任务
+    // Task id of the first poll, re-polls keep it so observers see a single task
+    task_id: usize,
+}
+
+impl FutureTask {
+    // 在工作线程上轮询一次 Future；仍未完成且轮询期间被唤醒时重新放入队列
+    // Poll the future once on a worker; if still pending and woken during the poll, queue it again
+    fn run(self: Arc<Self>) {
+        self.state.store(RUNNING, Ordering::SeqCst);
+
+        let mut future = self.future.lock().expect("Failed to lock future mutex");
+        let pending = match future.as_mut() {
+            Some(fut) => {
+                let waker = Waker::from(Arc::clone(&self));
+                let mut cx = Context::from_waker(&waker);
+
+                // 先在锁内捕获 panic 并清除 Future，释放锁后再继续传播，避免锁中毒
+                // Catch a panic under the lock and clear the future first, then propagate it after releasing the lock so the mutex is not poisoned
+                match panic::catch_unwind(AssertUnwindSafe(|| fut.as_mut().poll(&mut cx))) {
+                    Ok(poll) => poll.is_pending(),
+                    Err(payload) => {
+                        *future = None;
+                        self.state.store(DONE, Ordering::SeqCst);
+                        drop(future);
+                        panic::resume_unwind(payload);
+                    }
+                }
+            }
+            None => false,
+        };
+
+        if !pending {
+            *future = None;
+            self.state.store(DONE, Ordering::SeqCst);
+            return;
+        }
+        drop(future);
+
+        // 轮询期间没有被唤醒则回到空闲状态，否则重新放入队列
+        // Go back to idle if not woken during the poll, otherwise queue again
+        if self
+            .state
+            .compare_exchange(RUNNING, IDLE, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            self.state.store(SCHEDULED, Ordering::SeqCst);
+            self.schedule();
+        }
+    }
+
+    // 将任务作为再次轮询放入线程池的任务队列（不受队列容量限制，不计入已提交任务数）；
+    // 线程池已关闭或已释放时放弃该 Future，句柄得到 TaskError::Cancelled
+    // Put the task into the pool's task queue as a re-poll (ignoring capacity and not counted as submitted);
+    // once the pool is shut down or gone the future is abandoned and the handle gets TaskError::Cancelled
+    fn schedule(self: Arc<Self>) {
+        let queued = match self.pool.upgrade() {
+            Some(inner) => {
+                let task = Arc::clone(&self);
+                let mut job = inner.job_with_id(self.task_id, Box::new(move || task.run()));
+                job.repoll = true;
+                inner.enqueue_due(job)
+            }
+            None => false,
+        };
+
+        if !queued {
+            self.state.store(DONE, Ordering::SeqCst);
+
+            // 在锁外丢弃 Future，其析构中可能再次唤醒本任务
+            // Drop the future outside the lock, its destructor may wake this task again
+            let future = self.future.lock().expect("Failed to lock future mutex").take();
+            drop(future);
+        }
+    }
+}
+
+impl Wake for FutureTask {
+    fn wake(self: Arc<Self>) {
+        // 空闲时放入队列；正在轮询时标记为已通知，由轮询结束后重新放入；已在队列中或已完成时忽略
+        // Queue it when idle; mark it notified while being polled so it is queued after the poll; ignore when already queued or done
+        let mut state = self.state.load(Ordering::SeqCst);
+        loop {
+            let next = match state {
+                IDLE => SCHEDULED,
+                RUNNING => NOTIFIED,
+                _ => return,
+            };
+            match self.state.compare_exchange(state, next, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) if next == SCHEDULED => return self.schedule(),
+                Ok(_) => return,
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        Arc::clone(self).wake();
+    }
+}
+
+impl ThreadPool {
+    // 在线程池上运行 Future，返回的句柄本身是 Future，可以 await 或用 block_on 等待；
+    // Future 每次被唤醒时作为普通任务重新放入任务队列，两次轮询之间不占用工作线程
+    // Run a future on the pool, the returned handle is itself a future that can be awaited or waited on with block_on;
+    // every time the future is woken it re-enters the task queue as an ordinary task, so no worker is held between polls
+    pub fn spawn_future<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(JoinState { result: None, taken: false, waker: None }));
+        let spawned = Spawned {
+            future: Box::pin(future),
+            completer: Some(JoinCompleter { shared: Some(Arc::clone(&shared)) }),
+        };
+
+        let task_id = self.inner.next_task_id();
+        let task = Arc::new(FutureTask {
+            future: Mutex::new(Some(Box::pin(spawned))),
+            state: AtomicU8::new(SCHEDULED),
+            pool: Arc::downgrade(&self.inner),
+            task_id,
+        });
+
+        // 首次轮询作为普通任务提交，Future 只在此时计入已提交任务数；被拒绝时 Future 随任务丢弃，句柄得到 TaskError::Cancelled，因此忽略提交错误
+        // The first poll is submitted as an ordinary task, the only time the future counts as submitted; a rejected task drops the future and the handle gets TaskError::Cancelled, so the submit error is ignored
+        let _ = self.submit_job(self.inner.job_with_id(task_id, Box::new(move || task.run())));
+
+        JoinHandle { shared }
+    }
+}
+
+// 唤醒时恢复被挂起线程的 Waker
+// Waker that unparks the parked thread when woken
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// 在当前线程上阻塞运行 Future 直到完成；在工作线程上调用会占用该线程直到 Future 完成
+// Run a future to completion on the current thread, blocking it; calling it on a worker holds that worker until the future completes
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        // 唤醒可能发生在 park 之前，此时 park 会立即返回，因此不会丢失唤醒
+        // A wake may happen before park, in which case park returns immediately, so no wakeup is lost
+        thread::park();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observer::PoolObserver;
+    use crate::queue::Priority;
+    use crate::thread_pool::TaskInfo;
+    use std::sync::atomic::AtomicUsize;
+
+    // 在被轮询时立即唤醒自己并返回 Pending，共让出指定次数后完成
+    // Wakes itself during the poll and returns Pending, completing after yielding the given number of times
+    struct YieldNow(usize);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 == 0 {
+                return Poll::Ready(());
+            }
+            self.0 -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    // 记录提交次数和每次开始执行的任务编号
+    // Records submissions and the task id of every run
+    #[derive(Clone, Default)]
+    struct Recorder {
+        submitted: Arc<AtomicUsize>,
+        started: Arc<Mutex<Vec<usize>>>,
+    }
+
+    impl PoolObserver for Recorder {
+        fn on_task_submitted(&self, _task_id: usize, _priority: Priority) {
+            self.submitted.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_task_started(&self, task: &TaskInfo) {
+            self.started.lock().unwrap().push(task.task_id);
+        }
+    }
+
+    #[test]
+    fn wake_during_poll_requeues_without_counting_a_new_task() {
+        let recorder = Recorder::default();
+        let pool = ThreadPool::builder().max_threads(2).observer(recorder.clone()).build();
+
+        // 每次轮询期间唤醒自己，任务经 NOTIFIED 状态在轮询结束后重新入队
+        // Waking itself during every poll sends the task through NOTIFIED and back into the queue once the poll returns
+        let handle = pool.spawn_future(async {
+            YieldNow(3).await;
+            7
+        });
+        assert_eq!(block_on(handle), Ok(7));
+        pool.wait_for_completion();
+
+        // 四次轮询属于同一个任务，只提交和完成一次
+        // The four polls belong to one task, submitted and completed once
+        assert_eq!(pool.submitted_tasks(), 1);
+        assert_eq!(pool.completed_tasks(), 1);
+        assert_eq!(recorder.submitted.load(Ordering::SeqCst), 1);
+        let started = recorder.started.lock().unwrap().clone();
+        assert_eq!(started.len(), 4);
+        assert!(started.iter().all(|&id| id == started[0]));
+    }
+
+    #[test]
+    fn panicking_future_resolves_to_panicked() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        let handle = pool.spawn_future(async {
+            YieldNow(1).await;
+            panic!("future failed");
+        });
+        assert_eq!(block_on(handle), Err(TaskError::Panicked("future failed".to_string())));
+        pool.wait_for_completion();
+
+        // 工作线程存活，后续 Future 照常执行
+        // The worker survives and later futures still run
+        assert_eq!(block_on(pool.spawn_future(async { 1 })), Ok(1));
+    }
+
+    #[test]
+    fn pending_future_resolves_to_cancelled_after_shutdown() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        let slot: Arc<Mutex<Option<Waker>>> = Arc::new(Mutex::new(None));
+
+        // 保存 Waker 后一直挂起，直到线程池关闭后才被唤醒
+        // Stores its waker and stays pending until woken after the pool has shut down
+        let stored = Arc::clone(&slot);
+        let handle = pool.spawn_future(std::future::poll_fn(move |cx| {
+            *stored.lock().unwrap() = Some(cx.waker().clone());
+            Poll::<()>::Pending
+        }));
+        while slot.lock().unwrap().is_none() {
+            thread::yield_now();
+        }
+        pool.wait_idle();
+        assert!(!handle.is_finished());
+
+        // 关闭后唤醒无法重新入队，Future 被放弃
+        // After shutdown the wake cannot requeue the task, so the future is abandoned
+        pool.shutdown();
+        slot.lock().unwrap().take().unwrap().wake();
+        assert!(handle.is_finished());
+        assert_eq!(block_on(handle), Err(TaskError::Cancelled));
+    }
+
+    #[test]
+    fn handles_can_be_blocked_on_and_awaited() {
+        let pool = Arc::new(ThreadPool::builder().max_threads(2).build());
+
+        // 一个 Future 等待另一个 Future 的句柄，外层句柄由 block_on 等待
+        // One future awaits another's handle, and block_on waits for the outer handle
+        let inner_pool = Arc::clone(&pool);
+        let outer = pool.spawn_future(async move {
+            let inner = inner_pool.spawn_future(async {
+                YieldNow(2).await;
+                20
+            });
+            inner.await.map(|value| value + 1)
+        });
+        assert_eq!(block_on(outer), Ok(Ok(21)));
+        assert_eq!(block_on(async { 5 }), 5);
+    }
+}
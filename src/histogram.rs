@@ -0,0 +1,439 @@
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入原子类型和内存排序
+// Import atomic types and memory orderings
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 每个2的幂区间再线性划分的子桶数的位数（8个子桶，相对误差不超过 12.5%）
+// Bits of linear sub-buckets within every power-of-two range (8 sub-buckets, relative error at most 12.5%)
+const SUB_BITS: u32 = 3;
+
+// 每个2的幂区间的子桶数
+// Sub-buckets per power-of-two range
+const SUB_BUCKETS: u64 = 1 << SUB_BITS;
+
+// 可记录的最大值的位数（纳秒），约 18 分钟，更大的值记入最后一个桶
+// Bits of the largest recordable value (nanoseconds), about 18 minutes, larger values land in the last bucket
+const MAX_BITS: u32 = 40;
+
+// 桶的总数：小于 SUB_BUCKETS 的值各占一个桶，之后每个2的幂区间占 SUB_BUCKETS 个桶
+// Total bucket count: values below SUB_BUCKETS get one bucket each, then every power-of-two range gets SUB_BUCKETS buckets
+const BUCKETS: usize = ((MAX_BITS - SUB_BITS + 1) as usize) * SUB_BUCKETS as usize;
+
+// 滑动窗口划分的时间片数，窗口每经过一个时间片就丢弃最旧的时间片
+// Number of time slices in the sliding window, the oldest slice is dropped every time one slice elapses
+const SLICES: usize = 6;
+
+// 导出累计分布时最小边界的位数（纳秒），约 1 微秒
+// Bits of the smallest boundary when exporting the cumulative distribution (nanoseconds), about 1 microsecond
+const EXPORT_MIN_BITS: u32 = 10;
+
+// 计算值所在的桶
+// Compute the bucket of a value
+fn bucket_index(value: u64) -> usize {
+    let value = value.min((1 << MAX_BITS) - 1);
+    if value < SUB_BUCKETS {
+        return value as usize;
+    }
+
+    // 最高位决定所在的2的幂区间，其后 SUB_BITS 位决定区间内的子桶
+    // The highest bit picks the power-of-two range, the next SUB_BITS bits pick the sub-bucket within it
+    let exponent = 63 - value.leading_zeros();
+    let mantissa = (value >> (exponent - SUB_BITS)) & (SUB_BUCKETS - 1);
+    ((exponent - SUB_BITS + 1) as u64 * SUB_BUCKETS + mantissa) as usize
+}
+
+// 桶内的最大值
+// Largest value within a bucket
+fn bucket_upper_bound(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return index;
+    }
+    let shift = (index / SUB_BUCKETS - 1) as u32;
+    let lower = (SUB_BUCKETS + index % SUB_BUCKETS) << shift;
+    lower + (1 << shift) - 1
+}
+
+// 滑动窗口中的一个时间片
+// One time slice of the sliding window
+struct Slice {
+    // 时间片编号（从直方图创建起经过的时间片数加一），0 表示未使用或已重置
+    // Slice number (slices elapsed since the histogram was created, plus one), 0 means unused or reset
+    epoch: AtomicU64,
+
+    // 各桶的计数
+    // Count of every bucket
+    buckets: [AtomicU64; BUCKETS],
+
+    // 时间片内记录的最大值（纳秒）
+    // Largest value recorded within the slice (nanoseconds)
+    max: AtomicU64,
+}
+
+impl Slice {
+    // 清空时间片的计数
+    // Clear the counts of the slice
+    fn clear(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.max.store(0, Ordering::Relaxed);
+    }
+}
+
+// 固定桶的对数线性直方图，覆盖一个滑动时间窗口；记录只做原子操作，从不分配内存也不加锁；
+// 时间片切换与并发记录之间不做同步，切换瞬间的少量记录可能丢失，对延迟统计来说可以接受
+// Log-linear histogram with fixed buckets covering a sliding time window; recording only does atomic operations and never allocates or locks;
+// slice rotation is not synchronized with concurrent recording, so a few records around a rotation may be lost, which is acceptable for latency statistics
+pub(crate) struct LatencyHistogram {
+    // 环形排列的时间片
+    // Time slices arranged as a ring
+    slices: Box<[Slice]>,
+
+    // 每个时间片的长度（纳秒）
+    // Length of every slice (nanoseconds)
+    slice_nanos: u64,
+
+    // 创建时间，时间片编号从此开始计算
+    // Creation time, slice numbers are counted from here
+    started_at: Instant,
+
+    // 生命周期内各桶的计数，不受滑动窗口和 reset 影响，用于导出单调递增的累计分布
+    // Bucket counts over the lifetime, unaffected by the sliding window and reset, used to export a monotonic cumulative distribution
+    lifetime: Box<[AtomicU64; BUCKETS]>,
+
+    // 生命周期内记录的总时长（纳秒）
+    // Total duration recorded over the lifetime (nanoseconds)
+    lifetime_sum: AtomicU64,
+}
+
+// 生命周期内的累计分布，按 Prometheus 直方图的形式组织
+// Cumulative distribution over the lifetime, organized like a Prometheus histogram
+pub(crate) struct Cumulative {
+    // 各边界及不大于该边界的记录数（与 Prometheus 的 le 含义一致），边界为每个2的幂的前一纳秒
+    // Every boundary with the number of records at or below it (matching Prometheus' le), boundaries are one nanosecond below every power of two
+    pub(crate) buckets: Vec<(Duration, u64)>,
+
+    // 记录总数
+    // Total number of records
+    pub(crate) count: u64,
+
+    // 记录的总时长
+    // Total recorded duration
+    pub(crate) sum: Duration,
+}
+
+impl LatencyHistogram {
+    // 创建覆盖指定窗口的直方图
+    // Create a histogram covering the given window
+    pub(crate) fn new(window: Duration) -> Self {
+        let slices = (0..SLICES)
+            .map(|_| Slice {
+                epoch: AtomicU64::new(0),
+                buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+                max: AtomicU64::new(0),
+            })
+            .collect();
+
+        LatencyHistogram {
+            slices,
+            slice_nanos: (window.as_nanos() / SLICES as u128).max(1) as u64,
+            started_at: Instant::now(),
+            lifetime: Box::new(std::array::from_fn(|_| AtomicU64::new(0))),
+            lifetime_sum: AtomicU64::new(0),
+        }
+    }
+
+    // 当前时间片的编号
+    // Number of the current slice
+    fn current_epoch(&self) -> u64 {
+        (self.started_at.elapsed().as_nanos() / self.slice_nanos as u128) as u64 + 1
+    }
+
+    // 记录一个时间段
+    // Record one duration
+    pub(crate) fn record(&self, duration: Duration) {
+        let nanos = duration.as_nanos().min(u64::MAX as u128) as u64;
+        let epoch = self.current_epoch();
+        let slice = &self.slices[(epoch % SLICES as u64) as usize];
+
+        // 时间片属于更早的一轮时，由抢到编号的线程清空后再使用
+        // When the slice belongs to an earlier round, the thread winning the epoch clears it before use
+        let seen = slice.epoch.load(Ordering::Acquire);
+        if seen < epoch
+            && slice
+                .epoch
+                .compare_exchange(seen, epoch, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            slice.clear();
+        }
+
+        let index = bucket_index(nanos);
+        slice.buckets[index].fetch_add(1, Ordering::Relaxed);
+        slice.max.fetch_max(nanos, Ordering::Relaxed);
+
+        self.lifetime[index].fetch_add(1, Ordering::Relaxed);
+        self.lifetime_sum.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    // 清空所有时间片，生命周期内的累计分布保持不变
+    // Clear every slice, the cumulative distribution over the lifetime is kept
+    pub(crate) fn reset(&self) {
+        for slice in self.slices.iter() {
+            slice.epoch.store(0, Ordering::Release);
+            slice.clear();
+        }
+    }
+
+    // 生命周期内的累计分布；边界是2的幂区间起点的前一纳秒，恰好是上一个桶的上界，因此各边界处的计数是精确的
+    // Cumulative distribution over the lifetime; boundaries are one nanosecond below the start of a power-of-two range, exactly the upper bound of the previous bucket, so the count at every boundary is exact
+    pub(crate) fn cumulative(&self) -> Cumulative {
+        let counts: Vec<u64> = self.lifetime.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect();
+        let buckets = (EXPORT_MIN_BITS..MAX_BITS)
+            .map(|bits| {
+                let first = ((bits - SUB_BITS + 1) as u64 * SUB_BUCKETS) as usize;
+                (Duration::from_nanos(bucket_upper_bound(first - 1)), counts[..first].iter().sum())
+            })
+            .collect();
+
+        Cumulative {
+            buckets,
+            count: counts.iter().sum(),
+            sum: Duration::from_nanos(self.lifetime_sum.load(Ordering::Relaxed)),
+        }
+    }
+
+    // 汇总窗口内的记录并计算分位数
+    // Aggregate the records within the window and compute the percentiles
+    pub(crate) fn summary(&self) -> LatencySummary {
+        let epoch = self.current_epoch();
+        let mut counts = [0u64; BUCKETS];
+        let mut max = 0;
+
+        // 只汇总仍在窗口内的时间片
+        // Only aggregate slices still within the window
+        for slice in self.slices.iter() {
+            let slice_epoch = slice.epoch.load(Ordering::Acquire);
+            if slice_epoch == 0 || slice_epoch + (SLICES as u64) <= epoch {
+                continue;
+            }
+            for (count, bucket) in counts.iter_mut().zip(slice.buckets.iter()) {
+                *count += bucket.load(Ordering::Relaxed);
+            }
+            max = max.max(slice.max.load(Ordering::Relaxed));
+        }
+
+        let total: u64 = counts.iter().sum();
+
+        // 分位数取所在桶的上界，并且不超过实际最大值
+        // A percentile is the upper bound of its bucket, capped at the actual maximum
+        let percentile = |quantile: f64| {
+            if total == 0 {
+                return Duration::ZERO;
+            }
+            let rank = ((total as f64 * quantile).ceil() as u64).max(1);
+            let mut seen = 0;
+            for (index, count) in counts.iter().enumerate() {
+                seen += count;
+                if seen >= rank {
+                    return Duration::from_nanos(bucket_upper_bound(index).min(max));
+                }
+            }
+            Duration::from_nanos(max)
+        };
+
+        LatencySummary {
+            count: total,
+            p50: percentile(0.50),
+            p90: percentile(0.90),
+            p99: percentile(0.99),
+            max: Duration::from_nanos(max),
+        }
+    }
+}
+
+// 滑动窗口内延迟分布的摘要；分位数的相对误差不超过 12.5%
+// Summary of the latency distribution within the sliding window; percentiles have at most 12.5% relative error
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencySummary {
+    // 窗口内的记录数
+    // Number of records within the window
+    pub count: u64,
+
+    // 中位数
+    // Median
+    pub p50: Duration,
+
+    // 90 分位数
+    // 90th percentile
+    pub p90: Duration,
+
+    // 99 分位数
+    // 99th percentile
+    pub p99: Duration,
+
+    // 最大值
+    // Maximum
+    pub max: Duration,
+}
+
+impl fmt::Display for LatencySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "p50 {:?}, p90 {:?}, p99 {:?}, max {:?} (n={})",
+            self.p50, self.p90, self.p99, self.max, self.count
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    // 分位数不小于真实值，且相对误差不超过 12.5%
+    // The percentile is not below the exact value and within 12.5% relative error
+    fn assert_within_error(reported: Duration, exact: Duration) {
+        assert!(reported >= exact, "{:?} is below {:?}", reported, exact);
+        assert!(
+            reported.as_nanos() * 8 <= exact.as_nanos() * 9,
+            "{:?} is more than 12.5% above {:?}",
+            reported,
+            exact
+        );
+    }
+
+    #[test]
+    fn buckets_are_exact_below_sub_buckets_and_contiguous_above() {
+        for value in 0..SUB_BUCKETS {
+            assert_eq!(bucket_index(value), value as usize);
+            assert_eq!(bucket_upper_bound(value as usize), value);
+        }
+
+        // 2^SUB_BITS 是第一个2的幂区间的起点
+        // 2^SUB_BITS starts the first power-of-two range
+        assert_eq!(bucket_index(SUB_BUCKETS - 1), SUB_BUCKETS as usize - 1);
+        assert_eq!(bucket_index(SUB_BUCKETS), SUB_BUCKETS as usize);
+        assert_eq!(bucket_index(2 * SUB_BUCKETS - 1), 2 * SUB_BUCKETS as usize - 1);
+        assert_eq!(bucket_index(2 * SUB_BUCKETS), 2 * SUB_BUCKETS as usize);
+
+        // 每个桶的上界加一恰好落在下一个桶，桶之间没有空隙也没有重叠
+        // Every bucket's upper bound plus one lands in the next bucket, with no gaps or overlaps
+        for index in 0..BUCKETS - 1 {
+            let upper = bucket_upper_bound(index);
+            assert_eq!(bucket_index(upper), index);
+            assert_eq!(bucket_index(upper + 1), index + 1);
+        }
+    }
+
+    #[test]
+    fn values_beyond_max_bits_are_clamped_to_last_bucket() {
+        let last = BUCKETS - 1;
+        assert_eq!(bucket_upper_bound(last), (1 << MAX_BITS) - 1);
+        assert_eq!(bucket_index((1 << MAX_BITS) - 1), last);
+        assert_eq!(bucket_index(1 << MAX_BITS), last);
+        assert_eq!(bucket_index(u64::MAX), last);
+
+        // 分位数被限制在最后一个桶的上界，最大值保持精确
+        // Percentiles are capped at the last bucket's upper bound, the maximum stays exact
+        let histogram = LatencyHistogram::new(Duration::from_secs(60));
+        histogram.record(Duration::from_secs(3600));
+        let summary = histogram.summary();
+        assert_eq!(summary.p99, Duration::from_nanos((1 << MAX_BITS) - 1));
+        assert_eq!(summary.max, Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn bucket_upper_bound_is_within_relative_error() {
+        let mut value = 1;
+        while value < 1 << MAX_BITS {
+            let upper = bucket_upper_bound(bucket_index(value));
+            assert!(upper >= value);
+            assert!((upper - value) * SUB_BUCKETS <= value, "value {} bucket bound {}", value, upper);
+            value = value * 9 / 8 + 1;
+        }
+    }
+
+    #[test]
+    fn percentiles_are_within_stated_error() {
+        let histogram = LatencyHistogram::new(Duration::from_secs(60));
+        for micros in 1..=1000 {
+            histogram.record(Duration::from_micros(micros));
+        }
+
+        let summary = histogram.summary();
+        assert_eq!(summary.count, 1000);
+        assert_within_error(summary.p50, Duration::from_micros(500));
+        assert_within_error(summary.p90, Duration::from_micros(900));
+        assert_within_error(summary.p99, Duration::from_micros(990));
+        assert_eq!(summary.max, Duration::from_micros(1000));
+    }
+
+    #[test]
+    fn percentiles_do_not_exceed_max() {
+        let histogram = LatencyHistogram::new(Duration::from_secs(60));
+        histogram.record(Duration::from_nanos(1001));
+        let summary = histogram.summary();
+        assert_eq!(summary.p50, Duration::from_nanos(1001));
+        assert_eq!(summary.p99, Duration::from_nanos(1001));
+        assert_eq!(LatencyHistogram::new(Duration::from_secs(1)).summary(), LatencySummary::default());
+    }
+
+    #[test]
+    fn old_samples_expire_after_window() {
+        let histogram = LatencyHistogram::new(Duration::from_millis(60));
+        histogram.record(Duration::from_millis(5));
+        assert_eq!(histogram.summary().count, 1);
+
+        // 超过整个窗口之后，旧的时间片不再计入
+        // After the whole window has passed, the old slice no longer counts
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(histogram.summary(), LatencySummary::default());
+
+        // 新的记录重新使用环中的时间片
+        // New records reuse slices of the ring
+        histogram.record(Duration::from_millis(1));
+        let summary = histogram.summary();
+        assert_eq!(summary.count, 1);
+        assert_eq!(summary.max, Duration::from_millis(1));
+    }
+
+    #[test]
+    fn cumulative_counts_include_the_boundary() {
+        let histogram = LatencyHistogram::new(Duration::from_secs(60));
+        let first = Duration::from_nanos((1 << EXPORT_MIN_BITS) - 1);
+        histogram.record(first);
+        histogram.record(first + Duration::from_nanos(1));
+
+        // 恰好等于边界的值计入该边界，大一纳秒的值计入下一个边界
+        // A value equal to a boundary counts towards it, one nanosecond more counts towards the next boundary
+        let cumulative = histogram.cumulative();
+        assert_eq!(cumulative.buckets[0], (first, 1));
+        assert_eq!(cumulative.buckets[1], (Duration::from_nanos((1 << (EXPORT_MIN_BITS + 1)) - 1), 2));
+        assert_eq!(cumulative.buckets.last().map(|bucket| bucket.1), Some(2));
+        assert_eq!(cumulative.count, 2);
+    }
+
+    #[test]
+    fn reset_clears_window_but_keeps_lifetime() {
+        let histogram = LatencyHistogram::new(Duration::from_secs(60));
+        histogram.record(Duration::from_millis(2));
+        histogram.record(Duration::from_millis(3));
+        histogram.reset();
+
+        assert_eq!(histogram.summary(), LatencySummary::default());
+        let cumulative = histogram.cumulative();
+        assert_eq!(cumulative.count, 2);
+        assert_eq!(cumulative.sum, Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,204 @@
+// 导入 HashMap（按任务名计数）
+// Import HashMap (counts per task name)
+use std::collections::HashMap;
+
+// 导入 Arc（原子引用计数）和 Mutex（互斥锁）
+// Import Arc (atomic reference counting) and Mutex (mutual exclusion lock)
+use std::sync::{Arc, Mutex};
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 某个工作线程当前状态的快照；工作线程空闲时除 worker_id 外的字段均为 None
+// Snapshot of what a worker is doing; every field except worker_id is None while the worker is idle
+#[derive(Debug, Clone)]
+pub struct RunningTask {
+    // 工作线程 ID
+    // Worker thread ID
+    pub worker_id: usize,
+
+    // 正在执行的任务编号，空闲时为 None
+    // Id of the task being executed, None while idle
+    pub task_id: Option<usize>,
+
+    // 提交时指定的任务名，空闲或任务未命名时为 None
+    // Task name given at submission, None while idle or for unnamed tasks
+    pub name: Option<Arc<str>>,
+
+    // 开始执行的时间，空闲时为 None
+    // Time the task started executing, None while idle
+    pub started_at: Option<Instant>,
+
+    // 截至快照时已执行的时长，空闲时为 None
+    // Time spent executing as of the snapshot, None while idle
+    pub elapsed: Option<Duration>,
+}
+
+impl RunningTask {
+    // 工作线程是否空闲
+    // Whether the worker is idle
+    pub fn is_idle(&self) -> bool {
+        self.task_id.is_none()
+    }
+}
+
+// 正在执行的任务，由工作线程写入自己的状态槽
+// Task being executed, written by the worker into its own status slot
+pub(crate) struct CurrentTask {
+    // 任务编号
+    // Task id
+    task_id: usize,
+
+    // 任务名
+    // Task name
+    name: Option<Arc<str>>,
+
+    // 开始执行的时间
+    // Time the task started executing
+    started_at: Instant,
+}
+
+// 工作线程的状态槽：每个工作线程只写入自己的槽，执行任务时不会与其他工作线程争用同一把锁
+// Status slot of a worker: every worker only writes its own slot, so running tasks never contend on a lock shared with other workers
+pub(crate) struct WorkerStatus {
+    // 工作线程 ID
+    // Worker thread ID
+    pub(crate) worker_id: usize,
+
+    // 正在执行的任务，空闲时为 None
+    // Task being executed, None while idle
+    current: Mutex<Option<CurrentTask>>,
+}
+
+impl WorkerStatus {
+    // 创建空闲的状态槽
+    // Create an idle status slot
+    pub(crate) fn new(worker_id: usize) -> Self {
+        WorkerStatus { worker_id, current: Mutex::new(None) }
+    }
+
+    // 记录任务开始执行，返回之前正在执行的任务（任务内通过 join 帮助执行其他任务时存在），结束时交给 end 恢复
+    // Record that a task started, returning the task previously running (present when a task helps with others through join), to be restored by end
+    pub(crate) fn begin(&self, task_id: usize, name: Option<Arc<str>>, started_at: Instant) -> Option<CurrentTask> {
+        self.current
+            .lock()
+            .expect("Failed to lock worker status mutex")
+            .replace(CurrentTask { task_id, name, started_at })
+    }
+
+    // 记录任务执行结束，恢复之前正在执行的任务
+    // Record that the task finished, restoring the task previously running
+    pub(crate) fn end(&self, previous: Option<CurrentTask>) {
+        *self.current.lock().expect("Failed to lock worker status mutex") = previous;
+    }
+
+    // 工作线程状态的快照，空闲时只有 worker_id
+    // Snapshot of the worker's state, only worker_id is set while idle
+    pub(crate) fn snapshot(&self) -> RunningTask {
+        let current = self.current.lock().expect("Failed to lock worker status mutex");
+        RunningTask {
+            worker_id: self.worker_id,
+            task_id: current.as_ref().map(|task| task.task_id),
+            name: current.as_ref().and_then(|task| task.name.clone()),
+            started_at: current.as_ref().map(|task| task.started_at),
+            elapsed: current.as_ref().map(|task| task.started_at.elapsed()),
+        }
+    }
+}
+
+// 排队中的命名任务按名称计数，未命名的任务不经过这里
+// Count of queued named tasks per name, unnamed tasks never go through here
+pub(crate) struct QueuedNames {
+    // 任务名到排队数量的映射，数量降为0时移除
+    // Map from task name to queued count, removed when the count drops to 0
+    counts: Mutex<HashMap<Arc<str>, usize>>,
+}
+
+impl QueuedNames {
+    // 创建空的计数
+    // Create empty counts
+    pub(crate) fn new() -> Self {
+        QueuedNames { counts: Mutex::new(HashMap::new()) }
+    }
+
+    // 命名任务入队
+    // A named task was queued
+    pub(crate) fn add(&self, name: &Arc<str>) {
+        *self
+            .counts
+            .lock()
+            .expect("Failed to lock queued names mutex")
+            .entry(Arc::clone(name))
+            .or_insert(0) += 1;
+    }
+
+    // 命名任务离开队列（被取出执行或被移除）
+    // A named task left the queue (taken to run or removed)
+    pub(crate) fn remove(&self, name: &Arc<str>) {
+        let mut counts = self.counts.lock().expect("Failed to lock queued names mutex");
+        if let Some(count) = counts.get_mut(name) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(name);
+            }
+        }
+    }
+
+    // 各任务名当前的排队数量
+    // Current queued count of every task name
+    pub(crate) fn snapshot(&self) -> Vec<(Arc<str>, usize)> {
+        self.counts
+            .lock()
+            .expect("Failed to lock queued names mutex")
+            .iter()
+            .map(|(name, count)| (Arc::clone(name), *count))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::builder::ThreadPoolBuilder;
+
+    #[test]
+    fn running_tasks_lists_busy_and_idle_workers() {
+        let pool = ThreadPoolBuilder::new().core_threads(2).max_threads(2).prestart(true).build();
+        let (release, blocked) = mpsc::channel::<()>();
+        let (started, running) = mpsc::channel();
+        pool.submit_named("blocker", move || {
+            started.send(()).unwrap();
+            let _ = blocked.recv();
+        })
+        .unwrap();
+        running.recv().unwrap();
+
+        // 两个工作线程都登记了状态槽后再取快照
+        // Take the snapshot once both workers have registered their status slots
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let tasks = loop {
+            let tasks = pool.running_tasks();
+            if tasks.len() == 2 || Instant::now() > deadline {
+                break tasks;
+            }
+            thread::yield_now();
+        };
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks[0].worker_id < tasks[1].worker_id);
+
+        let busy: Vec<_> = tasks.iter().filter(|task| !task.is_idle()).collect();
+        assert_eq!(busy.len(), 1);
+        assert_eq!(busy[0].name.as_deref(), Some("blocker"));
+        assert!(busy[0].started_at.is_some() && busy[0].elapsed.is_some());
+
+        let idle: Vec<_> = tasks.iter().filter(|task| task.is_idle()).collect();
+        assert_eq!(idle.len(), 1);
+        assert!(idle[0].name.is_none() && idle[0].started_at.is_none() && idle[0].elapsed.is_none());
+
+        release.send(()).unwrap();
+    }
+}
@@ -0,0 +1,228 @@
+// 导入 panic 模块和 AssertUnwindSafe（用于捕获闭包 panic）
+// Import panic module and AssertUnwindSafe (used to catch closure panics)
+use std::panic::{self, AssertUnwindSafe};
+
+// 导入 Arc（原子引用计数）、Mutex（互斥锁）和 Condvar（条件变量）
+// Import Arc (atomic reference counting), Mutex (mutual exclusion lock), and Condvar (condition variable)
+use std::sync::{Arc, Condvar, Mutex};
+
+// 导入线程模块（使用 thread::Result 保存 panic 载荷）
+// Import thread module (thread::Result holds panic payloads)
+use std::thread;
+
+// 导入线程池
+// Import thread pool
+use crate::thread_pool::ThreadPool;
+
+// 交给线程池的闭包及其结果，由调用者和线程池任务共享
+// Closure handed to the pool and its result, shared by the caller and the pool task
+struct JoinState<B, R> {
+    // 待执行的闭包，被线程池任务或调用者取走后为 None
+    // Closure to execute, None once taken by the pool task or the caller
+    task: Mutex<Option<B>>,
+
+    // 闭包的执行结果，panic 时保存载荷
+    // Result of the closure, holding the payload if it panicked
+    result: Mutex<Option<thread::Result<R>>>,
+
+    // 结果写入后通知调用者的条件变量
+    // Condition variable notifying the caller once the result is written
+    done: Condvar,
+}
+
+impl<B, R> JoinState<B, R>
+where
+    B: FnOnce() -> R,
+{
+    // 取走闭包并执行、保存结果；闭包已被取走时什么也不做
+    // Take the closure, run it and store the result; does nothing if the closure was already taken
+    fn run(&self) {
+        let task = self.task.lock().expect("Failed to lock join task mutex").take();
+        if let Some(task) = task {
+            let result = panic::catch_unwind(AssertUnwindSafe(task));
+            *self.result.lock().expect("Failed to lock join result mutex") = Some(result);
+            self.done.notify_all();
+        }
+    }
+
+    // 结果是否已写入
+    // Whether the result has been written
+    fn is_done(&self) -> bool {
+        self.result.lock().expect("Failed to lock join result mutex").is_some()
+    }
+
+    // 阻塞等待并取出结果
+    // Block until the result is available and take it
+    fn wait(&self) -> thread::Result<R> {
+        let mut result = self.result.lock().expect("Failed to lock join result mutex");
+        loop {
+            if let Some(result) = result.take() {
+                return result;
+            }
+            result = self.done.wait(result).expect("Condvar wait failed");
+        }
+    }
+}
+
+impl ThreadPool {
+    // 并行执行两个闭包并返回两者的结果：a 在当前线程上执行，b 提交到线程池；
+    // 若 a 结束时 b 仍未开始，则由当前线程直接执行 b，否则在工作线程上一边执行其他排队任务一边等待 b，
+    // 因此在任务中递归调用 join 不会因线程数达到上限而死锁；两个闭包都结束后才返回，任一闭包的 panic 在返回时重新抛出（a 优先）
+    // Run two closures in parallel and return both results: a runs on the current thread, b is submitted to the pool;
+    // if b has not started when a finishes, the current thread runs b itself, otherwise a worker keeps executing other queued tasks while waiting for b,
+    // so recursive joins inside tasks cannot deadlock once the pool reaches max threads; returns only after both closures finished, re-throwing a panic from either one (a first)
+    pub fn join<A, B, RA, RB>(&self, a: A, b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA,
+        B: FnOnce() -> RB + Send,
+        RB: Send,
+    {
+        let state = Arc::new(JoinState {
+            task: Mutex::new(Some(b)),
+            result: Mutex::new(None),
+            done: Condvar::new(),
+        });
+
+        let remote = Arc::clone(&state);
+        let job: Box<dyn FnOnce() + Send + '_> = Box::new(move || remote.run());
+
+        // SAFETY: join 返回之前闭包 b 一定已被执行完毕（由线程池任务或调用者），且结果已被取出，
+        // 此后仍在队列中的任务只持有空的状态，执行时什么也不做，丢弃时也不会访问 b 借用的数据，因此可以擦除为 'static
+        // SAFETY: before join returns, b has always finished running (on the pool or on the caller) and its result has been taken,
+        // so a task still queued afterwards only holds an empty state that does nothing when run and touches none of b's borrows when dropped, which makes erasing to 'static sound
+        let job: Box<dyn FnOnce() + Send + 'static> = unsafe { std::mem::transmute(job) };
+
+        // 不等待队列空位：队列已满或线程池已关闭时 b 留在状态中，稍后由调用者直接执行，因此忽略提交错误
+        // Never wait for queue space: with a full queue or a shut down pool b stays in the state for the caller to run later, so the submit error is ignored
+        let _ = self.try_submit(job);
+
+        // 在当前线程上执行 a，即使 a panic 也必须等待 b 结束
+        // Run a on the current thread, b must be waited for even if a panics
+        let result_a = panic::catch_unwind(AssertUnwindSafe(a));
+
+        // b 仍未被取走时直接在当前线程上执行（相当于从队列中收回），否则帮助执行其他任务直到 b 结束或无任务可做
+        // Run b right here if it has not been taken yet (as if reclaimed from the queue), otherwise help with other tasks until b finishes or nothing is left
+        state.run();
+        while !state.is_done() && self.help_one() {}
+        let result_b = state.wait();
+
+        match (result_a, result_b) {
+            (Ok(value_a), Ok(value_b)) => (value_a, value_b),
+            (Err(payload), _) | (_, Err(payload)) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::blocked_pool;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    // 被丢弃时设置标志，用于确认闭包（及其借用）在 join 返回前已被丢弃
+    // Sets a flag when dropped, used to check that a closure (and its borrows) was dropped before join returned
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn a_panicking_reclaims_queued_b_before_returning() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        let dropped = Arc::new(AtomicBool::new(false));
+        let mut hits = 0;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let flag = DropFlag(Arc::clone(&dropped));
+            let hits = &mut hits;
+            pool.join(
+                || panic!("a failed"),
+                move || {
+                    let _flag = flag;
+                    *hits += 1;
+                },
+            )
+        }));
+
+        // b 在 join 返回前由调用者收回执行并丢弃，之后队列中的任务不再持有借用
+        // b was reclaimed and dropped by the caller before join returned, the queued task no longer holds the borrow
+        assert!(result.is_err());
+        assert!(dropped.load(Ordering::SeqCst));
+        assert_eq!(hits, 1);
+
+        drop(release);
+        pool.wait_for_completion();
+    }
+
+    #[test]
+    fn a_panicking_waits_for_running_b() {
+        let pool = ThreadPool::builder().core_threads(1).max_threads(1).build();
+        let finished = AtomicBool::new(false);
+        let (started, wait_started) = mpsc::channel();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.join(
+                || {
+                    wait_started.recv().unwrap();
+                    panic!("a failed")
+                },
+                || {
+                    started.send(()).unwrap();
+                    thread::sleep(Duration::from_millis(50));
+                    finished.store(true, Ordering::SeqCst);
+                },
+            )
+        }));
+
+        // b 已在工作线程上开始，join 在 a panic 后仍等待 b 结束
+        // b had started on the worker, join still waited for it after a panicked
+        assert!(result.is_err());
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn b_panicking_propagates_after_both_finish() {
+        let pool = ThreadPool::builder().core_threads(1).max_threads(1).build();
+        let a_done = AtomicBool::new(false);
+
+        let payload = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.join(|| a_done.store(true, Ordering::SeqCst), || -> () { panic!("b failed") })
+        }))
+        .unwrap_err();
+
+        assert!(a_done.load(Ordering::SeqCst));
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"b failed"));
+    }
+
+    #[test]
+    fn nested_join_on_single_worker_does_not_deadlock() {
+        let pool = Arc::new(ThreadPool::builder().core_threads(1).max_threads(1).build());
+
+        let worker_pool = Arc::clone(&pool);
+        let mut handle = pool.submit_with_result(move || {
+            let pool = &*worker_pool;
+            let (left, right) = pool.join(
+                || pool.join(|| 1, || 2),
+                || pool.join(|| pool.join(|| 3, || 4), || 5),
+            );
+            left.0 + left.1 + (right.0).0 + (right.0).1 + right.1
+        });
+
+        assert_eq!(handle.join_timeout(Duration::from_secs(10)), Some(Ok(15)));
+    }
+
+    #[test]
+    fn b_runs_on_caller_when_pool_is_shut_down() {
+        let pool = ThreadPool::builder().core_threads(1).max_threads(1).build();
+        pool.shutdown();
+
+        let mut value = 0;
+        let (a, ()) = pool.join(|| 1, || value = 2);
+        assert_eq!((a, value), (1, 2));
+    }
+}
@@ -0,0 +1,109 @@
+// 线程池库入口，导出线程池及其配套类型
+// Library entry point, exports the thread pool and its companion types
+
+// 线程池核心模块
+// Thread pool core module
+pub mod thread_pool;
+
+// 线程池构建器模块
+// Thread pool builder module
+pub mod builder;
+
+// 任务句柄模块，用于获取任务返回值
+// Task handle module, used to retrieve task return values
+pub mod task_handle;
+
+// 作用域任务模块，允许任务借用栈上数据
+// Scoped task module, allows tasks to borrow stack data
+pub mod scope;
+
+// 任务组模块，只跟踪并等待自身提交的任务
+// Task group module, tracks and waits for only its own tasks
+pub mod task_group;
+
+// 分治并行模块，提供 join 原语
+// Fork-join module, provides the join primitive
+pub mod join;
+
+// 并行辅助模块，提供区间和切片上的并行 for_each / map / reduce
+// Parallel helpers module, provides parallel for_each / map / reduce over ranges and slices
+pub mod parallel;
+
+// 异步执行器模块，在线程池上运行 Future
+// Async executor module, runs futures on the thread pool
+pub mod executor;
+
+// 拒绝策略模块，处理线程池饱和时的新任务
+// Rejection policy module, handles new tasks when the pool is saturated
+pub mod rejection;
+
+// 优先级任务队列模块
+// Priority task queue module
+pub mod queue;
+
+// 定时器模块，支持延迟和定时执行任务
+// Timer module, supports delayed and scheduled task execution
+pub mod timer;
+
+// cron 调度模块，按 cron 表达式定时向线程池提交任务
+// Cron scheduling module, submits jobs into the thread pool according to cron expressions
+pub mod cron;
+
+// 取消令牌模块，支持协作式任务取消
+// Cancellation token module, supports cooperative task cancellation
+pub mod cancel;
+
+// 工作窃取调度模块，为每个工作线程提供本地队列
+// Work-stealing scheduler module, gives every worker a local deque
+pub mod work_stealing;
+
+// 无锁队列模块，提供可选的无锁全局任务队列
+// Lock-free queue module, provides the optional lock-free global task queue
+pub mod lock_free;
+
+// 状态快照模块，汇总线程池的各项计数
+// Stats snapshot module, gathers the pool's counters
+pub mod stats;
+
+// 延迟直方图模块，统计排队等待时间和执行时间的分布
+// Latency histogram module, tracks the distributions of queue wait and run time
+pub mod histogram;
+
+// 指标导出模块，以 Prometheus 文本格式通过 HTTP 提供线程池指标
+// Metrics export module, serves the pool metrics over HTTP in the Prometheus text format
+pub mod metrics;
+
+// 事件观察者模块，提供线程池生命周期事件的回调和结构化日志观察者
+// Observer module, provides callbacks for pool lifecycle events and a structured log observer
+pub mod observer;
+
+// 任务查看模块，提供运行中任务的快照
+// Introspection module, provides snapshots of running tasks
+pub mod introspect;
+
+// 测试辅助模块，提供多个模块的测试共用的线程池场景
+// Test helper module, provides pool setups shared by the tests of several modules
+#[cfg(test)]
+mod test_util;
+
+// 重新导出常用类型，方便外部直接使用
+// Re-export commonly used types for convenient external use
+pub use builder::ThreadPoolBuilder;
+pub use cancel::CancellationToken;
+pub use executor::{block_on, JoinHandle};
+pub use cron::{CronError, CronExpr, CronJobId, CronJobInfo, CronScheduler, MissedFirePolicy, UtcOffset};
+pub use histogram::LatencySummary;
+pub use introspect::RunningTask;
+pub use lock_free::QueueKind;
+pub use metrics::MetricsExporter;
+pub use observer::{LogObserver, PoolObserver};
+pub use parallel::Parallel;
+pub use queue::Priority;
+pub use rejection::{RejectedTask, RejectionPolicy, SubmitError};
+pub use scope::Scope;
+pub use stats::PoolStats;
+pub use task_group::TaskGroup;
+pub use task_handle::{TaskError, TaskHandle};
+pub use thread_pool::{Task, TaskInfo, ThreadPool};
+pub use timer::{PeriodicTask, ScheduledTask};
+pub use work_stealing::SchedulerMode;
@@ -0,0 +1,1006 @@
+// 本文件中的 SegQueue、Block、Slot 和 Backoff 改编自 crossbeam-queue 的 SegQueue
+// SegQueue, Block, Slot and Backoff in this file are adapted from SegQueue in crossbeam-queue
+// https://github.com/crossbeam-rs/crossbeam (crossbeam-queue/src/seg_queue.rs)
+//
+// crossbeam 以 MIT 或 Apache-2.0 双许可证发布，以下为其 MIT 许可声明
+// crossbeam is dual-licensed under MIT or Apache-2.0, its MIT notice follows
+//
+// The MIT License (MIT)
+//
+// Copyright (c) 2019 The Crossbeam Project Developers
+//
+// Permission is hereby granted, free of charge, to any
+// person obtaining a copy of this software and associated
+// documentation files (the "Software"), to deal in the
+// Software without restriction, including without
+// limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software
+// is furnished to do so, subject to the following
+// conditions:
+//
+// The above copyright notice and this permission notice
+// shall be included in all copies or substantial portions
+// of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF
+// ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED
+// TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A
+// PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT
+// SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
+// IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+// 导入 UnsafeCell（内部可变性）
+// Import UnsafeCell (interior mutability)
+use std::cell::UnsafeCell;
+
+// 导入 PhantomData（标记泛型参数的所有权）
+// Import PhantomData (marks ownership of the generic parameter)
+use std::marker::PhantomData;
+
+// 导入 MaybeUninit（可能未初始化的内存）
+// Import MaybeUninit (possibly uninitialized memory)
+use std::mem::MaybeUninit;
+
+// 导入原子类型和内存排序
+// Import atomic types and memory orderings
+use std::sync::atomic::{self, AtomicPtr, AtomicUsize, Ordering};
+
+// 导入 Mutex（互斥锁）和 Condvar（条件变量），仅用于线程休眠
+// Import Mutex (mutual exclusion lock) and Condvar (condition variable), only used to put threads to sleep
+use std::sync::{Condvar, Mutex};
+
+// 导入线程模块
+// Import thread module
+use std::thread;
+
+// 导入 Duration（时间段）
+// Import Duration (time duration)
+use std::time::Duration;
+
+// 导入优先级
+// Import priority
+use crate::queue::Priority;
+
+// 导入队列中的任务条目
+// Import task entry stored in the queue
+use crate::thread_pool::Job;
+
+// 线程池全局队列的实现方式
+// Implementation of the pool's global queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueueKind {
+    // 由互斥锁保护的优先级队列，支持优先级老化
+    // Priority queue protected by a mutex, supports priority aging
+    #[default]
+    Locked,
+
+    // 基于原子操作的无锁多生产者多消费者队列，每个优先级一个；空闲线程通过事件计数器休眠；不支持优先级老化
+    // Lock-free multi-producer multi-consumer queue built on atomics, one per priority; idle workers sleep on an event count; priority aging is not supported
+    LockFree,
+}
+
+// 槽位状态：值已写入
+// Slot state: the value has been written
+const WRITE: usize = 1;
+
+// 槽位状态：值已读出
+// Slot state: the value has been read
+const READ: usize = 2;
+
+// 槽位状态：块的销毁交给仍在读取该槽位的线程完成
+// Slot state: destroying the block is left to the thread still reading this slot
+const DESTROY: usize = 4;
+
+// 每个块对应的下标跨度，最后一个下标不对应槽位，用于标记 "正在安装下一个块"
+// Index span of each block, the last index has no slot and marks "next block being installed"
+const LAP: usize = 32;
+
+// 每个块的槽位数
+// Number of slots per block
+const BLOCK_CAP: usize = LAP - 1;
+
+// 下标左移的位数，最低位用作标志位
+// Bits the index is shifted by, the lowest bit is used as a flag
+const SHIFT: usize = 1;
+
+// 头部下标的标志位：头部所在块之后已有下一个块，出队时无需再读取尾部下标
+// Flag in the head index: a block after the head block exists, so dequeuing need not read the tail index
+const HAS_NEXT: usize = 1;
+
+// 按缓存行对齐，避免头部和尾部的伪共享
+// Aligned to cache lines to avoid false sharing between head and tail
+#[repr(align(128))]
+struct CachePadded<T>(T);
+
+// 自旋退避：先忙等，再让出时间片
+// Spin backoff: busy-wait first, then yield the time slice
+struct Backoff {
+    step: u32,
+}
+
+impl Backoff {
+    // 忙等次数上限（按 2 的幂增长）
+    // Limit for busy-waiting (grows in powers of two)
+    const SPIN_LIMIT: u32 = 6;
+
+    fn new() -> Self {
+        Backoff { step: 0 }
+    }
+
+    // CAS 失败后的短暂退避
+    // Short backoff after a failed CAS
+    fn spin(&mut self) {
+        for _ in 0..1 << self.step.min(Self::SPIN_LIMIT) {
+            std::hint::spin_loop();
+        }
+        if self.step <= Self::SPIN_LIMIT {
+            self.step += 1;
+        }
+    }
+
+    // 等待其他线程完成操作时的退避，等待过久时让出时间片
+    // Backoff while waiting for another thread to finish, yields the time slice once waiting too long
+    fn snooze(&mut self) {
+        if self.step <= Self::SPIN_LIMIT {
+            self.spin();
+        } else {
+            thread::yield_now();
+        }
+    }
+}
+
+// 队列中的一个槽位
+// A slot in the queue
+struct Slot<T> {
+    // 槽位中的值
+    // Value in the slot
+    value: UnsafeCell<MaybeUninit<T>>,
+
+    // 槽位状态（WRITE / READ / DESTROY 的组合）
+    // Slot state (combination of WRITE / READ / DESTROY)
+    state: AtomicUsize,
+}
+
+impl<T> Slot<T> {
+    // 等待生产者写入值
+    // Wait until the producer has written the value
+    fn wait_write(&self) {
+        let mut backoff = Backoff::new();
+        while self.state.load(Ordering::Acquire) & WRITE == 0 {
+            backoff.snooze();
+        }
+    }
+}
+
+// 由固定数量槽位组成的块，块之间以链表连接
+// Block of a fixed number of slots, blocks are chained into a linked list
+struct Block<T> {
+    // 下一个块
+    // Next block
+    next: AtomicPtr<Block<T>>,
+
+    // 槽位数组
+    // Slot array
+    slots: [Slot<T>; BLOCK_CAP],
+}
+
+impl<T> Block<T> {
+    // 创建所有槽位都为空的块
+    // Create a block with all slots empty
+    fn new() -> Box<Self> {
+        Box::new(Block {
+            next: AtomicPtr::new(std::ptr::null_mut()),
+            slots: std::array::from_fn(|_| Slot {
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+                state: AtomicUsize::new(0),
+            }),
+        })
+    }
+
+    // 等待下一个块被安装
+    // Wait until the next block is installed
+    fn wait_next(&self) -> *mut Block<T> {
+        let mut backoff = Backoff::new();
+        loop {
+            let next = self.next.load(Ordering::Acquire);
+            if !next.is_null() {
+                return next;
+            }
+            backoff.snooze();
+        }
+    }
+
+    // 从下标 start 开始检查槽位，没有线程仍在读取时释放块，否则把释放交给该线程
+    // Check slots from index start, free the block when no thread is still reading, otherwise hand freeing over to that thread
+    //
+    // 安全性：调用者必须是块中某个槽位的最后读取者，且块不会再被生产者访问
+    // Safety: the caller must be the last reader of a slot in the block, and producers must no longer access the block
+    unsafe fn destroy(this: *mut Block<T>, start: usize) {
+        // 最后一个槽位的读取者负责发起销毁，因此不需要检查它
+        // The reader of the last slot starts the destruction, so it needs no check
+        for i in start..BLOCK_CAP - 1 {
+            let slot = &(*this).slots[i];
+
+            // 槽位仍在被读取时设置 DESTROY 标志，由读取者继续销毁
+            // Set the DESTROY flag if the slot is still being read, the reader continues the destruction
+            if slot.state.load(Ordering::Acquire) & READ == 0
+                && slot.state.fetch_or(DESTROY, Ordering::AcqRel) & READ == 0
+            {
+                return;
+            }
+        }
+
+        drop(Box::from_raw(this));
+    }
+}
+
+// 队列的一端：下标和所在块
+// One end of the queue: index and the block it lies in
+struct Position<T> {
+    index: AtomicUsize,
+    block: AtomicPtr<Block<T>>,
+}
+
+// 无界无锁多生产者多消费者队列，按块分配内存，只使用标准库原子操作
+// Unbounded lock-free multi-producer multi-consumer queue, allocating memory in blocks and using only std atomics
+pub(crate) struct SegQueue<T> {
+    // 出队端
+    // Dequeue end
+    head: CachePadded<Position<T>>,
+
+    // 入队端
+    // Enqueue end
+    tail: CachePadded<Position<T>>,
+
+    // 队列拥有 T 类型的值
+    // The queue owns values of type T
+    _marker: PhantomData<T>,
+}
+
+// 值只会在线程之间转移而不会共享，因此 T: Send 即可
+// Values are only moved between threads and never shared, so T: Send suffices
+unsafe impl<T: Send> Send for SegQueue<T> {}
+unsafe impl<T: Send> Sync for SegQueue<T> {}
+
+impl<T> SegQueue<T> {
+    // 创建空队列，第一次入队时才分配块
+    // Create an empty queue, the first block is allocated on the first push
+    pub(crate) fn new() -> Self {
+        SegQueue {
+            head: CachePadded(Position {
+                index: AtomicUsize::new(0),
+                block: AtomicPtr::new(std::ptr::null_mut()),
+            }),
+            tail: CachePadded(Position {
+                index: AtomicUsize::new(0),
+                block: AtomicPtr::new(std::ptr::null_mut()),
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    // 将值加入队尾
+    // Push a value to the back of the queue
+    pub(crate) fn push(&self, value: T) {
+        let mut backoff = Backoff::new();
+        let mut tail = self.tail.0.index.load(Ordering::Acquire);
+        let mut block = self.tail.0.block.load(Ordering::Acquire);
+        let mut next_block = None;
+
+        loop {
+            // 下标在块内的偏移
+            // Offset of the index within its block
+            let offset = (tail >> SHIFT) % LAP;
+
+            // 到达块尾，等待其他生产者安装下一个块
+            // Reached the end of the block, wait for another producer to install the next one
+            if offset == BLOCK_CAP {
+                backoff.snooze();
+                tail = self.tail.0.index.load(Ordering::Acquire);
+                block = self.tail.0.block.load(Ordering::Acquire);
+                continue;
+            }
+
+            // 即将填满当前块时提前分配下一个块，缩短其他生产者的等待时间
+            // Allocate the next block in advance when about to fill the current one, shortening the wait of other producers
+            if offset + 1 == BLOCK_CAP && next_block.is_none() {
+                next_block = Some(Block::<T>::new());
+            }
+
+            // 第一次入队，分配第一个块
+            // First push, allocate the first block
+            if block.is_null() {
+                let new = Box::into_raw(Block::<T>::new());
+                if self
+                    .tail
+                    .0
+                    .block
+                    .compare_exchange(block, new, Ordering::Release, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    self.head.0.block.store(new, Ordering::Release);
+                    block = new;
+                } else {
+                    // 其他生产者抢先安装了第一个块，留下这个块备用
+                    // Another producer installed the first block first, keep this one for later
+                    next_block = Some(unsafe { Box::from_raw(new) });
+                    tail = self.tail.0.index.load(Ordering::Acquire);
+                    block = self.tail.0.block.load(Ordering::Acquire);
+                    continue;
+                }
+            }
+
+            let new_tail = tail + (1 << SHIFT);
+
+            // 尝试推进尾部下标，成功后独占该槽位
+            // Try to advance the tail index, the slot is owned exclusively once it succeeds
+            match self.tail.0.index.compare_exchange_weak(tail, new_tail, Ordering::SeqCst, Ordering::Acquire) {
+                Ok(_) => unsafe {
+                    // 占用了块中最后一个槽位，安装下一个块并跳过块尾的标记下标
+                    // Took the last slot of the block, install the next block and skip the marker index at the block end
+                    if offset + 1 == BLOCK_CAP {
+                        let next_block = Box::into_raw(next_block.expect("Next block was not allocated"));
+                        let next_index = new_tail.wrapping_add(1 << SHIFT);
+                        self.tail.0.block.store(next_block, Ordering::Release);
+                        self.tail.0.index.store(next_index, Ordering::Release);
+                        (*block).next.store(next_block, Ordering::Release);
+                    }
+
+                    // 写入值并发布
+                    // Write the value and publish it
+                    let slot = &(*block).slots[offset];
+                    slot.value.get().write(MaybeUninit::new(value));
+                    slot.state.fetch_or(WRITE, Ordering::Release);
+                    return;
+                },
+                Err(current) => {
+                    tail = current;
+                    block = self.tail.0.block.load(Ordering::Acquire);
+                    backoff.spin();
+                }
+            }
+        }
+    }
+
+    // 从队头取出值，队列为空时返回 None
+    // Pop a value from the front of the queue, None when the queue is empty
+    pub(crate) fn pop(&self) -> Option<T> {
+        let mut backoff = Backoff::new();
+        let mut head = self.head.0.index.load(Ordering::Acquire);
+        let mut block = self.head.0.block.load(Ordering::Acquire);
+
+        loop {
+            // 下标在块内的偏移
+            // Offset of the index within its block
+            let offset = (head >> SHIFT) % LAP;
+
+            // 到达块尾，等待其他消费者切换到下一个块
+            // Reached the end of the block, wait for another consumer to move to the next one
+            if offset == BLOCK_CAP {
+                backoff.snooze();
+                head = self.head.0.index.load(Ordering::Acquire);
+                block = self.head.0.block.load(Ordering::Acquire);
+                continue;
+            }
+
+            let mut new_head = head + (1 << SHIFT);
+
+            // 不确定后面是否还有块时，与尾部下标比较判断队列是否为空
+            // When it is unknown whether more blocks follow, compare with the tail index to tell whether the queue is empty
+            if new_head & HAS_NEXT == 0 {
+                atomic::fence(Ordering::SeqCst);
+                let tail = self.tail.0.index.load(Ordering::Relaxed);
+
+                // 头尾下标相同，队列为空
+                // Head and tail indices are equal, the queue is empty
+                if head >> SHIFT == tail >> SHIFT {
+                    return None;
+                }
+
+                // 头尾位于不同的块，标记头部之后已有下一个块
+                // Head and tail lie in different blocks, mark that a next block exists after the head
+                if (head >> SHIFT) / LAP != (tail >> SHIFT) / LAP {
+                    new_head |= HAS_NEXT;
+                }
+            }
+
+            // 第一次入队尚未完成时块可能为空，等待其完成
+            // The block may be null while the first push is still in progress, wait for it
+            if block.is_null() {
+                backoff.snooze();
+                head = self.head.0.index.load(Ordering::Acquire);
+                block = self.head.0.block.load(Ordering::Acquire);
+                continue;
+            }
+
+            // 尝试推进头部下标，成功后独占该槽位
+            // Try to advance the head index, the slot is owned exclusively once it succeeds
+            match self.head.0.index.compare_exchange_weak(head, new_head, Ordering::SeqCst, Ordering::Acquire) {
+                Ok(_) => unsafe {
+                    // 取走了块中最后一个槽位，切换到下一个块
+                    // Took the last slot of the block, move on to the next block
+                    if offset + 1 == BLOCK_CAP {
+                        let next = (*block).wait_next();
+                        let mut next_index = (new_head & !HAS_NEXT).wrapping_add(1 << SHIFT);
+                        if !(*next).next.load(Ordering::Relaxed).is_null() {
+                            next_index |= HAS_NEXT;
+                        }
+                        self.head.0.block.store(next, Ordering::Release);
+                        self.head.0.index.store(next_index, Ordering::Release);
+                    }
+
+                    // 等待生产者写完后读出值
+                    // Wait for the producer to finish writing, then read the value
+                    let slot = &(*block).slots[offset];
+                    slot.wait_write();
+                    let value = slot.value.get().read().assume_init();
+
+                    // 读完最后一个槽位时销毁块；其他线程想销毁块但本线程仍在读取时，由本线程继续销毁
+                    // Destroy the block after reading its last slot; if another thread wanted to destroy it while this one was still reading, continue the destruction here
+                    if offset + 1 == BLOCK_CAP {
+                        Block::destroy(block, 0);
+                    } else if slot.state.fetch_or(READ, Ordering::AcqRel) & DESTROY != 0 {
+                        Block::destroy(block, offset + 1);
+                    }
+                    return Some(value);
+                },
+                Err(current) => {
+                    head = current;
+                    block = self.head.0.block.load(Ordering::Acquire);
+                    backoff.spin();
+                }
+            }
+        }
+    }
+}
+
+impl<T> Default for SegQueue<T> {
+    fn default() -> Self {
+        SegQueue::new()
+    }
+}
+
+impl<T> Drop for SegQueue<T> {
+    fn drop(&mut self) {
+        // 独占访问，清除标志位后逐个释放剩余的值和块
+        // Exclusive access, clear the flag bits and release the remaining values and blocks one by one
+        let mut head = *self.head.0.index.get_mut() & !((1 << SHIFT) - 1);
+        let tail = *self.tail.0.index.get_mut() & !((1 << SHIFT) - 1);
+        let mut block = *self.head.0.block.get_mut();
+
+        unsafe {
+            while head != tail {
+                let offset = (head >> SHIFT) % LAP;
+                if offset < BLOCK_CAP {
+                    (*(*block).slots[offset].value.get()).assume_init_drop();
+                } else {
+                    let next = *(*block).next.get_mut();
+                    drop(Box::from_raw(block));
+                    block = next;
+                }
+                head = head.wrapping_add(1 << SHIFT);
+            }
+
+            if !block.is_null() {
+                drop(Box::from_raw(block));
+            }
+        }
+    }
+}
+
+// 事件计数器：让等待者在条件不满足时休眠，而条件的检查和更新本身不需要加锁
+// Event count: lets waiters sleep while a condition does not hold, without locking to check or update the condition itself
+pub(crate) struct EventCount {
+    // 每次通知加一，等待者据此判断准备等待之后是否发生过通知
+    // Incremented on every notification, waiters use it to tell whether a notification happened since they prepared to wait
+    epoch: AtomicUsize,
+
+    // 正在等待的线程数，为0时通知无需加锁
+    // Number of waiting threads, notifications skip the lock when it is 0
+    waiters: AtomicUsize,
+
+    // 仅用于休眠和唤醒的锁和条件变量
+    // Lock and condition variable used only for sleeping and waking
+    sleep: (Mutex<()>, Condvar),
+}
+
+impl EventCount {
+    pub(crate) fn new() -> Self {
+        EventCount {
+            epoch: AtomicUsize::new(0),
+            waiters: AtomicUsize::new(0),
+            sleep: (Mutex::new(()), Condvar::new()),
+        }
+    }
+
+    // 准备等待：登记为等待者并返回当前纪元；调用后必须再次检查条件，然后调用 wait 或 cancel_wait
+    // Prepare to wait: register as a waiter and return the current epoch; the condition must be rechecked afterwards, followed by wait or cancel_wait
+    pub(crate) fn prepare_wait(&self) -> usize {
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        self.epoch.load(Ordering::SeqCst)
+    }
+
+    // 再次检查发现条件已满足，取消等待
+    // The recheck found the condition satisfied, cancel the wait
+    pub(crate) fn cancel_wait(&self) {
+        self.waiters.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    // 休眠直到纪元变化或超时（为 None 时不超时），返回是否超时
+    // Sleep until the epoch changes or the timeout elapses (never when None), returns whether it timed out
+    pub(crate) fn wait(&self, key: usize, timeout: Option<Duration>) -> bool {
+        let mut guard = self.sleep.0.lock().expect("Failed to lock event count mutex");
+        let mut timed_out = false;
+        while self.epoch.load(Ordering::SeqCst) == key {
+            match timeout {
+                None => guard = self.sleep.1.wait(guard).expect("Condvar wait failed"),
+                Some(timeout) => {
+                    let result = self
+                        .sleep
+                        .1
+                        .wait_timeout(guard, timeout)
+                        .expect("Condvar wait failed");
+                    guard = result.0;
+                    if result.1.timed_out() {
+                        timed_out = self.epoch.load(Ordering::SeqCst) == key;
+                        break;
+                    }
+                }
+            }
+        }
+        drop(guard);
+        self.waiters.fetch_sub(1, Ordering::SeqCst);
+        timed_out
+    }
+
+    // 唤醒一个等待者（条件更新之后调用）
+    // Wake one waiter (called after updating the condition)
+    pub(crate) fn notify_one(&self) {
+        self.epoch.fetch_add(1, Ordering::SeqCst);
+        if self.waiters.load(Ordering::SeqCst) > 0 {
+            // 先获取再释放锁，保证通知不会发生在等待者检查纪元和开始等待之间
+            // Acquire and release the lock first so the notification cannot land between a waiter's epoch check and its wait
+            drop(self.sleep.0.lock().expect("Failed to lock event count mutex"));
+            self.sleep.1.notify_one();
+        }
+    }
+
+    // 唤醒所有等待者（条件更新之后调用）
+    // Wake all waiters (called after updating the condition)
+    pub(crate) fn notify_all(&self) {
+        self.epoch.fetch_add(1, Ordering::SeqCst);
+        if self.waiters.load(Ordering::SeqCst) > 0 {
+            drop(self.sleep.0.lock().expect("Failed to lock event count mutex"));
+            self.sleep.1.notify_all();
+        }
+    }
+}
+
+// 线程池的无锁全局队列：每个优先级一个无锁队列，容量通过原子计数预留
+// Lock-free global queue of the pool: one lock-free queue per priority, capacity is reserved through an atomic counter
+pub(crate) struct LockFreeQueue {
+    // 每个优先级一个先进先出队列，下标即优先级数值
+    // One FIFO queue per priority, the index is the priority value
+    levels: [SegQueue<Job>; Priority::COUNT],
+
+    // 每个优先级当前的任务数（近似值，仅用于统计）
+    // Current task count per priority (approximate, only used for statistics)
+    depths: [AtomicUsize; Priority::COUNT],
+
+    // 已预留的位置数：入队前加一，出队后减一，因此总不小于实际任务数
+    // Number of reserved places: incremented before pushing and decremented after popping, so never below the actual task count
+    len: AtomicUsize,
+
+    // 队列容量，为 None 时队列无界
+    // Queue capacity, the queue is unbounded when None
+    capacity: Option<usize>,
+
+    // 空闲工作线程在此休眠，入队后唤醒
+    // Idle workers sleep here and are woken after a push
+    pub(crate) workers: EventCount,
+
+    // 等待空位的提交者在此休眠，出队后唤醒
+    // Submitters waiting for space sleep here and are woken after a pop
+    pub(crate) space: EventCount,
+}
+
+impl LockFreeQueue {
+    pub(crate) fn new(capacity: Option<usize>) -> Self {
+        LockFreeQueue {
+            levels: [SegQueue::new(), SegQueue::new(), SegQueue::new()],
+            depths: [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)],
+            len: AtomicUsize::new(0),
+            capacity,
+            workers: EventCount::new(),
+            space: EventCount::new(),
+        }
+    }
+
+    // 队列中的任务数（包括已预留但尚未放入的位置）
+    // Number of queued tasks (including places reserved but not yet filled)
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    // 队列是否为空
+    // Whether the queue is empty
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 队列是否已达到容量上限
+    // Whether the queue has reached its capacity
+    pub(crate) fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|capacity| self.len() >= capacity)
+    }
+
+    // 尝试预留一个位置，队列已满时失败
+    // Try to reserve a place, fails when the queue is full
+    pub(crate) fn try_reserve(&self) -> bool {
+        match self.capacity {
+            Some(capacity) => self
+                .len
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |len| (len < capacity).then_some(len + 1))
+                .is_ok(),
+            None => {
+                self.len.fetch_add(1, Ordering::SeqCst);
+                true
+            }
+        }
+    }
+
+    // 不考虑容量强制预留一个位置
+    // Reserve a place regardless of capacity
+    pub(crate) fn force_reserve(&self) {
+        self.len.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // 释放预留的位置（任务未入队或已出队），并唤醒一个等待空位的提交者
+    // Release a reserved place (the task was not pushed or has been popped), and wake one submitter waiting for space
+    pub(crate) fn release(&self, count: usize) {
+        if count > 0 {
+            self.len.fetch_sub(count, Ordering::SeqCst);
+            self.space.notify_one();
+        }
+    }
+
+    // 将任务放入已预留的位置
+    // Push a task into a reserved place
+    pub(crate) fn push(&self, job: Job) {
+        let level = job.priority as usize;
+        self.depths[level].fetch_add(1, Ordering::Relaxed);
+        self.levels[level].push(job);
+    }
+
+    // 从最高的非空级别取出最早的任务，不释放其位置
+    // Take the earliest task from the highest non-empty level, without releasing its place
+    pub(crate) fn pop(&self) -> Option<Job> {
+        (0..Priority::COUNT).rev().find_map(|level| self.pop_level(level))
+    }
+
+    // 从最低的非空级别取出最旧的任务，不释放其位置
+    // Take the oldest task from the lowest non-empty level, without releasing its place
+    pub(crate) fn pop_oldest(&self) -> Option<Job> {
+        (0..Priority::COUNT).find_map(|level| self.pop_level(level))
+    }
+
+    // 从指定级别取出一个任务
+    // Take one task from the given level
+    fn pop_level(&self, level: usize) -> Option<Job> {
+        let job = self.levels[level].pop()?;
+        self.depths[level].fetch_sub(1, Ordering::Relaxed);
+        Some(job)
+    }
+
+    // 每个优先级当前排队的任务数，从高到低排列
+    // Number of tasks queued at each priority, ordered from high to low
+    pub(crate) fn depths(&self) -> Vec<(Priority, usize)> {
+        Priority::ALL
+            .iter()
+            .map(|&priority| (priority, self.depths[priority as usize].load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{mpsc, Arc};
+    use std::time::Instant;
+
+    use crate::builder::ThreadPoolBuilder;
+    use crate::rejection::Abort;
+
+    // Miri 下运行得很慢，缩小压力测试的规模
+    // Miri runs slowly, shrink the stress test
+    const PER_THREAD: usize = if cfg!(miri) { 100 } else { 20_000 };
+
+    // 被丢弃时计数的载荷
+    // Payload that counts its drops
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn job(id: usize, priority: Priority) -> Job {
+        Job {
+            id,
+            priority,
+            token: None,
+            deadline: None,
+            on_expired: None,
+            enqueued_at: Instant::now(),
+            name: None,
+            repoll: false,
+            task: Box::new(|| {}),
+        }
+    }
+
+    #[test]
+    fn fifo_across_block_boundaries() {
+        let queue = SegQueue::new();
+        assert!(queue.pop().is_none());
+
+        // 一次放入多个块的元素，再全部取出
+        // Fill several blocks at once, then drain them
+        for i in 0..BLOCK_CAP * 3 + 5 {
+            queue.push(i);
+        }
+        for i in 0..BLOCK_CAP * 3 + 5 {
+            assert_eq!(queue.pop(), Some(i));
+        }
+        assert!(queue.pop().is_none());
+
+        // 交替放入和取出，使头部和尾部在不同的块中跨越边界
+        // Interleave pushes and pops so head and tail cross boundaries in different blocks
+        let mut next_push = 0;
+        let mut next_pop = 0;
+        for _ in 0..LAP * 4 {
+            for _ in 0..3 {
+                queue.push(next_push);
+                next_push += 1;
+            }
+            for _ in 0..2 {
+                assert_eq!(queue.pop(), Some(next_pop));
+                next_pop += 1;
+            }
+        }
+        while let Some(value) = queue.pop() {
+            assert_eq!(value, next_pop);
+            next_pop += 1;
+        }
+        assert_eq!(next_pop, next_push);
+    }
+
+    #[test]
+    fn mpmc_delivers_every_value_exactly_once() {
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        let queue = SegQueue::new();
+        let consumed = AtomicUsize::new(0);
+
+        let received: Vec<Vec<usize>> = thread::scope(|scope| {
+            for producer in 0..PRODUCERS {
+                let queue = &queue;
+                scope.spawn(move || {
+                    for i in 0..PER_THREAD {
+                        queue.push(producer * PER_THREAD + i);
+                    }
+                });
+            }
+            let consumers: Vec<_> = (0..CONSUMERS)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut values = Vec::new();
+                        while consumed.load(Ordering::SeqCst) < PRODUCERS * PER_THREAD {
+                            match queue.pop() {
+                                Some(value) => {
+                                    values.push(value);
+                                    consumed.fetch_add(1, Ordering::SeqCst);
+                                }
+                                None => thread::yield_now(),
+                            }
+                        }
+                        values
+                    })
+                })
+                .collect();
+            consumers.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        // 每个消费者看到的同一生产者的值保持先进先出
+        // Each consumer sees the values of one producer in FIFO order
+        for values in &received {
+            let mut last = [None; PRODUCERS];
+            for &value in values {
+                let producer = value / PER_THREAD;
+                assert!(last[producer] < Some(value));
+                last[producer] = Some(value);
+            }
+        }
+
+        // 所有值恰好被取出一次
+        // Every value is taken exactly once
+        let mut all: Vec<usize> = received.into_iter().flatten().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..PRODUCERS * PER_THREAD).collect::<Vec<_>>());
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn drop_releases_queued_values() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let queue = SegQueue::new();
+        for _ in 0..BLOCK_CAP * 2 + 10 {
+            queue.push(DropCounter(drops.clone()));
+        }
+
+        // 取出一部分，使头部停在一个块的中间
+        // Take some out so the head stops in the middle of a block
+        for _ in 0..BLOCK_CAP + 3 {
+            drop(queue.pop());
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), BLOCK_CAP + 3);
+
+        drop(queue);
+        assert_eq!(drops.load(Ordering::SeqCst), BLOCK_CAP * 2 + 10);
+
+        // 恰好取空一个块后丢弃
+        // Drop right after draining exactly one block
+        let drops = Arc::new(AtomicUsize::new(0));
+        let queue = SegQueue::new();
+        for _ in 0..BLOCK_CAP {
+            queue.push(DropCounter(drops.clone()));
+        }
+        while queue.pop().is_some() {}
+        drop(queue);
+        assert_eq!(drops.load(Ordering::SeqCst), BLOCK_CAP);
+    }
+
+    #[test]
+    fn pops_by_priority_and_oldest_from_lowest_level() {
+        let queue = LockFreeQueue::new(None);
+        for (id, priority) in [(0, Priority::Low), (1, Priority::High), (2, Priority::Low), (3, Priority::Normal)] {
+            assert!(queue.try_reserve());
+            queue.push(job(id, priority));
+        }
+        assert_eq!(queue.depths(), vec![(Priority::High, 1), (Priority::Normal, 1), (Priority::Low, 2)]);
+
+        // pop 取最高级别，pop_oldest 取最低级别中最早的任务
+        // pop takes the highest level, pop_oldest the earliest task of the lowest level
+        assert_eq!(queue.pop().map(|job| job.id), Some(1));
+        assert_eq!(queue.pop_oldest().map(|job| job.id), Some(0));
+        assert_eq!(queue.pop_oldest().map(|job| job.id), Some(2));
+        assert_eq!(queue.pop_oldest().map(|job| job.id), Some(3));
+        assert!(queue.pop().is_none());
+        assert!(queue.pop_oldest().is_none());
+
+        // 出队不释放位置，需显式释放
+        // Popping does not release places, they are released explicitly
+        assert_eq!(queue.len(), 4);
+        queue.release(4);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn reservations_respect_capacity() {
+        let queue = LockFreeQueue::new(Some(2));
+        assert!(queue.try_reserve());
+        assert!(queue.try_reserve());
+        assert!(queue.is_full());
+        assert!(!queue.try_reserve());
+        assert_eq!(queue.len(), 2);
+
+        // 强制预留可以超出容量
+        // A forced reservation may exceed the capacity
+        queue.force_reserve();
+        assert_eq!(queue.len(), 3);
+
+        queue.release(2);
+        assert!(!queue.is_full());
+        assert!(queue.try_reserve());
+        assert!(!queue.try_reserve());
+
+        // 无界队列总能预留
+        // An unbounded queue can always reserve
+        let unbounded = LockFreeQueue::new(None);
+        for _ in 0..100 {
+            assert!(unbounded.try_reserve());
+        }
+        assert!(!unbounded.is_full());
+    }
+
+    #[test]
+    fn release_wakes_submitter_waiting_for_space() {
+        let queue = LockFreeQueue::new(Some(1));
+        assert!(queue.try_reserve());
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| loop {
+                let key = queue.space.prepare_wait();
+                if queue.try_reserve() {
+                    queue.space.cancel_wait();
+                    return;
+                }
+                assert!(!queue.space.wait(key, Some(Duration::from_secs(10))));
+            });
+            queue.release(1);
+            waiter.join().unwrap();
+        });
+        assert!(queue.is_full());
+    }
+
+    #[test]
+    fn wait_times_out_without_notification() {
+        let event = EventCount::new();
+        let key = event.prepare_wait();
+        assert!(event.wait(key, Some(Duration::from_millis(10))));
+
+        // 准备等待之后的通知不会丢失
+        // A notification after preparing to wait is not lost
+        let key = event.prepare_wait();
+        event.notify_one();
+        assert!(!event.wait(key, Some(Duration::from_millis(10))));
+    }
+
+    #[test]
+    fn saturated_submit_with_policy_never_blocks() {
+        const SUBMITTERS: usize = 4;
+        const SUBMITS: usize = if cfg!(miri) { 20 } else { 500 };
+        let pool = ThreadPoolBuilder::new()
+            .core_threads(1)
+            .max_threads(1)
+            .queue_capacity(1)
+            .queue_kind(QueueKind::LockFree)
+            .rejection_policy(Abort)
+            .build();
+
+        // 唯一的工作线程阻塞，队列只剩一个空位
+        // The only worker is blocked, leaving a single place in the queue
+        let (release, blocked) = mpsc::channel::<()>();
+        let (started, wait_started) = mpsc::channel();
+        pool.submit(move || {
+            started.send(()).unwrap();
+            let _ = blocked.recv();
+        })
+        .unwrap();
+        wait_started.recv().unwrap();
+
+        // 多个提交者争抢最后一个空位，没抢到的必须立即被拒绝而不是等待空位
+        // Several submitters race for the last place, the losers must be rejected at once instead of waiting for space
+        let (done, results) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..SUBMITTERS {
+                let pool = &pool;
+                let done = done.clone();
+                scope.spawn(move || {
+                    let accepted = (0..SUBMITS).filter(|_| pool.submit(|| {}).is_ok()).count();
+                    done.send(accepted).unwrap();
+                });
+            }
+            let accepted: usize = (0..SUBMITTERS)
+                .map(|_| results.recv_timeout(Duration::from_secs(10)).expect("Submit blocked on a saturated pool"))
+                .sum();
+            assert_eq!(accepted, 1);
+            assert_eq!(pool.rejected_tasks(), SUBMITTERS * SUBMITS - 1);
+            drop(release);
+        });
+        pool.wait_for_completion();
+    }
+
+    #[test]
+    #[should_panic(expected = "Priority aging is not supported by the lock-free queue")]
+    fn priority_aging_is_rejected_with_lock_free_queue() {
+        ThreadPoolBuilder::new()
+            .queue_kind(QueueKind::LockFree)
+            .priority_aging(Duration::from_millis(100))
+            .build();
+    }
+}
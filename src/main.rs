@@ -1,7 +1,3 @@
-// 引入线程池模块
-// Import thread pool module
-mod thread_pool; 
-
 // 导入Arc（原子引用计数）和Mutex（互斥锁）
 // Import Arc (atomic reference counting) and Mutex (mutual exclusion lock)
 use std::sync::{Arc, Mutex}; 
@@ -14,9 +10,9 @@ use std::thread;
 // Import Duration (time duration)
 use std::time::Duration; 
 
-// 从线程池模块导入ThreadPool
-// Import ThreadPool from thread pool module
-use thread_pool::ThreadPool; 
+// 从线程池库导入ThreadPool和周期任务句柄
+// Import ThreadPool and the periodic task handle from thread pool library
+use rust_dynamic_thread_pool::{PeriodicTask, ThreadPool}; 
 
 // 使用标准库的OnceLock创建全局互斥锁，替代lazy_static
 // Use standard library's OnceLock to create global mutex, replacing lazy_static
@@ -70,40 +66,62 @@ fn task(task_id: usize) {
 
 // 定义监控函数，用于实时监控线程池状态
 // Define monitor function for real-time monitoring of thread pool status
-fn monitor(pool: Arc<ThreadPool>, seconds: usize) {
-    // 循环监控指定秒数，每100毫秒检查一次线程数
-    // Loop monitoring for specified seconds, checking thread count every 100 milliseconds
-    for i in 1..seconds * 10 { // 每秒钟循环10次，共循环秒数乘以10次
-                               // Loop 10 times per second, total loops = seconds * 10
-        {
+// 使用固定频率的周期任务采样，两次采样之间不占用工作线程
+// Samples with a fixed-rate periodic task, so no worker is held between samples
+fn monitor(pool: &Arc<ThreadPool>, seconds: usize) {
+    // 每秒钟采样10次，共采样秒数乘以10次
+    // Sample 10 times per second, total samples = seconds * 10
+    let total = seconds * 10;
+
+    // 周期任务在调度之后才有句柄，由采样闭包在最后一次采样时取出并取消
+    // The periodic task only has a handle once scheduled, the sampling closure takes it out and cancels it at the last sample
+    let sampler: Arc<Mutex<Option<PeriodicTask>>> = Arc::new(Mutex::new(None));
+    let handle = Arc::clone(&sampler);
+
+    // 周期任务保存在线程池内部，只持有弱引用，避免线程池永远无法释放
+    // The periodic task lives inside the pool, so it only holds a weak reference to keep the pool droppable
+    let weak_pool = Arc::downgrade(pool);
+    let mut i = 0;
+    let periodic = pool
+        .schedule_at_fixed_rate(Duration::ZERO, Duration::from_millis(100), move || {
+            let pool = match weak_pool.upgrade() {
+                Some(pool) => pool,
+                None => return,
+            };
+            i += 1;
+
+            // 采样完成后立即取消周期任务；句柄尚未保存时由之后的执行再次尝试
+            // Cancel the periodic task as soon as sampling is done; if the handle is not stored yet a later run tries again
+            if i >= total {
+                if let Some(sampler) = handle.lock().expect("Failed to lock sampler mutex").take() {
+                    sampler.cancel();
+                }
+            }
+            if i > total {
+                return;
+            }
+
             // 获取控制台输出的锁
             // Get console output lock
             let _guard = get_cout_mutex().lock().expect("Failed to lock console output mutex"); 
             
-            println!("thread num: {} (monitoring: {}/{})", 
-                pool.threads_num(), // 输出当前线程数
-                                    // Output current thread count
+            println!("{} (monitoring: {}/{})", 
+                pool.stats(), // 输出线程池状态快照
+                              // Output pool state snapshot
                 i, // 当前监控次数
                    // Current monitoring iteration
-                seconds * 10 - 1 // 总监控次数
-                                 // Total monitoring iterations
+                total // 总监控次数
+                      // Total monitoring iterations
             );
-        } // _guard在此处自动释放
-          // _guard automatically releases here
-        
-        // 休眠100毫秒，控制监控频率
-        // Sleep for 100 milliseconds, controlling monitoring frequency
-        thread::sleep(Duration::from_millis(100)); 
-    }
-    
-    // 监控结束提示
-    // Monitoring end notification
-    {
-        let _guard = get_cout_mutex().lock().expect("Failed to lock console output mutex");
-        // 输出监控结束和最终线程数
-        // Output monitoring end and final thread count
-        println!("监控结束，最终线程数: {}", pool.threads_num()); 
-    }
+
+            // 最后一次采样后输出监控结束和最终线程数
+            // Output monitoring end and final thread count after the last sample
+            if i == total {
+                println!("监控结束，最终线程数: {}", pool.threads_num()); 
+            }
+        })
+        .expect("Failed to schedule monitoring task");
+    *sampler.lock().expect("Failed to lock sampler mutex") = Some(periodic);
 }
 
 fn main() {
@@ -130,15 +148,9 @@ fn main() {
     println!("线程池创建完成，最大线程数: {}", pool.get_max_threads()); 
     println!("Thread pool created, max threads: {}", pool.get_max_threads()); 
 
-    // 启动监控线程，监控20秒的线程池状态
-    // Start monitoring thread to monitor thread pool status for 20 seconds
-    // 克隆线程池的Arc，用于在监控线程中使用
-    // Clone Arc of thread pool for use in monitoring thread
-    let pool_clone = Arc::clone(&pool); 
-    
-    // 提交监控任务到线程池
-    // Submit monitoring task to thread pool
-    pool.submit(move || monitor(pool_clone, 20)); 
+    // 启动周期监控，监控20秒的线程池状态
+    // Start periodic monitoring of thread pool status for 20 seconds
+    monitor(&pool, 20); 
     
     // 输出监控任务提交确认
     // Output monitoring task submission confirmation
@@ -171,7 +183,7 @@ fn main() {
         
         // 提交工作任务到线程池
         // Submit work task to thread pool
-        pool_clone.submit(move || task(task_id)); 
+        pool_clone.submit(move || task(task_id)).expect("Failed to submit task"); 
         
         // 每提交10个任务输出一次进度
         // Output progress every 10 submitted tasks
@@ -194,8 +206,8 @@ fn main() {
     // Record start time of waiting
     let start_time = std::time::Instant::now(); 
     
-    // 等待所有任务完成（包括监控任务和工作任务）
-    // Wait for all tasks to complete (including monitoring and work tasks)
+    // 等待所有已提交的任务完成（周期监控的采样只在到期时才计入）
+    // Wait for all submitted tasks to complete (periodic monitoring samples only count once they are due)
     pool.wait_for_completion(); 
     
     // 计算等待时间
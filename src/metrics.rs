@@ -0,0 +1,334 @@
+// 导入 fmt::Write（向 String 写入格式化文本）
+// Import fmt::Write (writes formatted text into a String)
+use std::fmt::Write as _;
+
+// 导入 io 模块及读写 trait
+// Import io module and read/write traits
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+// 导入 TCP 监听器、TCP 连接和地址类型
+// Import TCP listener, TCP stream and address types
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+
+// 导入 Arc（原子引用计数）
+// Import Arc (atomic reference counting)
+use std::sync::Arc;
+
+// 导入 AtomicBool（原子布尔）和 Ordering（内存排序）
+// Import AtomicBool (atomic boolean) and Ordering (memory ordering)
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 导入线程模块和 JoinHandle（用于线程句柄）
+// Import thread module and JoinHandle (for thread handles)
+use std::thread::{self, JoinHandle};
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 导入延迟的累计分布
+// Import cumulative latency distribution
+use crate::histogram::Cumulative;
+
+// 导入线程池
+// Import thread pool
+use crate::thread_pool::ThreadPool;
+
+// 读取整个请求的期限，避免缓慢发送或从不发送请求的连接一直占用导出线程
+// Deadline for reading the whole request, so a connection that sends slowly or never sends cannot hold the exporter thread
+const READ_DEADLINE: Duration = Duration::from_secs(5);
+
+// 每次写入响应的超时时间，避免不读取响应的连接一直占用导出线程
+// Timeout of every write of the response, so a connection that never reads it cannot hold the exporter thread
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 请求行和请求头的最大字节数，超过时直接关闭连接
+// Maximum size in bytes of the request line and headers, the connection is closed when exceeded
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+// 没有连接时检查停止标志的间隔
+// Interval for checking the stop flag while no connection arrives
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// 指标名前缀
+// Metric name prefix
+const PREFIX: &str = "thread_pool";
+
+// 写入一个只有单个样本的指标
+// Write a metric with a single sample
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "# HELP {PREFIX}_{name} {help}");
+    let _ = writeln!(out, "# TYPE {PREFIX}_{name} {kind}");
+    let _ = writeln!(out, "{PREFIX}_{name} {value}");
+}
+
+// 写入一个直方图指标：各边界的累计计数、+Inf 桶、总和与总数
+// Write a histogram metric: cumulative count at every boundary, the +Inf bucket, the sum and the count
+fn write_histogram(out: &mut String, name: &str, help: &str, histogram: &Cumulative) {
+    let _ = writeln!(out, "# HELP {PREFIX}_{name} {help}");
+    let _ = writeln!(out, "# TYPE {PREFIX}_{name} histogram");
+    for (bound, count) in &histogram.buckets {
+        let _ = writeln!(out, "{PREFIX}_{name}_bucket{{le=\"{}\"}} {count}", bound.as_secs_f64());
+    }
+    let _ = writeln!(out, "{PREFIX}_{name}_bucket{{le=\"+Inf\"}} {}", histogram.count);
+    let _ = writeln!(out, "{PREFIX}_{name}_sum {}", histogram.sum.as_secs_f64());
+    let _ = writeln!(out, "{PREFIX}_{name}_count {}", histogram.count);
+}
+
+impl ThreadPool {
+    // 以 Prometheus 文本格式输出线程池指标，可交给自有的 HTTP 服务返回
+    // Render the pool metrics in the Prometheus text exposition format, can be served by an existing HTTP server
+    pub fn metrics_text(&self) -> String {
+        let stats = self.stats();
+        let mut out = String::new();
+
+        // 线程数
+        // Thread gauges
+        write_metric(&mut out, "threads", "gauge", "Current number of worker threads.", stats.threads);
+        write_metric(&mut out, "idle_threads", "gauge", "Number of idle worker threads.", stats.idle_threads);
+        write_metric(&mut out, "peak_threads", "gauge", "Highest number of worker threads so far.", stats.peak_threads);
+        write_metric(&mut out, "max_threads", "gauge", "Maximum number of worker threads.", self.get_max_threads());
+        write_metric(&mut out, "threads_spawned_total", "counter", "Worker threads spawned.", stats.spawned_threads);
+        write_metric(&mut out, "threads_retired_total", "counter", "Worker threads exited.", stats.retired_threads);
+
+        // 队列深度和正在执行的任务数
+        // Queue depth and executing tasks
+        write_metric(&mut out, "queued_tasks", "gauge", "Number of queued tasks.", stats.queued_tasks);
+        write_metric(&mut out, "active_tasks", "gauge", "Number of tasks currently executing.", stats.active_tasks);
+
+        // 任务计数
+        // Task counters
+        write_metric(&mut out, "tasks_submitted_total", "counter", "Tasks submitted.", stats.submitted_tasks);
+        write_metric(&mut out, "tasks_completed_total", "counter", "Tasks completed normally.", stats.completed_tasks);
+        write_metric(&mut out, "tasks_panicked_total", "counter", "Tasks that panicked.", stats.panicked_tasks);
+        write_metric(&mut out, "tasks_rejected_total", "counter", "Tasks rejected.", stats.rejected_tasks);
+        write_metric(&mut out, "tasks_cancelled_total", "counter", "Tasks cancelled.", stats.cancelled_tasks);
+        write_metric(&mut out, "tasks_expired_total", "counter", "Tasks discarded because they expired.", stats.expired_tasks);
+
+        write_metric(&mut out, "uptime_seconds", "gauge", "Time since the pool was created.", stats.uptime.as_secs_f64());
+
+        // 延迟直方图使用生命周期内的累计分布，保证各计数单调递增
+        // The latency histograms use the lifetime cumulative distribution so every count is monotonic
+        write_histogram(
+            &mut out,
+            "queue_wait_seconds",
+            "Time tasks spent queued before being dequeued.",
+            &self.inner.queue_wait.cumulative(),
+        );
+        write_histogram(
+            &mut out,
+            "run_time_seconds",
+            "Time tasks spent executing.",
+            &self.inner.run_time.cumulative(),
+        );
+
+        out
+    }
+}
+
+// Prometheus 指标导出器：由单独的线程在 /metrics 上提供线程池指标；导出器被丢弃时停止服务
+// Prometheus metrics exporter: a dedicated thread serves the pool metrics on /metrics; the server stops when the exporter is dropped
+pub struct MetricsExporter {
+    // 实际监听的地址
+    // Address actually listened on
+    local_addr: SocketAddr,
+
+    // 停止标志
+    // Stop flag
+    stopped: Arc<AtomicBool>,
+
+    // 导出线程句柄
+    // Exporter thread handle
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MetricsExporter {
+    // 在指定地址上监听并开始导出指标，端口为0时由系统分配
+    // Listen on the given address and start exporting metrics, the system picks the port when it is 0
+    pub fn bind<A: ToSocketAddrs>(pool: Arc<ThreadPool>, addr: A) -> io::Result<MetricsExporter> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+
+        // 非阻塞地接受连接并定期检查停止标志，丢弃导出器时无需连接自身来唤醒导出线程
+        // Accept connections without blocking and check the stop flag periodically, so dropping the exporter does not need to connect to itself to wake the thread
+        listener.set_nonblocking(true)?;
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread_stopped = Arc::clone(&stopped);
+        let thread = thread::Builder::new()
+            .name("metrics-exporter".to_string())
+            .spawn(move || {
+                while !thread_stopped.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        // 单个连接出错不影响后续的抓取
+                        // An error on one connection does not affect later scrapes
+                        Ok((stream, _)) => {
+                            let _ = serve(&pool, stream);
+                        }
+
+                        // 暂无连接或接受失败（如文件描述符耗尽）时稍后重试
+                        // Retry later when no connection is pending or accepting failed (such as running out of file descriptors)
+                        Err(_) => thread::sleep(ACCEPT_POLL_INTERVAL),
+                    }
+                }
+            })?;
+
+        Ok(MetricsExporter { local_addr, stopped, thread: Some(thread) })
+    }
+
+    // 实际监听的地址
+    // Address actually listened on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+// 处理一个连接：GET /metrics 返回指标，其他请求返回 404
+// Handle one connection: GET /metrics returns the metrics, any other request gets a 404
+fn serve(pool: &ThreadPool, stream: TcpStream) -> io::Result<()> {
+    // 接受的连接在部分平台上会继承监听器的非阻塞模式
+    // Accepted connections inherit the listener's non-blocking mode on some platforms
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let deadline = DeadlineReader { stream, deadline: Instant::now() + READ_DEADLINE };
+    let mut reader = BufReader::new(deadline.take(MAX_REQUEST_BYTES));
+
+    // 只关心请求行，其余请求头读取后丢弃
+    // Only the request line matters, the remaining headers are read and discarded
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+        header.clear();
+    }
+
+    // 请求超过大小上限时不再响应
+    // Do not respond to a request exceeding the size limit
+    if reader.get_ref().limit() == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "request too large"));
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) if path == "/metrics" || path.starts_with("/metrics?") => {
+            ("200 OK", "text/plain; version=0.0.4; charset=utf-8", pool.metrics_text())
+        }
+        _ => ("404 Not Found", "text/plain; charset=utf-8", String::from("Not Found\n")),
+    };
+
+    let mut stream = reader.into_inner().into_inner().stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()
+}
+
+// 为整个请求设置统一期限的读取器：每次读取前把读超时设为剩余时间，逐字节缓慢发送也无法延长期限
+// Reader enforcing one deadline for the whole request: before every read the read timeout is set to the remaining time, so trickling bytes cannot extend it
+struct DeadlineReader {
+    // 客户端连接
+    // Client connection
+    stream: TcpStream,
+
+    // 读取请求的截止时间
+    // Deadline for reading the request
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request deadline elapsed"));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        // 导出线程在下一次轮询或处理完当前连接后看到停止标志并退出
+        // The exporter thread sees the stop flag and exits at its next poll or after serving the current connection
+        self.stopped.store(true, Ordering::SeqCst);
+
+        if let Some(thread) = self.thread.take() {
+            thread.join().expect("Metrics exporter thread join failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::builder::ThreadPoolBuilder;
+
+    // 发送请求并读取完整响应
+    // Send a request and read the whole response
+    fn request(addr: SocketAddr, request: &[u8]) -> io::Result<String> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        stream.write_all(request)?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    fn exporter() -> MetricsExporter {
+        let pool = Arc::new(ThreadPoolBuilder::new().max_threads(1).build());
+        MetricsExporter::bind(pool, "127.0.0.1:0").expect("Failed to bind metrics exporter")
+    }
+
+    #[test]
+    fn serves_metrics_and_404() {
+        let exporter = exporter();
+        let addr = exporter.local_addr();
+
+        let response = request(addr, b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("thread_pool_max_threads 1\n"));
+        assert!(response.contains("thread_pool_run_time_seconds_bucket{le=\"+Inf\"} 0\n"));
+
+        let response = request(addr, b"GET /other HTTP/1.1\r\n\r\n").unwrap();
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[test]
+    fn oversized_request_is_closed_without_response() {
+        let exporter = exporter();
+        let addr = exporter.local_addr();
+
+        let mut oversized = b"GET /metrics HTTP/1.1\r\n".to_vec();
+        while oversized.len() as u64 <= MAX_REQUEST_BYTES {
+            oversized.extend_from_slice(b"X-Padding: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\r\n");
+        }
+        oversized.extend_from_slice(b"\r\n");
+
+        // 服务端可能在读完之前关闭连接，此时写入或读取会失败
+        // The server may close the connection before reading everything, in which case writing or reading fails
+        if let Ok(response) = request(addr, &oversized) {
+            assert!(response.is_empty());
+        }
+
+        // 之后的抓取不受影响
+        // Later scrapes are unaffected
+        let response = request(addr, b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    }
+
+    #[test]
+    fn drop_stops_exporter_on_wildcard_address() {
+        let pool = Arc::new(ThreadPoolBuilder::new().max_threads(1).build());
+        let exporter = MetricsExporter::bind(pool, "0.0.0.0:0").expect("Failed to bind metrics exporter");
+
+        // 不依赖连接自身即可停止
+        // Stops without relying on connecting to itself
+        let started = Instant::now();
+        drop(exporter);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
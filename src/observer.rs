@@ -0,0 +1,271 @@
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入 io::Write（结构化日志的输出目标）
+// Import io::Write (output target of the structured log)
+use std::io::Write;
+
+// 导入 Mutex（互斥锁，串行化日志写入）
+// Import Mutex (mutual exclusion lock, serializes log writes)
+use std::sync::Mutex;
+
+// 导入 Duration（时间段）和 SystemTime（系统时间）
+// Import Duration (time duration) and SystemTime (system time)
+use std::time::{Duration, SystemTime};
+
+// 导入任务优先级
+// Import task priority
+use crate::queue::Priority;
+
+// 导入提交错误类型
+// Import submit error type
+use crate::rejection::SubmitError;
+
+// 导入任务元数据
+// Import task metadata
+use crate::thread_pool::TaskInfo;
+
+// 线程池事件观察者，所有回调都有空的默认实现，只需实现关心的事件；
+// 回调在产生事件的线程上同步执行（提交者、工作线程或定时器线程），不持有线程池内部的锁，回调中的 panic 被捕获并忽略；
+// 事件在状态变化之后产生，不同线程上的事件可能以交错的顺序到达（例如任务的 started 早于其 submitted）
+// Pool event observer, every callback has an empty default so only the events of interest need implementing;
+// callbacks run synchronously on the thread producing the event (submitter, worker, or timer thread) without holding pool locks, panics inside them are caught and ignored;
+// events fire after the state change, so events from different threads may arrive interleaved (e.g. a task's started before its submitted)
+pub trait PoolObserver: Send + Sync {
+    // 任务已进入队列
+    // A task entered the queue
+    fn on_task_submitted(&self, _task_id: usize, _priority: Priority) {}
+
+    // 任务被工作线程取出，附带排队等待时间；被跳过或丢弃的任务同样会触发
+    // A worker took the task out of the queue, with its queue wait; also fired for tasks that are then skipped or discarded
+    fn on_task_dequeued(&self, _task: &TaskInfo, _queue_wait: Duration) {}
+
+    // 任务开始执行
+    // A task started executing
+    fn on_task_started(&self, _task: &TaskInfo) {}
+
+    // 任务执行结束（未发生 panic），附带执行时间
+    // A task finished executing (without panicking), with its run time
+    fn on_task_finished(&self, _task: &TaskInfo, _run_time: Duration) {}
+
+    // 任务发生 panic，附带 panic 信息
+    // A task panicked, with the panic message
+    fn on_task_panicked(&self, _task: &TaskInfo, _message: &str) {}
+
+    // 任务被拒绝（被拒绝策略处理或移除、被原样返回给提交方，或线程池已关闭）
+    // A task was rejected (handled or removed by the rejection policy, handed back to the submitter, or the pool was shut down)
+    fn on_task_rejected(&self, _error: &SubmitError) {}
+
+    // 创建了新的工作线程
+    // A new worker thread was spawned
+    fn on_worker_spawned(&self, _worker_id: usize) {}
+
+    // 工作线程空闲超过存活时间后被回收
+    // A worker thread was reclaimed after staying idle past the keep-alive time
+    fn on_worker_retired(&self, _worker_id: usize) {}
+
+    // 工作线程因线程池关闭而退出
+    // A worker thread exited because the pool was shut down
+    fn on_worker_exited(&self, _worker_id: usize) {}
+}
+
+// 将事件以 key=value 形式的结构化日志逐行写入任意输出目标的观察者
+// Observer writing every event as one structured key=value log line to any output
+pub struct LogObserver<W> {
+    // 输出目标，使用互斥锁保证每行完整写入
+    // Output target, behind a mutex so every line is written whole
+    out: Mutex<W>,
+}
+
+impl<W: Write + Send> LogObserver<W> {
+    // 创建写入指定输出目标的观察者
+    // Create an observer writing to the given output
+    pub fn new(out: W) -> Self {
+        LogObserver { out: Mutex::new(out) }
+    }
+
+    // 写入一行日志，行首为 Unix 时间戳（秒）和事件名；写入失败时忽略，不影响线程池
+    // Write one log line, starting with the Unix timestamp (seconds) and the event name; write errors are ignored so the pool is unaffected
+    fn log(&self, event: &str, fields: std::fmt::Arguments<'_>) {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        let mut out = self.out.lock().expect("Failed to lock log output mutex");
+        let _ = writeln!(out, "ts={:.6} event={} {}", timestamp, event, fields);
+    }
+}
+
+// 任务元数据的日志字段；任务由拒绝策略在线程池外的调用者线程上执行时省略 worker_id，未命名的任务省略 name
+// Log fields of the task metadata; worker_id is left out when a rejection policy ran the task on a caller outside the pool, name is left out for unnamed tasks
+struct TaskFields<'a>(&'a TaskInfo);
+
+impl fmt::Display for TaskFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task_id={} priority={:?}", self.0.task_id, self.0.priority)?;
+        if let Some(worker_id) = self.0.worker_id {
+            write!(f, " worker_id={}", worker_id)?;
+        }
+
+        // 任务名与 panic 信息一样使用 Debug 格式，带引号并转义引号和换行，保证一行一条日志
+        // The task name uses Debug formatting like the panic message, quoted with quotes and newlines escaped, keeping one log entry per line
+        if let Some(name) = &self.0.name {
+            write!(f, " name={:?}", name)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> PoolObserver for LogObserver<W> {
+    fn on_task_submitted(&self, task_id: usize, priority: Priority) {
+        self.log("task_submitted", format_args!("task_id={} priority={:?}", task_id, priority));
+    }
+
+    fn on_task_dequeued(&self, task: &TaskInfo, queue_wait: Duration) {
+        self.log(
+            "task_dequeued",
+            format_args!("{} queue_wait_us={}", TaskFields(task), queue_wait.as_micros()),
+        );
+    }
+
+    fn on_task_started(&self, task: &TaskInfo) {
+        self.log("task_started", format_args!("{}", TaskFields(task)));
+    }
+
+    fn on_task_finished(&self, task: &TaskInfo, run_time: Duration) {
+        self.log(
+            "task_finished",
+            format_args!("{} run_time_us={}", TaskFields(task), run_time.as_micros()),
+        );
+    }
+
+    fn on_task_panicked(&self, task: &TaskInfo, message: &str) {
+        // panic 信息使用 Debug 格式输出，带引号并转义换行等特殊字符，保证一行一条日志
+        // The panic message uses Debug formatting, quoted with newlines and other special characters escaped, keeping one log entry per line
+        self.log(
+            "task_panicked",
+            format_args!("{} message={:?}", TaskFields(task), message),
+        );
+    }
+
+    fn on_task_rejected(&self, error: &SubmitError) {
+        self.log("task_rejected", format_args!("reason={:?}", error));
+    }
+
+    fn on_worker_spawned(&self, worker_id: usize) {
+        self.log("worker_spawned", format_args!("worker_id={}", worker_id));
+    }
+
+    fn on_worker_retired(&self, worker_id: usize) {
+        self.log("worker_retired", format_args!("worker_id={}", worker_id));
+    }
+
+    fn on_worker_exited(&self, worker_id: usize) {
+        self.log("worker_exited", format_args!("worker_id={}", worker_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Weak};
+
+    use crate::builder::ThreadPoolBuilder;
+    use crate::thread_pool::ThreadPool;
+
+    // 可共享的日志输出缓冲区
+    // Shareable log output buffer
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("Failed to lock buffer").write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuf {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().expect("Failed to lock buffer").clone()).unwrap()
+        }
+    }
+
+    // 统计提交事件的观察者
+    // Observer counting submit events
+    struct Counter(Arc<AtomicUsize>);
+
+    impl PoolObserver for Counter {
+        fn on_task_submitted(&self, _task_id: usize, _priority: Priority) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // 在第一次提交事件中注册一个计数观察者
+    // Registers a counting observer on the first submit event
+    struct Registrar {
+        pool: Weak<ThreadPool>,
+        count: Arc<AtomicUsize>,
+        registered: AtomicUsize,
+    }
+
+    impl PoolObserver for Registrar {
+        fn on_task_submitted(&self, _task_id: usize, _priority: Priority) {
+            if self.registered.fetch_add(1, Ordering::SeqCst) == 0 {
+                let pool = self.pool.upgrade().expect("pool is alive while submitting");
+                pool.add_observer(Counter(self.count.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn observer_registered_from_callback_sees_later_events() {
+        let pool = Arc::new(ThreadPoolBuilder::new().max_threads(1).build());
+        let before = Arc::new(AtomicUsize::new(0));
+        let after = Arc::new(AtomicUsize::new(0));
+        pool.add_observer(Counter(before.clone()));
+        pool.add_observer(Registrar {
+            pool: Arc::downgrade(&pool),
+            count: after.clone(),
+            registered: AtomicUsize::new(0),
+        });
+
+        // 正在通知的快照不包含新注册的观察者，之后的事件才会送达
+        // The snapshot being notified does not include the new observer, only later events reach it
+        pool.submit(|| {}).unwrap();
+        assert_eq!(after.load(Ordering::SeqCst), 0);
+        pool.submit(|| {}).unwrap();
+        pool.submit(|| {}).unwrap();
+        assert_eq!(before.load(Ordering::SeqCst), 3);
+        assert_eq!(after.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn log_fields_include_escaped_name_and_worker() {
+        let buf = SharedBuf::default();
+        let observer = LogObserver::new(buf.clone());
+        let named = TaskInfo {
+            task_id: 7,
+            priority: Priority::High,
+            worker_id: Some(2),
+            name: Some(Arc::from("load \"users\"\nbatch")),
+        };
+        let unnamed = TaskInfo { task_id: 8, priority: Priority::Low, worker_id: None, name: None };
+        observer.on_task_started(&named);
+        observer.on_task_started(&unnamed);
+
+        let out = buf.contents();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(
+            "event=task_started task_id=7 priority=High worker_id=2 name=\"load \\\"users\\\"\\nbatch\""
+        ));
+        assert!(lines[1].ends_with("event=task_started task_id=8 priority=Low"));
+    }
+}
@@ -0,0 +1,296 @@
+// 导入 Range（整数区间）
+// Import Range (integer range)
+use std::ops::Range;
+
+// 导入线程池
+// Import thread pool
+use crate::thread_pool::ThreadPool;
+
+// 自动分块时每个线程平均分到的块数，多于1块以便负载不均时由空闲线程分担
+// Chunks per thread when chunking automatically, more than one so idle threads can pick up the slack of uneven work
+const CHUNKS_PER_THREAD: usize = 4;
+
+// 并行辅助操作的入口，保存分块大小；由 ThreadPool::with_chunk_size 创建，ThreadPool 上的 par_* 方法使用自动分块
+// Entry point of the parallel helpers, holding the chunk size; created by ThreadPool::with_chunk_size, the par_* methods on ThreadPool chunk automatically
+pub struct Parallel<'pool> {
+    // 执行任务的线程池
+    // Thread pool executing the tasks
+    pool: &'pool ThreadPool,
+
+    // 每块的元素数，为 None 时根据最大线程数自动计算
+    // Elements per chunk, computed from the maximum thread count when None
+    chunk_size: Option<usize>,
+}
+
+impl<'pool> Parallel<'pool> {
+    // 计算长度为 len 的数据的分块大小
+    // Compute the chunk size for data of length len
+    fn chunk_size(&self, len: usize) -> usize {
+        self.chunk_size
+            .unwrap_or_else(|| len.div_ceil(self.pool.get_max_threads() * CHUNKS_PER_THREAD))
+            .max(1)
+    }
+
+    // 对区间内的每个下标并行调用 f
+    // Call f for every index in the range in parallel
+    pub fn par_for_each<F>(&self, range: Range<usize>, f: F)
+    where
+        F: Fn(usize) + Sync,
+    {
+        let chunk_size = self.chunk_size(range.len());
+        let mut chunks: Vec<Range<usize>> = range
+            .clone()
+            .step_by(chunk_size)
+            .map(|start| start..(start + chunk_size).min(range.end))
+            .collect();
+
+        self.join_all(&mut chunks, &|chunk: &mut Range<usize>| chunk.clone().for_each(&f));
+    }
+
+    // 对切片中的每个元素并行调用 f，按原顺序返回结果
+    // Call f for every element of the slice in parallel, returning the results in the original order
+    pub fn par_map<T, U, F>(&self, items: &[T], f: F) -> Vec<U>
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(&T) -> U + Sync,
+    {
+        let chunk_size = self.chunk_size(items.len());
+        let mut chunks: Vec<(&[T], Vec<U>)> = items.chunks(chunk_size).map(|chunk| (chunk, Vec::new())).collect();
+
+        self.join_all(&mut chunks, &|(chunk, output): &mut (&[T], Vec<U>)| {
+            *output = chunk.iter().map(&f).collect();
+        });
+
+        // 按块的顺序拼接结果
+        // Concatenate the results in chunk order
+        let mut results = Vec::with_capacity(items.len());
+        for (_, output) in chunks {
+            results.extend(output);
+        }
+        results
+    }
+
+    // 并行归约：每块从 identity() 开始用 op 折叠，再按块的顺序合并各块结果；
+    // op 必须满足结合律，identity() 必须是 op 的单位元；没有元素时返回 identity()
+    // Parallel reduction: every chunk folds with op starting from identity(), then the chunk results are combined in chunk order;
+    // op must be associative and identity() must be its identity element; returns identity() when there are no elements
+    pub fn par_reduce<I, T, ID, OP>(&self, iter: I, identity: ID, op: OP) -> T
+    where
+        I: IntoIterator<Item = T>,
+        T: Send,
+        ID: Fn() -> T + Sync,
+        OP: Fn(T, T) -> T + Sync,
+    {
+        let mut items: Vec<T> = iter.into_iter().collect();
+        let chunk_size = self.chunk_size(items.len());
+
+        // 从尾部依次切下各块，再恢复原顺序
+        // Split chunks off the end one by one, then restore the original order
+        let mut chunks: Vec<(Vec<T>, Option<T>)> = Vec::with_capacity(items.len().div_ceil(chunk_size));
+        while !items.is_empty() {
+            let start = (items.len() - 1) / chunk_size * chunk_size;
+            chunks.push((items.split_off(start), None));
+        }
+        chunks.reverse();
+
+        self.join_all(&mut chunks, &|(chunk, partial): &mut (Vec<T>, Option<T>)| {
+            *partial = Some(chunk.drain(..).fold(identity(), &op));
+        });
+
+        chunks
+            .into_iter()
+            .map(|(_, partial)| partial.expect("Chunk was not reduced"))
+            .fold(identity(), &op)
+    }
+
+    // 将可变切片分块，并行地以每块的起始下标和该块调用 f
+    // Split the mutable slice into chunks and call f with each chunk's start index and the chunk, in parallel
+    pub fn par_chunks_mut<T, F>(&self, data: &mut [T], f: F)
+    where
+        T: Send,
+        F: Fn(usize, &mut [T]) + Sync,
+    {
+        let chunk_size = self.chunk_size(data.len());
+        let mut chunks: Vec<(usize, &mut [T])> = data
+            .chunks_mut(chunk_size)
+            .enumerate()
+            .map(|(index, chunk)| (index * chunk_size, chunk))
+            .collect();
+
+        self.join_all(&mut chunks, &|(start, chunk): &mut (usize, &mut [T])| f(*start, chunk));
+    }
+
+    // 对每块状态调用 f：递归地二分并用 join 并行执行，因此只等待自己的任务，在工作线程中调用时也不会阻塞线程
+    // Call f on every chunk state: halves recursively and runs the halves in parallel with join, so only its own tasks are waited for and calling from a worker never blocks it
+    fn join_all<S, F>(&self, states: &mut [S], f: &F)
+    where
+        S: Send,
+        F: Fn(&mut S) + Sync,
+    {
+        match states.len() {
+            0 => {}
+            1 => f(&mut states[0]),
+            len => {
+                let (left, right) = states.split_at_mut(len / 2);
+                self.pool.join(|| self.join_all(left, f), || self.join_all(right, f));
+            }
+        }
+    }
+}
+
+impl ThreadPool {
+    // 返回使用固定分块大小的并行辅助操作
+    // Return the parallel helpers with a fixed chunk size
+    pub fn with_chunk_size(&self, chunk_size: usize) -> Parallel<'_> {
+        // 分块大小为0时无法划分数据
+        // Data cannot be split with a zero chunk size
+        assert!(chunk_size > 0, "Chunk size must be greater than 0");
+        Parallel { pool: self, chunk_size: Some(chunk_size) }
+    }
+
+    // 使用自动分块的并行辅助操作
+    // Parallel helpers with automatic chunking
+    fn parallel(&self) -> Parallel<'_> {
+        Parallel { pool: self, chunk_size: None }
+    }
+
+    // 对区间内的每个下标并行调用 f，分块大小根据最大线程数自动计算
+    // Call f for every index in the range in parallel, with the chunk size computed from the maximum thread count
+    pub fn par_for_each<F>(&self, range: Range<usize>, f: F)
+    where
+        F: Fn(usize) + Sync,
+    {
+        self.parallel().par_for_each(range, f)
+    }
+
+    // 对切片中的每个元素并行调用 f，按原顺序返回结果，分块大小自动计算
+    // Call f for every element of the slice in parallel, returning the results in the original order, with automatic chunking
+    pub fn par_map<T, U, F>(&self, items: &[T], f: F) -> Vec<U>
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(&T) -> U + Sync,
+    {
+        self.parallel().par_map(items, f)
+    }
+
+    // 并行归约（见 Parallel::par_reduce），分块大小自动计算
+    // Parallel reduction (see Parallel::par_reduce), with automatic chunking
+    pub fn par_reduce<I, T, ID, OP>(&self, iter: I, identity: ID, op: OP) -> T
+    where
+        I: IntoIterator<Item = T>,
+        T: Send,
+        ID: Fn() -> T + Sync,
+        OP: Fn(T, T) -> T + Sync,
+    {
+        self.parallel().par_reduce(iter, identity, op)
+    }
+
+    // 将可变切片分块并行处理（见 Parallel::par_chunks_mut），分块大小自动计算
+    // Process the mutable slice in parallel chunks (see Parallel::par_chunks_mut), with automatic chunking
+    pub fn par_chunks_mut<T, F>(&self, data: &mut [T], f: F)
+    where
+        T: Send,
+        F: Fn(usize, &mut [T]) + Sync,
+    {
+        self.parallel().par_chunks_mut(data, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn par_map_preserves_order() {
+        let pool = ThreadPool::builder().max_threads(4).build();
+        let items: Vec<usize> = (0..1000).collect();
+
+        assert_eq!(pool.par_map(&items, |item| item * 2), (0..1000).map(|item| item * 2).collect::<Vec<_>>());
+        assert_eq!(pool.with_chunk_size(7).par_map(&items, |item| item + 1), (1..1001).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn par_for_each_visits_every_index_once() {
+        let pool = ThreadPool::builder().max_threads(4).build();
+        let visits: Vec<AtomicUsize> = (0..500).map(|_| AtomicUsize::new(0)).collect();
+
+        pool.with_chunk_size(3).par_for_each(0..500, |index| {
+            visits[index].fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert!(visits.iter().all(|count| count.load(Ordering::SeqCst) == 1));
+    }
+
+    #[test]
+    fn empty_inputs_do_nothing() {
+        let pool = ThreadPool::builder().max_threads(2).build();
+        let calls = AtomicUsize::new(0);
+
+        pool.par_for_each(0..0, |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(pool.par_map(&[] as &[usize], |item| *item), Vec::<usize>::new());
+        assert_eq!(pool.par_reduce(Vec::<usize>::new(), || 42, |a, b| a + b), 42);
+        pool.par_chunks_mut(&mut [] as &mut [usize], |_, _| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(pool.completed_tasks(), 0);
+    }
+
+    #[test]
+    fn par_reduce_combines_chunks_in_order() {
+        let pool = ThreadPool::builder().max_threads(4).build();
+        let words: Vec<String> = (0..100).map(|index| index.to_string()).collect();
+
+        // 字符串拼接满足结合律但不满足交换律，结果能反映合并顺序
+        // String concatenation is associative but not commutative, so the result shows the combination order
+        let joined = pool.with_chunk_size(9).par_reduce(words.clone(), String::new, |a, b| a + &b);
+        assert_eq!(joined, words.concat());
+    }
+
+    #[test]
+    #[should_panic(expected = "Chunk size must be greater than 0")]
+    fn zero_chunk_size_panics() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        pool.with_chunk_size(0);
+    }
+
+    #[test]
+    fn par_chunks_mut_passes_chunk_start_indices() {
+        let pool = ThreadPool::builder().max_threads(4).build();
+        let mut data = vec![0; 100];
+        let starts = Mutex::new(Vec::new());
+
+        pool.with_chunk_size(30).par_chunks_mut(&mut data, |start, chunk| {
+            starts.lock().expect("Failed to lock starts").push((start, chunk.len()));
+            for (offset, value) in chunk.iter_mut().enumerate() {
+                *value = start + offset;
+            }
+        });
+
+        let mut starts = starts.into_inner().expect("Failed to lock starts");
+        starts.sort();
+        assert_eq!(starts, vec![(0, 30), (30, 30), (60, 30), (90, 10)]);
+        assert_eq!(data, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn calling_from_a_worker_on_a_single_thread_pool_does_not_deadlock() {
+        let pool = Arc::new(ThreadPool::builder().core_threads(1).max_threads(1).build());
+
+        let worker_pool = Arc::clone(&pool);
+        let mut handle = pool.submit_with_result(move || {
+            let items: Vec<usize> = (0..100).collect();
+            worker_pool.with_chunk_size(10).par_map(&items, |item| item * 2).into_iter().sum::<usize>()
+        });
+
+        assert_eq!(handle.join_timeout(Duration::from_secs(10)), Some(Ok(9900)));
+    }
+}
@@ -0,0 +1,280 @@
+// 导入 VecDeque（双端队列，标准库实现）
+// Import VecDeque (double-ended queue, standard library implementation)
+use std::collections::VecDeque;
+
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 导入队列中的任务条目
+// Import task entry stored in the queue
+use crate::thread_pool::Job;
+
+// 任务优先级，数值越大越先执行
+// Task priority, higher values run first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    // 低优先级，适合批处理等后台任务
+    // Low priority, suitable for batch and background work
+    Low = 0,
+
+    // 普通优先级，submit 提交的任务默认使用
+    // Normal priority, used by default for tasks submitted with submit
+    #[default]
+    Normal = 1,
+
+    // 高优先级，适合紧急任务
+    // High priority, suitable for urgent work
+    High = 2,
+}
+
+impl Priority {
+    // 优先级级别数量
+    // Number of priority levels
+    pub const COUNT: usize = 3;
+
+    // 从高到低排列的所有优先级
+    // All priorities ordered from high to low
+    pub const ALL: [Priority; Priority::COUNT] = [Priority::High, Priority::Normal, Priority::Low];
+
+    // 优先级在队列数组中的下标
+    // Index of the priority in the queue array
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// 队列条目，记录任务进入当前级别的时间，用于老化
+// Queue entry, records when the task entered its current level, used for aging
+struct Entry {
+    // 进入当前级别的时间
+    // Time the task entered its current level
+    since: Instant,
+
+    // 任务本身
+    // The task itself
+    job: Job,
+}
+
+// 按优先级分级的任务队列，同一级别内保持先进先出
+// Task queue split by priority level, FIFO within each level
+pub(crate) struct TaskQueue {
+    // 每个优先级一个先进先出队列，下标即优先级数值
+    // One FIFO queue per priority, the index is the priority value
+    levels: [VecDeque<Entry>; Priority::COUNT],
+
+    // 老化间隔：任务在某一级别等待超过该时间后提升一级，为 None 时不老化
+    // Aging interval: a task waiting longer than this at a level is promoted one level, no aging when None
+    aging: Option<Duration>,
+
+    // 所有级别的任务总数
+    // Total task count across all levels
+    len: usize,
+}
+
+impl TaskQueue {
+    // 创建空队列
+    // Create an empty queue
+    pub(crate) fn new(aging: Option<Duration>) -> Self {
+        TaskQueue {
+            levels: [VecDeque::new(), VecDeque::new(), VecDeque::new()],
+            aging,
+            len: 0,
+        }
+    }
+
+    // 队列中的任务总数
+    // Total number of queued tasks
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    // 队列是否为空
+    // Whether the queue is empty
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 按任务优先级加入对应级别的队尾
+    // Push the task to the back of the level matching its priority
+    pub(crate) fn push(&mut self, job: Job) {
+        let level = job.priority.index();
+        self.levels[level].push_back(Entry { since: Instant::now(), job });
+        self.len += 1;
+    }
+
+    // 先执行老化，再从最高的非空级别取出最早的任务
+    // Apply aging first, then take the earliest task from the highest non-empty level
+    pub(crate) fn pop(&mut self) -> Option<Job> {
+        self.promote_aged();
+
+        let entry = self.levels.iter_mut().rev().find_map(|level| level.pop_front())?;
+        self.len -= 1;
+        Some(entry.job)
+    }
+
+    // 从最低的非空级别取出最旧的任务，用于丢弃最不重要的任务
+    // Take the oldest task from the lowest non-empty level, used to discard the least important task
+    pub(crate) fn pop_oldest(&mut self) -> Option<Job> {
+        let entry = self.levels.iter_mut().find_map(|level| level.pop_front())?;
+        self.len -= 1;
+        Some(entry.job)
+    }
+
+    // 按优先级从高到低取出所有任务
+    // Take all tasks out, ordered from high to low priority
+    pub(crate) fn drain(&mut self) -> Vec<Job> {
+        self.len = 0;
+        self.levels
+            .iter_mut()
+            .rev()
+            .flat_map(|level| level.drain(..).map(|entry| entry.job))
+            .collect()
+    }
+
+    // 每个优先级当前排队的任务数（按老化后的实际级别统计），从高到低排列
+    // Number of tasks queued at each priority (by effective level after aging), ordered from high to low
+    pub(crate) fn depths(&self) -> Vec<(Priority, usize)> {
+        Priority::ALL
+            .iter()
+            .map(|&priority| (priority, self.levels[priority.index()].len()))
+            .collect()
+    }
+
+    // 将等待时间超过老化间隔的任务提升一级，防止低优先级任务饿死
+    // Promote tasks that waited longer than the aging interval by one level, preventing starvation of low priorities
+    fn promote_aged(&mut self) {
+        let aging = match self.aging {
+            Some(aging) => aging,
+            None => return,
+        };
+        let now = Instant::now();
+
+        // 从最低级别向上处理，等待了多个老化间隔的任务可以在同一轮中连续提升
+        // Process from the lowest level upwards, so a task that waited several aging intervals is promoted repeatedly in one pass
+        for level in 0..Priority::COUNT - 1 {
+            // 同一级别内按进入时间排序，只需检查队头
+            // Entries within a level are ordered by entry time, so only the head needs checking
+            while self.levels[level]
+                .front()
+                .is_some_and(|entry| now.duration_since(entry.since) >= aging)
+            {
+                let mut entry = self.levels[level].pop_front().expect("Aged entry disappeared");
+
+                // 以应当提升的时刻作为进入新级别的时间，并按该时间插入以保持级别内有序
+                // Enter the new level at the moment the promotion was due, inserted by that time to keep the level ordered
+                entry.since += aging;
+                let next = &mut self.levels[level + 1];
+                let position = next.partition_point(|queued| queued.since <= entry.since);
+                next.insert(position, entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn job(id: usize, priority: Priority) -> Job {
+        Job {
+            id,
+            priority,
+            token: None,
+            deadline: None,
+            on_expired: None,
+            enqueued_at: Instant::now(),
+            name: None,
+            repoll: false,
+            task: Box::new(|| {}),
+        }
+    }
+
+    // 依次取出所有任务的 ID
+    // Pop every task and collect the IDs in order
+    fn pop_ids(queue: &mut TaskQueue) -> Vec<usize> {
+        std::iter::from_fn(|| queue.pop()).map(|job| job.id).collect()
+    }
+
+    #[test]
+    fn pops_by_priority_then_fifo_within_a_level() {
+        let mut queue = TaskQueue::new(None);
+        queue.push(job(0, Priority::Low));
+        queue.push(job(1, Priority::Normal));
+        queue.push(job(2, Priority::High));
+        queue.push(job(3, Priority::Normal));
+        queue.push(job(4, Priority::High));
+        queue.push(job(5, Priority::Low));
+
+        assert_eq!(queue.len(), 6);
+        assert_eq!(
+            queue.depths(),
+            vec![(Priority::High, 2), (Priority::Normal, 2), (Priority::Low, 2)]
+        );
+        assert_eq!(pop_ids(&mut queue), vec![2, 4, 1, 3, 0, 5]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn waiting_tasks_are_promoted_after_the_aging_interval() {
+        let mut queue = TaskQueue::new(Some(Duration::from_millis(30)));
+        queue.push(job(0, Priority::Low));
+        thread::sleep(Duration::from_millis(40));
+        queue.push(job(1, Priority::Normal));
+
+        // 低优先级任务已提升到普通级别，且比新的普通任务更早进入该级别
+        // The low task was promoted to normal and entered that level before the new normal task
+        assert_eq!(pop_ids(&mut queue), vec![0, 1]);
+    }
+
+    #[test]
+    fn tasks_are_not_promoted_without_aging() {
+        let mut queue = TaskQueue::new(None);
+        queue.push(job(0, Priority::Low));
+        thread::sleep(Duration::from_millis(40));
+        queue.push(job(1, Priority::Normal));
+
+        assert_eq!(pop_ids(&mut queue), vec![1, 0]);
+    }
+
+    #[test]
+    fn pop_oldest_takes_from_the_lowest_level_first() {
+        let mut queue = TaskQueue::new(None);
+        queue.push(job(0, Priority::High));
+        queue.push(job(1, Priority::Normal));
+        queue.push(job(2, Priority::Low));
+        queue.push(job(3, Priority::Low));
+
+        let oldest: Vec<usize> = std::iter::from_fn(|| queue.pop_oldest()).map(|job| job.id).collect();
+        assert_eq!(oldest, vec![2, 3, 1, 0]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn drain_returns_tasks_from_high_to_low() {
+        let mut queue = TaskQueue::new(None);
+        queue.push(job(0, Priority::Low));
+        queue.push(job(1, Priority::High));
+        queue.push(job(2, Priority::Normal));
+
+        let drained: Vec<usize> = queue.drain().into_iter().map(|job| job.id).collect();
+        assert_eq!(drained, vec![1, 2, 0]);
+        assert_eq!(queue.len(), 0);
+    }
+}
@@ -0,0 +1,387 @@
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入任务优先级
+// Import task priority
+use crate::queue::Priority;
+
+// 导入 Arc（原子引用计数）
+// Import Arc (atomic reference counting)
+use std::sync::Arc;
+
+// 导入线程池及其内部状态、任务条目和任务类型
+// Import thread pool and its internal state, task entry and task type
+use crate::thread_pool::{Inner, Job, Task, ThreadPool};
+
+// 任务提交失败的原因
+// Reason why a task submission failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitError {
+    // 线程池已饱和，任务被拒绝策略拒绝；无法创建定时器线程时延迟任务也以此拒绝
+    // Pool is saturated, the task was rejected by the rejection policy; delayed tasks are rejected this way too when the timer thread cannot be created
+    Rejected,
+
+    // 线程池已关闭，不再接收新任务
+    // Pool has been shut down and no longer accepts new tasks
+    ShutDown,
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Rejected => write!(f, "task rejected: thread pool is saturated"),
+            SubmitError::ShutDown => write!(f, "task rejected: thread pool has been shut down"),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+// 被拒绝的任务，保留提交时的优先级、取消令牌、截止时间和任务名，重新入队或在调用者线程上执行时这些设置依然生效；
+// 任务在被丢弃、执行或取出闭包时计入被拒绝的任务数并通知观察者，被策略重新入队的任务不算被拒绝
+// A rejected task, keeping the priority, cancellation token, deadline and name given at submission, which still apply when it is requeued or run on the caller;
+// the task counts as rejected and observers are notified once it is dropped, run or unwrapped, a task requeued by the policy is not rejected after all
+pub struct RejectedTask {
+    // 被拒绝的任务条目，计为被拒绝或重新入队后为 None
+    // The rejected task entry, None once counted as rejected or requeued
+    job: Option<Job>,
+
+    // 线程池内部状态，用于记录拒绝
+    // Pool internals, used to record the rejection
+    inner: Arc<Inner>,
+}
+
+impl RejectedTask {
+    // 包装被拒绝的任务条目
+    // Wrap a rejected task entry
+    pub(crate) fn new(job: Job, inner: &Arc<Inner>) -> Self {
+        RejectedTask { job: Some(job), inner: Arc::clone(inner) }
+    }
+
+    // 被拒绝的任务条目
+    // The rejected task entry
+    fn job(&self) -> &Job {
+        self.job.as_ref().expect("Rejected task already taken")
+    }
+
+    // 取出任务条目并记为被拒绝
+    // Take out the task entry and record it as rejected
+    fn take_rejected(mut self) -> Job {
+        let job = self.job.take().expect("Rejected task already taken");
+        self.inner.reject(SubmitError::Rejected);
+        job
+    }
+
+    // 任务编号
+    // Task id
+    pub fn task_id(&self) -> usize {
+        self.job().id
+    }
+
+    // 提交时指定的任务优先级
+    // Task priority given at submission
+    pub fn priority(&self) -> Priority {
+        self.job().priority
+    }
+
+    // 提交时指定的任务名，未命名时为 None
+    // Task name given at submission, None for unnamed tasks
+    pub fn name(&self) -> Option<&str> {
+        self.job().name.as_deref()
+    }
+
+    // 在当前线程上执行任务，与工作线程一样检查令牌和截止时间、捕获 panic 并更新统计和通知观察者
+    // Run the task on the current thread, checking the token and deadline, catching panics, and updating stats and observers just like a worker does
+    pub fn run(self, pool: &ThreadPool) {
+        pool.inner.run_on_caller(self.take_rejected());
+    }
+
+    // 不等待地尝试将任务重新放入队列，队列仍满或线程池已关闭时原样返回；重新入队的任务不计入被拒绝的任务数
+    // Try to put the task back into the queue without waiting, returned as is when the queue is still full or the pool is shut down; a requeued task is not counted as rejected
+    pub fn try_requeue(mut self, pool: &ThreadPool) -> Result<(), RejectedTask> {
+        let job = self.job.take().expect("Rejected task already taken");
+        pool.try_submit_job(job).map_err(|job| {
+            self.job = Some(job);
+            self
+        })
+    }
+
+    // 丢弃任务的设置，只取出任务闭包
+    // Drop the task's settings and take out only the closure
+    pub fn into_task(self) -> Task {
+        self.take_rejected().task
+    }
+}
+
+impl Drop for RejectedTask {
+    fn drop(&mut self) {
+        // 任务未被执行、取出或重新入队就被丢弃，计为被拒绝
+        // The task is dropped without being run, unwrapped or requeued, so it counts as rejected
+        if self.job.take().is_some() {
+            self.inner.reject(SubmitError::Rejected);
+        }
+    }
+}
+
+// 拒绝策略：线程池已达到最大线程数且队列已满时，决定如何处理新任务；
+// 策略只处理线程池饱和的情况，线程池关闭后提交直接返回 SubmitError::ShutDown 而不经过策略，避免关闭后任务仍在调用者线程上执行
+// Rejection policy: decides what happens to a new task when the pool is at max threads and the queue is full;
+// policies only handle saturation, submitting after shutdown returns SubmitError::ShutDown without consulting the policy, so no task runs on a caller after shutdown
+pub trait RejectionPolicy: Send + Sync {
+    // 处理被拒绝的任务，返回 Err 时提交方会收到该错误
+    // Handle the rejected task, the submitter receives the error when Err is returned
+    fn reject(&self, task: RejectedTask, pool: &ThreadPool) -> Result<(), SubmitError>;
+}
+
+// 中止策略：丢弃任务并向提交方返回错误
+// Abort policy: drops the task and returns an error to the submitter
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Abort;
+
+// 调用者执行策略：在提交任务的线程上直接执行任务，从而自然地减缓提交速度；
+// 任务的 panic 被捕获并交给 panic 处理器，不会传播到提交方
+// Caller-runs policy: executes the task on the submitting thread, naturally slowing down the producer;
+// a panic in the task is caught and handed to the panic handler instead of reaching the submitter
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CallerRuns;
+
+// 丢弃最旧策略：移除队列中最低优先级的最旧任务，为新任务腾出空位，被移除的任务计为被拒绝；
+// 队列已满却没有可移除的任务时（例如其他提交者已预留位置但尚未放入任务）拒绝新任务
+// Discard-oldest policy: removes the oldest queued task of the lowest priority to make room for the new one, the removed task counts as rejected;
+// rejects the new task when the queue is full but nothing can be removed (e.g. other submitters reserved places but have not pushed yet)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiscardOldest;
+
+// 丢弃策略：静默丢弃新任务
+// Discard policy: silently drops the new task
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Discard;
+
+impl RejectionPolicy for Abort {
+    fn reject(&self, _task: RejectedTask, _pool: &ThreadPool) -> Result<(), SubmitError> {
+        Err(SubmitError::Rejected)
+    }
+}
+
+impl RejectionPolicy for CallerRuns {
+    fn reject(&self, task: RejectedTask, pool: &ThreadPool) -> Result<(), SubmitError> {
+        // 在当前线程执行任务
+        // Execute the task on the current thread
+        task.run(pool);
+        Ok(())
+    }
+}
+
+impl RejectionPolicy for DiscardOldest {
+    fn reject(&self, mut task: RejectedTask, pool: &ThreadPool) -> Result<(), SubmitError> {
+        // 其他提交者可能抢先占用腾出的空位，因此循环直到新任务入队；每轮都移除了一个任务，不会空转
+        // Other submitters may grab the freed slot first, so loop until the new task is queued; every round removes a task, so it never spins idle
+        loop {
+            // 线程池已关闭时重新入队永远不会成功
+            // Requeueing can never succeed once the pool is shut down
+            if pool.is_shutdown() {
+                return Err(SubmitError::ShutDown);
+            }
+
+            // 丢弃最低优先级中最旧的任务
+            // Drop the oldest task of the lowest priority
+            let discarded = pool.take_oldest();
+
+            match task.try_requeue(pool) {
+                Ok(()) => return Ok(()),
+
+                // 没有可丢弃的任务而队列仍满，再次尝试只会空转，拒绝新任务
+                // Nothing to discard while the queue is still full, retrying would only spin, so reject the new task
+                Err(_) if discarded.is_none() => return Err(SubmitError::Rejected),
+                Err(returned) => task = returned,
+            }
+        }
+    }
+}
+
+impl RejectionPolicy for Discard {
+    fn reject(&self, _task: RejectedTask, _pool: &ThreadPool) -> Result<(), SubmitError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancel::CancellationToken;
+    use crate::lock_free::QueueKind;
+    use crate::test_util::blocked_pool;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::thread;
+
+    // 单线程、队列容量有限的线程池，唯一的工作线程阻塞到返回的发送端被使用或丢弃
+    // Single-thread pool with a bounded queue whose only worker is blocked until the returned sender is used or dropped
+    fn saturated_pool<P: RejectionPolicy + 'static>(kind: QueueKind, policy: P, capacity: usize) -> (ThreadPool, mpsc::Sender<()>) {
+        blocked_pool(ThreadPool::builder().queue_capacity(capacity).queue_kind(kind).rejection_policy(policy))
+    }
+
+    #[test]
+    fn discard_oldest_keeps_priority_and_name_of_requeued_tasks() {
+        for kind in [QueueKind::Locked, QueueKind::LockFree] {
+            let (pool, release) = saturated_pool(kind, DiscardOldest, 2);
+            let order = Arc::new(Mutex::new(Vec::new()));
+
+            for label in ["first", "second"] {
+                let order = Arc::clone(&order);
+                pool.submit_named(label, move || order.lock().unwrap().push(label)).unwrap();
+            }
+
+            // 队列已满，丢弃最旧的任务后新任务以原有的高优先级重新入队
+            // The queue is full, after the oldest task is discarded the new one is requeued with its high priority
+            let urgent = Arc::clone(&order);
+            pool.submit_with_priority(Priority::High, move || urgent.lock().unwrap().push("urgent")).unwrap();
+
+            // 再次被拒绝的命名任务重新入队后保留名称
+            // A named task rejected in turn keeps its name once requeued
+            let late = Arc::clone(&order);
+            pool.submit_named("late", move || late.lock().unwrap().push("late")).unwrap();
+
+            let queued: Vec<_> = pool.queued_tasks().into_iter().map(|(name, _)| name.map(|name| name.to_string())).collect();
+            assert_eq!(queued, [None, Some("late".to_string())]);
+
+            drop(release);
+            pool.wait_for_completion();
+            assert_eq!(*order.lock().unwrap(), ["urgent", "late"]);
+            assert_eq!(pool.rejected_tasks(), 2);
+        }
+    }
+
+    #[test]
+    fn discard_oldest_counts_the_discarded_task_instead_of_the_requeued_one() {
+        for kind in [QueueKind::Locked, QueueKind::LockFree] {
+            let (pool, release) = saturated_pool(kind, DiscardOldest, 1);
+            let ran = Arc::new(Mutex::new(Vec::new()));
+            for label in ["oldest", "newest"] {
+                let ran = Arc::clone(&ran);
+                pool.submit(move || ran.lock().unwrap().push(label)).unwrap();
+            }
+
+            // 最旧的任务被移除并计为被拒绝，重新入队的新任务不算被拒绝
+            // The oldest task was removed and counted as rejected, the requeued new task is not
+            assert_eq!(pool.rejected_tasks(), 1);
+            assert_eq!(pool.queue_len(), 1);
+
+            drop(release);
+            pool.wait_for_completion();
+            assert_eq!(*ran.lock().unwrap(), ["newest"]);
+            assert_eq!(pool.rejected_tasks(), 1);
+        }
+    }
+
+    #[test]
+    fn returned_tasks_count_as_rejected_whether_full_or_shut_down() {
+        for kind in [QueueKind::Locked, QueueKind::LockFree] {
+            let (pool, release) = blocked_pool(ThreadPool::builder().queue_capacity(1).queue_kind(kind));
+            pool.submit(|| {}).unwrap();
+
+            // 队列已满时原样返回的任务计为被拒绝
+            // Tasks handed back because the queue is full count as rejected
+            assert!(pool.try_submit(|| {}).is_err());
+            assert!(pool.submit_timeout(|| {}, std::time::Duration::from_millis(10)).is_err());
+            assert_eq!(pool.rejected_tasks(), 2);
+
+            // 关闭后原样返回的任务同样计为被拒绝
+            // Tasks handed back after shutdown count as rejected as well
+            pool.shutdown();
+            assert!(pool.try_submit(|| {}).is_err());
+            assert!(pool.submit_timeout(|| {}, std::time::Duration::from_millis(10)).is_err());
+            assert_eq!(pool.rejected_tasks(), 4);
+
+            drop(release);
+            pool.wait_for_completion();
+        }
+    }
+
+    #[test]
+    fn discard_oldest_keeps_cancellation_token() {
+        let (pool, release) = saturated_pool(QueueKind::Locked, DiscardOldest, 1);
+        pool.submit(|| {}).unwrap();
+
+        let token = CancellationToken::new();
+        let ran = Arc::new(AtomicUsize::new(0));
+        let task_ran = Arc::clone(&ran);
+        pool.submit_cancellable(&token, move |_| {
+            task_ran.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        token.cancel();
+
+        drop(release);
+        pool.wait_for_completion();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        assert_eq!(pool.cancelled_tasks(), 1);
+    }
+
+    #[test]
+    fn caller_runs_catches_panics_and_counts_them() {
+        for kind in [QueueKind::Locked, QueueKind::LockFree] {
+            let (pool, release) = saturated_pool(kind, CallerRuns, 1);
+            pool.submit(|| {}).unwrap();
+
+            let handled = Arc::new(Mutex::new(Vec::new()));
+            let seen = Arc::clone(&handled);
+            pool.set_panic_handler(move |_, info| seen.lock().unwrap().push(info.worker_id));
+
+            // 任务在提交者线程上 panic，submit 仍然正常返回
+            // The task panics on the submitting thread, submit still returns normally
+            let caller = thread::current().id();
+            let ran_on = Arc::new(Mutex::new(None));
+            let task_ran_on = Arc::clone(&ran_on);
+            let result = pool.submit(move || {
+                *task_ran_on.lock().unwrap() = Some(thread::current().id());
+                panic!("caller-run task failed");
+            });
+
+            assert_eq!(result, Ok(()));
+            assert_eq!(*ran_on.lock().unwrap(), Some(caller));
+            assert_eq!(*handled.lock().unwrap(), [None]);
+            assert_eq!(pool.panicked_tasks(), 1);
+
+            drop(release);
+            pool.wait_for_completion();
+            assert_eq!(pool.submitted_tasks(), 3);
+        }
+    }
+
+    #[test]
+    fn caller_runs_skips_expired_tasks() {
+        let (pool, release) = saturated_pool(QueueKind::Locked, CallerRuns, 1);
+        pool.submit(|| {}).unwrap();
+
+        let expired = Arc::new(AtomicUsize::new(0));
+        let on_expired = Arc::clone(&expired);
+        let deadline = std::time::Instant::now();
+        pool.submit_with_deadline_or_else(deadline, || panic!("expired task ran"), move || {
+            on_expired.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        assert_eq!(expired.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.expired_tasks(), 1);
+
+        drop(release);
+        pool.wait_for_completion();
+    }
+
+    #[test]
+    fn policy_is_not_consulted_after_shutdown() {
+        let pool = ThreadPool::builder().max_threads(1).queue_capacity(1).rejection_policy(CallerRuns).build();
+        pool.shutdown();
+
+        let ran = Arc::new(AtomicUsize::new(0));
+        let task_ran = Arc::clone(&ran);
+        let result = pool.submit(move || {
+            task_ran.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(result, Err(SubmitError::ShutDown));
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,324 @@
+// 导入 Any（任意类型，用于 panic 载荷）
+// Import Any (arbitrary type, used for panic payloads)
+use std::any::Any;
+
+// 导入 PhantomData（用于标记生命周期）
+// Import PhantomData (used to mark lifetimes)
+use std::marker::PhantomData;
+
+// 导入 panic 模块和 AssertUnwindSafe（用于捕获任务 panic）
+// Import panic module and AssertUnwindSafe (used to catch task panics)
+use std::panic::{self, AssertUnwindSafe};
+
+// 导入 Arc（原子引用计数）、Mutex（互斥锁）和 Condvar（条件变量）
+// Import Arc (atomic reference counting), Mutex (mutual exclusion lock), and Condvar (condition variable)
+use std::sync::{Arc, Condvar, Mutex};
+
+// 导入线程池
+// Import thread pool
+use crate::thread_pool::ThreadPool;
+
+// 作用域内所有任务共享的状态
+// State shared by all tasks within a scope
+struct ScopeState {
+    // 尚未结束的作用域任务数
+    // Number of scoped tasks that have not finished yet
+    pending: Mutex<usize>,
+
+    // 所有任务结束时通知作用域的条件变量
+    // Condition variable notifying the scope when all tasks have finished
+    done: Condvar,
+
+    // 第一个发生 panic 的任务的载荷，作用域结束时重新抛出
+    // Payload of the first panicked task, re-thrown when the scope ends
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+}
+
+// 作用域句柄，可在其中提交借用栈上数据的任务
+// Scope handle, tasks borrowing stack data can be spawned through it
+pub struct Scope<'scope, 'env: 'scope> {
+    // 执行任务的线程池
+    // Thread pool executing the tasks
+    pool: &'scope ThreadPool,
+
+    // 作用域共享状态
+    // Shared scope state
+    state: Arc<ScopeState>,
+
+    // 与 std::thread::Scope 相同，使 'scope 和 'env 保持不变性
+    // Same as std::thread::Scope, keeps 'scope and 'env invariant
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+// 包装作用域任务，无论执行还是被丢弃都会减少待完成计数
+// Wraps a scoped task, the pending count is decremented whether it runs or gets dropped
+struct ScopedTask<F> {
+    // 待执行的任务，执行后置为 None
+    // Task to execute, set to None after running
+    task: Option<F>,
+
+    // 作用域共享状态
+    // Shared scope state
+    state: Arc<ScopeState>,
+}
+
+impl ScopeState {
+    // 记录任务 panic，只保留第一个载荷
+    // Record a task panic, only the first payload is kept
+    fn record_panic(&self, payload: Box<dyn Any + Send>) {
+        let mut panic = self.panic.lock().expect("Failed to lock scope panic mutex");
+        if panic.is_none() {
+            *panic = Some(payload);
+        }
+    }
+}
+
+impl<F: FnOnce()> ScopedTask<F> {
+    // 执行任务并记录可能发生的 panic
+    // Execute the task and record a possible panic
+    fn run(mut self) {
+        if let Some(task) = self.task.take() {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(task)) {
+                self.state.record_panic(payload);
+            }
+        }
+    } // self 在此处被 drop，减少待完成计数
+      // self is dropped here, decrementing the pending count
+}
+
+impl<F> Drop for ScopedTask<F> {
+    fn drop(&mut self) {
+        // 任务未执行就被丢弃（例如被拒绝策略丢弃），作为 panic 报告给作用域
+        // Task dropped without running (e.g. discarded by the rejection policy), report it to the scope as a panic
+        if self.task.is_some() {
+            self.state.record_panic(Box::new("scoped task was dropped before it ran"));
+        }
+
+        // 先丢弃任务（及其借用的数据），再通知作用域，保证作用域返回后不再访问借用数据
+        // Drop the task (and its borrows) first, then notify the scope, so borrows are never touched after the scope returns
+        drop(self.task.take());
+
+        let mut pending = self.state.pending.lock().expect("Failed to lock scope pending mutex");
+        *pending -= 1;
+
+        // 最后一个任务结束时唤醒作用域
+        // Wake the scope when the last task finishes
+        if *pending == 0 {
+            self.state.done.notify_all();
+        }
+    }
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    // 在线程池中执行可借用作用域外数据的任务
+    // Execute a task on the thread pool that may borrow data from outside the scope
+    pub fn spawn<F>(&'scope self, task: F)
+    where
+        F: FnOnce() + Send + 'scope, // 任务只需存活到作用域结束
+                                     // Task only needs to live until the scope ends
+    {
+        // 先增加待完成计数，再提交任务
+        // Increment the pending count before submitting the task
+        *self.state.pending.lock().expect("Failed to lock scope pending mutex") += 1;
+
+        let scoped = ScopedTask {
+            task: Some(task),
+            state: Arc::clone(&self.state),
+        };
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || scoped.run());
+
+        // SAFETY: 作用域在所有任务执行或丢弃之前不会返回（见 ThreadPool::scope），
+        // 因此任务借用的数据在任务存活期间始终有效，可以安全地擦除为 'static
+        // SAFETY: the scope does not return until every task has run or been dropped (see ThreadPool::scope),
+        // so data borrowed by the task stays valid for the task's whole life and erasing to 'static is sound
+        let job: Box<dyn FnOnce() + Send + 'static> = unsafe { std::mem::transmute(job) };
+
+        // 被拒绝的任务会在丢弃时记录 panic，因此忽略提交错误
+        // A rejected task records a panic when dropped, so the submit error is ignored
+        let _ = self.pool.submit(job);
+    }
+
+    // 等待作用域内的所有任务结束；在工作线程上等待时先帮助执行排队的任务（其中可能就有本作用域的任务），
+    // 与 join 一样，在任务中使用作用域不会因线程数达到上限而死锁
+    // Wait for all tasks within the scope to finish; when waiting on a worker, help run queued tasks first (which may include this scope's own tasks),
+    // so like join, using a scope inside a task cannot deadlock once the pool reaches max threads
+    fn wait(&self) {
+        while *self.state.pending.lock().expect("Failed to lock scope pending mutex") > 0 && self.pool.help_one() {}
+
+        let mut pending = self.state.pending.lock().expect("Failed to lock scope pending mutex");
+        while *pending > 0 {
+            pending = self.state.done.wait(pending).expect("Condvar wait failed");
+        }
+    }
+}
+
+impl ThreadPool {
+    // 创建任务作用域，作用域内提交的任务可以借用非 'static 数据，
+    // 所有任务结束后才返回；任务中的 panic 会在返回时重新抛出
+    // Create a task scope, tasks spawned inside may borrow non-'static data;
+    // returns only after all tasks have finished, panics in tasks are re-thrown on return
+    pub fn scope<'env, F, T>(&self, f: F) -> T
+    where
+        F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+    {
+        let scope = Scope {
+            pool: self,
+            state: Arc::new(ScopeState {
+                pending: Mutex::new(0),
+                done: Condvar::new(),
+                panic: Mutex::new(None),
+            }),
+            scope: PhantomData,
+            env: PhantomData,
+        };
+
+        // 即使作用域闭包自身 panic，也必须等待已提交的任务结束
+        // Even if the scope closure itself panics, submitted tasks must be waited for
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+        scope.wait();
+
+        // 作用域闭包的 panic 优先传播
+        // Panic from the scope closure propagates first
+        let value = match result {
+            Ok(value) => value,
+            Err(payload) => panic::resume_unwind(payload),
+        };
+
+        // 传播任务中发生的 panic
+        // Propagate panic that happened in a task
+        let task_panic = scope.state.panic.lock().expect("Failed to lock scope panic mutex").take();
+        if let Some(payload) = task_panic {
+            panic::resume_unwind(payload);
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rejection::Abort;
+    use crate::test_util::blocked_pool;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    // 被丢弃时设置标志，用于确认任务（及其借用）在作用域返回前已被丢弃
+    // Sets a flag when dropped, used to check that a task (and its borrows) was dropped before the scope returned
+    struct DropFlag<'a>(&'a AtomicBool);
+
+    impl Drop for DropFlag<'_> {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn tasks_mutate_borrowed_stack_data() {
+        let pool = ThreadPool::with_max_threads(4);
+        let mut values = vec![0; 16];
+
+        pool.scope(|scope| {
+            for (index, value) in values.iter_mut().enumerate() {
+                scope.spawn(move || *value = index * 2);
+            }
+        });
+
+        assert_eq!(values, (0..16).map(|index| index * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn scope_inside_a_task_on_single_worker_does_not_deadlock() {
+        let pool = Arc::new(ThreadPool::builder().core_threads(1).max_threads(1).build());
+
+        // 唯一的工作线程在作用域中等待，作用域任务只能由它自己帮助执行
+        // The only worker waits in the scope, so the scoped tasks can only run by it helping
+        let worker_pool = Arc::clone(&pool);
+        let mut handle = pool.submit_with_result(move || {
+            let mut values = [0; 4];
+            worker_pool.scope(|scope| {
+                for (index, value) in values.iter_mut().enumerate() {
+                    scope.spawn(move || *value = index + 1);
+                }
+            });
+            values.iter().sum::<usize>()
+        });
+
+        assert_eq!(handle.join_timeout(Duration::from_secs(10)), Some(Ok(10)));
+    }
+
+    #[test]
+    fn rejected_task_is_dropped_before_scope_returns() {
+        // 阻塞唯一的工作线程并占满队列，使作用域任务被拒绝
+        // Block the only worker and fill the queue so the scoped task gets rejected
+        let (pool, release) = blocked_pool(ThreadPool::builder().queue_capacity(1).rejection_policy(Abort));
+        pool.submit(|| {}).unwrap();
+
+        let dropped = AtomicBool::new(false);
+        let ran = AtomicBool::new(false);
+        let payload = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.scope(|scope| {
+                let flag = DropFlag(&dropped);
+                let ran = &ran;
+                scope.spawn(move || {
+                    let _flag = flag;
+                    ran.store(true, Ordering::SeqCst);
+                });
+            })
+        }))
+        .unwrap_err();
+
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"scoped task was dropped before it ran"));
+        assert!(dropped.load(Ordering::SeqCst));
+        assert!(!ran.load(Ordering::SeqCst));
+        assert_eq!(pool.rejected_tasks(), 1);
+
+        drop(release);
+        pool.wait_for_completion();
+    }
+
+    #[test]
+    fn scope_waits_for_tasks_when_closure_panics() {
+        let pool = ThreadPool::with_max_threads(2);
+        let finished = AtomicUsize::new(0);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.scope(|scope| {
+                for _ in 0..4 {
+                    scope.spawn(|| {
+                        thread::sleep(Duration::from_millis(20));
+                        finished.fetch_add(1, Ordering::SeqCst);
+                    });
+                }
+                panic!("scope closure failed");
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(finished.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn task_panic_is_rethrown_after_all_tasks_finish() {
+        let pool = ThreadPool::with_max_threads(2);
+        let finished = AtomicUsize::new(0);
+
+        let payload = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.scope(|scope| {
+                scope.spawn(|| panic!("task failed"));
+                for _ in 0..3 {
+                    scope.spawn(|| {
+                        thread::sleep(Duration::from_millis(20));
+                        finished.fetch_add(1, Ordering::SeqCst);
+                    });
+                }
+            })
+        }))
+        .unwrap_err();
+
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"task failed"));
+        assert_eq!(finished.load(Ordering::SeqCst), 3);
+    }
+}
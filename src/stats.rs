@@ -0,0 +1,167 @@
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入 Duration（时间段）
+// Import Duration (time duration)
+use std::time::Duration;
+
+// 导入延迟分布摘要
+// Import latency distribution summary
+use crate::histogram::LatencySummary;
+
+// 线程池状态快照，各计数分别原子读取，彼此之间不保证一致
+// Snapshot of the pool state, every counter is read atomically on its own without cross-counter consistency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolStats {
+    // 当前线程数
+    // Current thread count
+    pub threads: usize,
+
+    // 空闲线程数
+    // Idle thread count
+    pub idle_threads: usize,
+
+    // 线程池生命周期内的最大线程数
+    // Highest thread count over the pool's lifetime
+    pub peak_threads: usize,
+
+    // 当前排队的任务数（包括各本地队列中的任务）
+    // Number of queued tasks (including tasks in the local deques)
+    pub queued_tasks: usize,
+
+    // 正在执行的任务数
+    // Number of tasks currently executing
+    pub active_tasks: usize,
+
+    // 已提交的任务数
+    // Submitted task count
+    pub submitted_tasks: usize,
+
+    // 已完成的任务数
+    // Completed task count
+    pub completed_tasks: usize,
+
+    // 发生 panic 的任务数
+    // Panicked task count
+    pub panicked_tasks: usize,
+
+    // 被拒绝的任务数
+    // Rejected task count
+    pub rejected_tasks: usize,
+
+    // 被取消的任务数
+    // Cancelled task count
+    pub cancelled_tasks: usize,
+
+    // 因过期而被丢弃的任务数
+    // Count of tasks discarded because they expired
+    pub expired_tasks: usize,
+
+    // 线程池生命周期内创建的线程数
+    // Threads spawned over the pool's lifetime
+    pub spawned_threads: usize,
+
+    // 线程池生命周期内退出的线程数（空闲回收或关闭）
+    // Threads retired over the pool's lifetime (reclaimed when idle or exited on shutdown)
+    pub retired_threads: usize,
+
+    // 线程池创建以来经过的时间
+    // Time elapsed since the pool was created
+    pub uptime: Duration,
+
+    // 滑动窗口内的排队等待时间分布（入队到出队）
+    // Queue wait distribution within the sliding window (enqueue to dequeue)
+    pub queue_wait: LatencySummary,
+
+    // 滑动窗口内的执行时间分布（开始到结束）
+    // Run time distribution within the sliding window (start to finish)
+    pub run_time: LatencySummary,
+}
+
+impl fmt::Display for PoolStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 在一行内输出全部计数，便于监控程序周期性打印
+        // Output every counter on one line, convenient for monitors printing periodically
+        write!(
+            f,
+            "threads: {} (idle {}, peak {}, spawned {}, retired {}) | queued: {} | tasks: active {}, submitted {}, completed {}, panicked {}, rejected {}, cancelled {}, expired {} | uptime: {:.1}s | queue wait: {} | run time: {}",
+            self.threads,
+            self.idle_threads,
+            self.peak_threads,
+            self.spawned_threads,
+            self.retired_threads,
+            self.queued_tasks,
+            self.active_tasks,
+            self.submitted_tasks,
+            self.completed_tasks,
+            self.panicked_tasks,
+            self.rejected_tasks,
+            self.cancelled_tasks,
+            self.expired_tasks,
+            self.uptime.as_secs_f64(),
+            self.queue_wait,
+            self.run_time,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::thread_pool::ThreadPool;
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn snapshot_tracks_thread_lifetimes_and_displays_them() {
+        let pool = ThreadPool::builder()
+            .core_threads(0)
+            .max_threads(3)
+            .keep_alive(Duration::from_millis(50))
+            .build();
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Arc::new(Mutex::new(release_rx));
+
+        // 三个任务同时阻塞，迫使线程池创建三个线程
+        // Three tasks block at the same time, forcing the pool to spawn three threads
+        for _ in 0..3 {
+            let started = started_tx.clone();
+            let release = Arc::clone(&release_rx);
+            pool.submit(move || {
+                started.send(()).expect("Failed to signal start");
+                let _ = release.lock().expect("Failed to lock release receiver").recv();
+            })
+            .expect("Failed to submit task");
+        }
+        for _ in 0..3 {
+            started_rx.recv_timeout(Duration::from_secs(10)).expect("Task did not start");
+        }
+
+        let stats = pool.stats();
+        assert_eq!((stats.threads, stats.peak_threads, stats.spawned_threads, stats.retired_threads), (3, 3, 3, 0));
+        assert_eq!(stats.active_tasks, 3);
+
+        drop(release_tx);
+        pool.wait_for_completion();
+
+        // 非核心线程空闲超过存活时间后退出
+        // Non-core threads exit once idle for longer than the keep-alive time
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let stats = loop {
+            let stats = pool.stats();
+            if stats.retired_threads == 3 && stats.threads == 0 {
+                break stats;
+            }
+            assert!(Instant::now() < deadline, "Idle threads were not retired");
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!((stats.threads, stats.peak_threads, stats.spawned_threads, stats.retired_threads), (0, 3, 3, 3));
+        assert_eq!((stats.submitted_tasks, stats.completed_tasks, stats.active_tasks), (3, 3, 0));
+
+        let line = stats.to_string();
+        assert!(line.starts_with("threads: 0 (idle 0, peak 3, spawned 3, retired 3) | queued: 0 |"), "{}", line);
+        assert!(line.contains("tasks: active 0, submitted 3, completed 3, panicked 0, rejected 0, cancelled 0, expired 0"), "{}", line);
+    }
+}
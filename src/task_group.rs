@@ -0,0 +1,331 @@
+// 导入 VecDeque（双端队列，按完成顺序保存结果）
+// Import VecDeque (double-ended queue, holds results in completion order)
+use std::collections::VecDeque;
+
+// 导入 panic 模块和 AssertUnwindSafe（用于捕获任务 panic）
+// Import panic module and AssertUnwindSafe (used to catch task panics)
+use std::panic::{self, AssertUnwindSafe};
+
+// 导入 Arc（原子引用计数）、Mutex（互斥锁）和 Condvar（条件变量）
+// Import Arc (atomic reference counting), Mutex (mutual exclusion lock), and Condvar (condition variable)
+use std::sync::{Arc, Condvar, Mutex};
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 导入取消令牌
+// Import cancellation token
+use crate::cancel::CancellationToken;
+
+// 导入任务错误类型和 panic 信息提取函数
+// Import task error type and panic message helper
+use crate::task_handle::{self, TaskError};
+
+// 导入线程池
+// Import thread pool
+use crate::thread_pool::ThreadPool;
+
+// 任务组的计数和结果，使用互斥锁保护
+// Counters and results of the group, protected by the mutex
+struct GroupState<T> {
+    // 已提交但尚未结束的任务数
+    // Number of tasks submitted but not finished yet
+    running: usize,
+
+    // 已结束但尚未被 join_next 取走的结果，按完成顺序排列
+    // Results of finished tasks not yet taken by join_next, in completion order
+    finished: VecDeque<Result<T, TaskError>>,
+}
+
+// 任务组与其任务共享的状态
+// State shared between the group and its tasks
+struct Shared<T> {
+    // 计数和结果
+    // Counters and results
+    state: Mutex<GroupState<T>>,
+
+    // 任务结束时通知等待者的条件变量
+    // Condition variable notifying waiters when a task finishes
+    finished: Condvar,
+}
+
+// 任务端持有的结果写入器，被丢弃而未写入时自动报告取消
+// Result writer held by the task side, reports cancellation automatically if dropped without writing
+struct GroupCompleter<T> {
+    // 写入后置为 None，避免 Drop 时重复写入
+    // Set to None after writing, avoiding a second write on Drop
+    shared: Option<Arc<Shared<T>>>,
+}
+
+impl<T> GroupCompleter<T> {
+    // 写入任务结果并唤醒等待者
+    // Write the task result and wake the waiters
+    fn complete(mut self, result: Result<T, TaskError>) {
+        if let Some(shared) = self.shared.take() {
+            Self::store(&shared, result);
+        }
+    }
+
+    // 将结果追加到完成队列并减少运行中的任务数
+    // Append the result to the finished queue and decrement the running count
+    fn store(shared: &Shared<T>, result: Result<T, TaskError>) {
+        let mut state = shared.state.lock().expect("Failed to lock task group mutex");
+        state.running -= 1;
+        state.finished.push_back(result);
+        shared.finished.notify_all();
+    }
+}
+
+impl<T> Drop for GroupCompleter<T> {
+    fn drop(&mut self) {
+        // 任务未执行就被丢弃（被拒绝、被取消或线程池关闭），报告取消，避免等待者永远阻塞
+        // Task dropped without running (rejected, cancelled, or pool shut down), report cancellation so waiters never block forever
+        if let Some(shared) = self.shared.take() {
+            Self::store(&shared, Err(TaskError::Cancelled));
+        }
+    }
+}
+
+// 任务组：只跟踪通过自身提交的任务，多个调用方共享同一个线程池时互不等待对方的任务；
+// 任务组被丢弃时已提交的任务继续执行，其结果被丢弃
+// Task group: tracks only the tasks submitted through it, so callers sharing one pool never wait on each other's tasks;
+// dropping the group leaves its submitted tasks running and discards their results
+pub struct TaskGroup<'pool, T> {
+    // 执行任务的线程池
+    // Thread pool executing the tasks
+    pool: &'pool ThreadPool,
+
+    // 与任务共享的状态
+    // State shared with the tasks
+    shared: Arc<Shared<T>>,
+
+    // 当前批次任务共用的取消令牌，cancel_all 后替换为新令牌
+    // Cancellation token shared by the current batch of tasks, replaced with a fresh one after cancel_all
+    token: Mutex<CancellationToken>,
+}
+
+impl<'pool, T> TaskGroup<'pool, T>
+where
+    T: Send + 'static,
+{
+    // 在组内提交任务，结果通过 join_next 按完成顺序取得；任务被拒绝时得到 TaskError::Cancelled
+    // Submit a task within the group, its result is taken in completion order through join_next; a rejected task yields TaskError::Cancelled
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        self.spawn_cancellable(move |_| task());
+    }
+
+    // 在组内提交可取消的任务，运行中的任务可通过传入的令牌响应 cancel_all
+    // Submit a cancellable task within the group, a running task can respond to cancel_all through the passed token
+    pub fn spawn_cancellable<F>(&self, task: F)
+    where
+        F: FnOnce(&CancellationToken) -> T + Send + 'static,
+    {
+        self.shared.state.lock().expect("Failed to lock task group mutex").running += 1;
+        let completer = GroupCompleter { shared: Some(Arc::clone(&self.shared)) };
+        let token = self.token.lock().expect("Failed to lock task group token").clone();
+
+        // 任务被拒绝时闭包随之丢弃，结果为 TaskError::Cancelled，因此忽略提交错误
+        // A rejected task drops the closure and its result is TaskError::Cancelled, so the submit error is ignored
+        let _ = self.pool.submit_cancellable(&token, move |token| {
+            // 捕获任务 panic，将其作为错误报告给任务组
+            // Catch task panic and report it to the group as an error
+            match panic::catch_unwind(AssertUnwindSafe(|| task(token))) {
+                Ok(value) => completer.complete(Ok(value)),
+                Err(payload) => {
+                    completer.complete(Err(TaskError::Panicked(task_handle::panic_message(&*payload))));
+
+                    // 继续传播 panic，交由工作线程统计并调用 panic 处理器
+                    // Keep propagating the panic so the worker counts it and calls the panic handler
+                    panic::resume_unwind(payload);
+                }
+            }
+        });
+    }
+
+    // 阻塞等待下一个结束的任务并返回其结果（按完成顺序）；组内没有未取走的任务时返回 None
+    // Block until the next task finishes and return its result (in completion order); returns None when the group has no tasks left to take
+    pub fn join_next(&self) -> Option<Result<T, TaskError>> {
+        let mut state = self.shared.state.lock().expect("Failed to lock task group mutex");
+        loop {
+            if let Some(result) = state.finished.pop_front() {
+                return Some(result);
+            }
+            if state.running == 0 {
+                return None;
+            }
+            state = self.shared.finished.wait(state).expect("Condvar wait failed");
+        }
+    }
+
+    // 非阻塞地取出下一个已结束任务的结果，没有已结束的任务时返回 None
+    // Take the result of the next finished task without blocking, returns None when no task has finished
+    pub fn try_join_next(&self) -> Option<Result<T, TaskError>> {
+        self.shared.state.lock().expect("Failed to lock task group mutex").finished.pop_front()
+    }
+
+    // 阻塞等待组内已提交的任务全部结束，结果仍保留供 join_next 取走
+    // Block until every task submitted to the group has finished, the results stay available for join_next
+    pub fn wait(&self) {
+        let mut state = self.shared.state.lock().expect("Failed to lock task group mutex");
+        while state.running > 0 {
+            state = self.shared.finished.wait(state).expect("Condvar wait failed");
+        }
+    }
+
+    // 最多等待指定时间，返回组内任务是否已全部结束
+    // Wait for at most the given duration, returns whether all tasks of the group have finished
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        // 计算截止时间，避免虚假唤醒导致总等待时间变长
+        // Compute deadline so spurious wakeups do not extend the total waiting time
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.state.lock().expect("Failed to lock task group mutex");
+
+        while state.running > 0 {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            // 剩余时间耗尽，返回 false
+            // Remaining time exhausted, return false
+            if remaining.is_zero() {
+                return false;
+            }
+
+            state = self
+                .shared
+                .finished
+                .wait_timeout(state, remaining)
+                .expect("Condvar wait failed")
+                .0;
+        }
+
+        true
+    }
+
+    // 取消组内已提交的全部任务：尚未开始的任务被跳过并得到 TaskError::Cancelled，运行中的任务可通过令牌响应；
+    // 之后提交的任务使用新的令牌，不受本次取消影响
+    // Cancel every task submitted to the group: tasks not yet started are skipped and yield TaskError::Cancelled, running tasks can respond through the token;
+    // tasks submitted afterwards use a fresh token and are not affected by this cancellation
+    pub fn cancel_all(&self) {
+        let token = std::mem::take(&mut *self.token.lock().expect("Failed to lock task group token"));
+        token.cancel();
+    }
+
+    // 组内尚未结束的任务数
+    // Number of tasks in the group that have not finished yet
+    pub fn running(&self) -> usize {
+        self.shared.state.lock().expect("Failed to lock task group mutex").running
+    }
+}
+
+impl ThreadPool {
+    // 创建只跟踪自身任务的任务组
+    // Create a task group that tracks only its own tasks
+    pub fn task_group<T>(&self) -> TaskGroup<'_, T>
+    where
+        T: Send + 'static,
+    {
+        TaskGroup {
+            pool: self,
+            shared: Arc::new(Shared {
+                state: Mutex::new(GroupState { running: 0, finished: VecDeque::new() }),
+                finished: Condvar::new(),
+            }),
+            token: Mutex::new(CancellationToken::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::blocked_pool;
+    use std::sync::mpsc;
+
+    #[test]
+    fn join_next_returns_results_in_completion_order() {
+        let pool = ThreadPool::builder().max_threads(2).build();
+        let group = pool.task_group();
+        let (release, blocked) = mpsc::channel::<()>();
+
+        group.spawn(move || {
+            let _ = blocked.recv();
+            "slow"
+        });
+        group.spawn(|| "fast");
+
+        assert_eq!(group.join_next(), Some(Ok("fast")));
+        release.send(()).expect("Failed to release task");
+        assert_eq!(group.join_next(), Some(Ok("slow")));
+        assert_eq!(group.join_next(), None);
+        assert_eq!(group.running(), 0);
+    }
+
+    #[test]
+    fn cancel_all_skips_queued_tasks_and_later_spawns_use_a_fresh_token() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        let group = pool.task_group();
+
+        for value in 0..3 {
+            group.spawn(move || value);
+        }
+        group.cancel_all();
+        group.spawn(|| 3);
+        release.send(()).expect("Failed to release worker");
+
+        let mut results: Vec<_> = std::iter::from_fn(|| group.join_next()).collect();
+        results.sort_by_key(|result| result.as_ref().ok().copied());
+        assert_eq!(
+            results,
+            vec![Err(TaskError::Cancelled), Err(TaskError::Cancelled), Err(TaskError::Cancelled), Ok(3)]
+        );
+    }
+
+    #[test]
+    fn wait_timeout_returns_false_while_a_task_is_blocked() {
+        let pool = ThreadPool::builder().max_threads(2).build();
+        let group = pool.task_group();
+        let (release, blocked) = mpsc::channel::<()>();
+
+        group.spawn(move || {
+            let _ = blocked.recv();
+        });
+
+        assert!(!group.wait_timeout(Duration::from_millis(50)));
+        assert_eq!(group.running(), 1);
+
+        release.send(()).expect("Failed to release task");
+        assert!(group.wait_timeout(Duration::from_secs(10)));
+        assert_eq!(group.try_join_next(), Some(Ok(())));
+    }
+
+    #[test]
+    fn group_does_not_wait_for_tasks_outside_it() {
+        let pool = ThreadPool::builder().max_threads(2).build();
+        let (release, blocked) = mpsc::channel::<()>();
+        pool.submit(move || {
+            let _ = blocked.recv();
+        })
+        .expect("Failed to submit task");
+
+        let group = pool.task_group();
+        group.spawn(|| 1);
+        assert!(group.wait_timeout(Duration::from_secs(10)));
+        assert_eq!(group.join_next(), Some(Ok(1)));
+
+        release.send(()).expect("Failed to release task");
+    }
+
+    #[test]
+    fn panicking_task_yields_panicked() {
+        let pool = ThreadPool::builder().max_threads(1).panic_handler(|_, _| {}).build();
+        let group = pool.task_group::<()>();
+
+        group.spawn(|| panic!("boom"));
+
+        assert_eq!(group.join_next(), Some(Err(TaskError::Panicked("boom".to_string()))));
+        assert_eq!(group.join_next(), None);
+    }
+}
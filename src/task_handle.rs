@@ -0,0 +1,266 @@
+// 导入 Any（任意类型，用于 panic 载荷）
+// Import Any (arbitrary type, used for panic payloads)
+use std::any::Any;
+
+// 导入 fmt（格式化输出）
+// Import fmt (formatted output)
+use std::fmt;
+
+// 导入 Arc（原子引用计数）、Mutex（互斥锁）和 Condvar（条件变量）
+// Import Arc (atomic reference counting), Mutex (mutual exclusion lock), and Condvar (condition variable)
+use std::sync::{Arc, Condvar, Mutex};
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 任务执行失败的原因
+// Reason why a task failed to produce a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskError {
+    // 任务执行过程中发生 panic，携带 panic 信息
+    // Task panicked during execution, carries the panic message
+    Panicked(String),
+
+    // 任务在执行前被丢弃（例如线程池关闭），永远不会产生结果
+    // Task was dropped before running (e.g. pool shut down), it will never produce a value
+    Cancelled,
+
+    // 结果已经通过 try_join 或 join_timeout 取走，再次获取时返回
+    // Result was already taken through try_join or join_timeout, returned on any later attempt
+    AlreadyTaken,
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // 输出 panic 信息
+            // Output panic message
+            TaskError::Panicked(message) => write!(f, "task panicked: {}", message),
+
+            // 输出取消信息
+            // Output cancellation message
+            TaskError::Cancelled => write!(f, "task was cancelled before it ran"),
+
+            // 输出重复获取信息
+            // Output already-taken message
+            TaskError::AlreadyTaken => write!(f, "task result was already taken"),
+        }
+    }
+}
+
+impl std::error::Error for TaskError {}
+
+// 结果槽的状态
+// State of the result slot
+enum Slot<T> {
+    // 任务尚未完成
+    // Task has not finished yet
+    Pending,
+
+    // 任务已完成，结果等待取走
+    // Task has finished, result is waiting to be taken
+    Ready(Result<T, TaskError>),
+
+    // 结果已被句柄取走
+    // Result has already been taken by the handle
+    Taken,
+}
+
+// 任务端与句柄端共享的状态
+// State shared between the task side and the handle side
+struct Shared<T> {
+    // 结果槽，使用互斥锁保护
+    // Result slot, protected by a mutex
+    slot: Mutex<Slot<T>>,
+
+    // 结果就绪时通知等待者的条件变量
+    // Condition variable notifying waiters when the result is ready
+    ready: Condvar,
+}
+
+// 带返回值任务的句柄，可用于阻塞或超时等待结果
+// Handle of a task with a return value, can be used to wait for the result with blocking or timeout
+pub struct TaskHandle<T> {
+    // 与任务端共享的状态
+    // State shared with the task side
+    shared: Arc<Shared<T>>,
+}
+
+// 任务端持有的结果写入器，被丢弃而未写入时自动报告取消
+// Result writer held by the task side, reports cancellation automatically if dropped without writing
+pub(crate) struct Completer<T> {
+    // 写入后置为 None，避免 Drop 时重复写入
+    // Set to None after writing, avoiding a second write on Drop
+    shared: Option<Arc<Shared<T>>>,
+}
+
+// 创建一对相互关联的结果写入器和任务句柄
+// Create a linked pair of result writer and task handle
+pub(crate) fn channel<T>() -> (Completer<T>, TaskHandle<T>) {
+    let shared = Arc::new(Shared {
+        slot: Mutex::new(Slot::Pending),
+        ready: Condvar::new(),
+    });
+
+    (
+        Completer { shared: Some(Arc::clone(&shared)) },
+        TaskHandle { shared },
+    )
+}
+
+// 从 panic 载荷中提取可读的信息
+// Extract a readable message from a panic payload
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    // panic!("literal") 的载荷是 &str
+    // Payload of panic!("literal") is &str
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+
+    // panic!("{}", value) 的载荷是 String
+    // Payload of panic!("{}", value) is String
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+
+    // 其他类型的载荷无法直接输出
+    // Payloads of other types cannot be printed directly
+    String::from("Box<dyn Any>")
+}
+
+impl<T> Completer<T> {
+    // 写入任务结果并唤醒所有等待者
+    // Write the task result and wake all waiters
+    pub(crate) fn complete(mut self, result: Result<T, TaskError>) {
+        if let Some(shared) = self.shared.take() {
+            Self::store(&shared, result);
+        }
+    }
+
+    // 将结果存入结果槽
+    // Store the result into the result slot
+    fn store(shared: &Shared<T>, result: Result<T, TaskError>) {
+        // 获取结果槽的锁，使用expect提供更好的错误信息
+        // Get result slot lock, use expect for better error information
+        let mut slot = shared.slot.lock().expect("Failed to lock task result mutex");
+        *slot = Slot::Ready(result);
+
+        // 通知所有等待结果的线程
+        // Notify all threads waiting for the result
+        shared.ready.notify_all();
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        // 任务未执行就被丢弃，报告取消，避免句柄永远等待
+        // Task dropped without running, report cancellation so the handle never waits forever
+        if let Some(shared) = self.shared.take() {
+            Self::store(&shared, Err(TaskError::Cancelled));
+        }
+    }
+}
+
+impl<T> TaskHandle<T> {
+    // 阻塞等待任务完成并返回结果
+    // Block until the task finishes and return its result
+    pub fn join(self) -> Result<T, TaskError> {
+        let mut slot = self.shared.slot.lock().expect("Failed to lock task result mutex");
+
+        // 结果未就绪时在条件变量上等待
+        // Wait on the condition variable while the result is not ready
+        while let Slot::Pending = *slot {
+            slot = self.shared.ready.wait(slot).expect("Condvar wait failed");
+        }
+
+        Self::take(&mut slot).expect("Task result missing after wait")
+    }
+
+    // 非阻塞地尝试获取结果，任务未完成时返回 None，结果已被取走后返回 TaskError::AlreadyTaken
+    // Try to get the result without blocking, returns None if the task has not finished and TaskError::AlreadyTaken once the result was taken
+    pub fn try_join(&mut self) -> Option<Result<T, TaskError>> {
+        let mut slot = self.shared.slot.lock().expect("Failed to lock task result mutex");
+        Self::take(&mut slot)
+    }
+
+    // 最多等待指定时间，超时仍未完成时返回 None，结果已被取走后返回 TaskError::AlreadyTaken
+    // Wait for at most the given duration, returns None if still not finished after the timeout and TaskError::AlreadyTaken once the result was taken
+    pub fn join_timeout(&mut self, timeout: Duration) -> Option<Result<T, TaskError>> {
+        // 计算截止时间，避免虚假唤醒导致总等待时间变长
+        // Compute deadline so spurious wakeups do not extend the total waiting time
+        let deadline = Instant::now() + timeout;
+        let mut slot = self.shared.slot.lock().expect("Failed to lock task result mutex");
+
+        while let Slot::Pending = *slot {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            // 剩余时间耗尽，返回 None
+            // Remaining time exhausted, return None
+            if remaining.is_zero() {
+                return None;
+            }
+
+            slot = self
+                .shared
+                .ready
+                .wait_timeout(slot, remaining)
+                .expect("Condvar wait failed")
+                .0;
+        }
+
+        Self::take(&mut slot)
+    }
+
+    // 任务是否已经结束（成功、panic 或被取消）
+    // Whether the task has finished (succeeded, panicked, or was cancelled)
+    pub fn is_finished(&self) -> bool {
+        let slot = self.shared.slot.lock().expect("Failed to lock task result mutex");
+        !matches!(*slot, Slot::Pending)
+    }
+
+    // 从结果槽中取走结果，结果只能被取走一次，之后返回 TaskError::AlreadyTaken
+    // Take the result out of the slot, the result can only be taken once, later calls return TaskError::AlreadyTaken
+    fn take(slot: &mut Slot<T>) -> Option<Result<T, TaskError>> {
+        match std::mem::replace(slot, Slot::Taken) {
+            // 任务未完成，恢复原状态
+            // Task not finished, restore the original state
+            Slot::Pending => {
+                *slot = Slot::Pending;
+                None
+            }
+            Slot::Ready(result) => Some(result),
+
+            // 轮询方式使用时可能重复获取，返回错误而不是 panic
+            // Polling callers may ask again after taking the result, return an error instead of panicking
+            Slot::Taken => Some(Err(TaskError::AlreadyTaken)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_join_after_result_taken_returns_already_taken() {
+        let (completer, mut handle) = channel::<u32>();
+        assert_eq!(handle.try_join(), None);
+
+        completer.complete(Ok(7));
+        assert_eq!(handle.try_join(), Some(Ok(7)));
+        assert_eq!(handle.try_join(), Some(Err(TaskError::AlreadyTaken)));
+        assert_eq!(handle.join_timeout(Duration::from_millis(10)), Some(Err(TaskError::AlreadyTaken)));
+        assert!(handle.is_finished());
+        assert_eq!(handle.join(), Err(TaskError::AlreadyTaken));
+    }
+
+    #[test]
+    fn dropped_completer_reports_cancelled_once() {
+        let (completer, mut handle) = channel::<u32>();
+        drop(completer);
+
+        assert_eq!(handle.join_timeout(Duration::from_secs(1)), Some(Err(TaskError::Cancelled)));
+        assert_eq!(handle.try_join(), Some(Err(TaskError::AlreadyTaken)));
+    }
+}
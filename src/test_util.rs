@@ -0,0 +1,25 @@
+// 导入 mpsc 通道（阻塞和释放工作线程）
+// Import mpsc channels (block and release the worker)
+use std::sync::mpsc;
+
+// 导入线程池和构建器
+// Import thread pool and builder
+use crate::builder::ThreadPoolBuilder;
+use crate::thread_pool::ThreadPool;
+
+// 用给定的构建器创建单线程线程池，唯一的工作线程阻塞到返回的发送端被使用或丢弃；
+// 返回时阻塞任务已在运行，之后提交的任务都留在队列中
+// Build a single-thread pool from the given builder whose only worker is blocked until the returned sender is used or dropped;
+// the blocking task is already running on return, so every task submitted afterwards stays queued
+pub(crate) fn blocked_pool(builder: ThreadPoolBuilder) -> (ThreadPool, mpsc::Sender<()>) {
+    let pool = builder.core_threads(1).max_threads(1).build();
+    let (release, blocked) = mpsc::channel::<()>();
+    let (started, wait_started) = mpsc::channel();
+    pool.submit(move || {
+        started.send(()).unwrap();
+        let _ = blocked.recv();
+    })
+    .unwrap();
+    wait_started.recv().unwrap();
+    (pool, release)
+}
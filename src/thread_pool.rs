@@ -1,14 +1,10 @@
-// 导入 VecDeque（双端队列，标准库实现）
-// Import VecDeque (double-ended queue, standard library implementation)
-use std::collections::VecDeque; 
-
 // 导入 HashMap（哈希表）
 // Import HashMap (hash table)
 use std::collections::HashMap; 
 
 // 导入 Arc（原子引用计数）、Mutex（互斥锁）和 Condvar（条件变量）
 // Import Arc (atomic reference counting), Mutex (mutual exclusion lock), and Condvar (condition variable)
-use std::sync::{Arc, Mutex, Condvar}; 
+use std::sync::{Arc, Mutex, MutexGuard, Condvar, RwLock}; 
 
 // 导入线程模块和 JoinHandle（用于线程句柄）
 // Import thread module and JoinHandle (for thread handles)
@@ -18,9 +14,65 @@ use std::thread::{self, JoinHandle};
 // Import AtomicBool (atomic boolean), AtomicUsize (atomic unsigned integer), and Ordering (memory ordering operations)
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering}; 
 
-// 导入 Duration（时间段）
-// Import Duration (time duration)
-use std::time::Duration; 
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant}; 
+
+// 导入 Any（任意类型，用于 panic 载荷）
+// Import Any (arbitrary type, used for panic payloads)
+use std::any::Any;
+
+// 导入 panic 模块和 AssertUnwindSafe（用于捕获任务 panic）
+// Import panic module and AssertUnwindSafe (used to catch task panics)
+use std::panic::{self, AssertUnwindSafe};
+
+// 导入任务句柄相关类型
+// Import task handle related types
+use crate::task_handle::{self, TaskError, TaskHandle};
+
+// 导入线程池构建器
+// Import thread pool builder
+use crate::builder::ThreadPoolBuilder;
+
+// 导入拒绝策略相关类型
+// Import rejection policy related types
+use crate::rejection::{RejectedTask, RejectionPolicy, SubmitError};
+
+// 导入优先级任务队列
+// Import priority task queue
+use crate::queue::{Priority, TaskQueue};
+
+// 导入定时器和定时任务句柄
+// Import timer and scheduled task handle
+use crate::timer::{Periodic, PeriodicTask, Schedule, ScheduledTask, Timer};
+
+// 导入取消令牌
+// Import cancellation token
+use crate::cancel::CancellationToken;
+
+// 导入工作窃取调度相关类型
+// Import work-stealing scheduler related types
+use crate::work_stealing::{self, LocalQueue, SchedulerMode};
+
+// 导入无锁队列相关类型
+// Import lock-free queue related types
+use crate::lock_free::{LockFreeQueue, QueueKind};
+
+// 导入线程池状态快照
+// Import pool state snapshot
+use crate::stats::PoolStats;
+
+// 导入延迟直方图
+// Import latency histogram
+use crate::histogram::LatencyHistogram;
+
+// 导入线程池事件观察者
+// Import pool event observer
+use crate::observer::PoolObserver;
+
+// 导入运行中任务的快照、工作线程状态槽和排队任务名计数
+// Import running task snapshot, worker status slot and queued task name counts
+use crate::introspect::{QueuedNames, RunningTask, WorkerStatus};
 
 // Atomic 相关概念
 // Atomic concepts explanation
@@ -35,11 +87,104 @@ use std::time::Duration;
 
 // 定义任务类型为无返回值的闭包，必须是线程安全和可发送的
 // Define task type as a closure with no return value, must be thread-safe and sendable
-type Task = Box<dyn FnOnce() + Send + 'static>; 
+pub type Task = Box<dyn FnOnce() + Send + 'static>; 
+
+// 队列中的任务条目，携带任务编号等元数据
+// Task entry in the queue, carrying metadata such as the task id
+pub(crate) struct Job {
+    // 任务编号，按提交顺序递增
+    // Task id, increasing in submission order
+    pub(crate) id: usize,
+
+    // 任务优先级
+    // Task priority
+    pub(crate) priority: Priority,
+
+    // 取消令牌，出队时已取消的任务会被跳过
+    // Cancellation token, tasks already cancelled at dequeue are skipped
+    pub(crate) token: Option<CancellationToken>,
+
+    // 截止时间，出队时已过期的任务不再执行
+    // Deadline, tasks already expired at dequeue are not executed
+    pub(crate) deadline: Option<Instant>,
+
+    // 任务过期时执行的回调
+    // Callback executed when the task expires
+    pub(crate) on_expired: Option<Task>,
+
+    // 入队时间，出队时据此记录排队等待时间
+    // Enqueue time, the queue wait is recorded from it at dequeue
+    pub(crate) enqueued_at: Instant,
+
+    // 任务名，用于查看运行中和排队中的任务
+    // Task name, used when inspecting running and queued tasks
+    pub(crate) name: Option<Arc<str>>,
+
+    // 是否为 Future 被唤醒后的再次轮询，再次轮询沿用首次轮询的任务编号，不计入任务数
+    // Whether this is a re-poll of a woken future, re-polls keep the task id of the first poll and are not counted as tasks
+    pub(crate) repoll: bool,
+
+    // 待执行的任务
+    // Task to execute
+    pub(crate) task: Task,
+}
+
+// 传递给 panic 处理器的任务元数据
+// Task metadata passed to the panic handler
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    // 任务编号，按提交顺序递增
+    // Task id, increasing in submission order
+    pub task_id: usize,
+
+    // 提交时指定的任务优先级
+    // Task priority specified at submission
+    pub priority: Priority,
+
+    // 执行该任务的工作线程 ID，由拒绝策略在线程池外的调用者线程上执行时为 None
+    // ID of the worker thread that executed the task, None when a rejection policy ran it on a caller outside the pool
+    pub worker_id: Option<usize>,
+
+    // 提交时指定的任务名，未命名时为 None
+    // Task name given at submission, None for unnamed tasks
+    pub name: Option<Arc<str>>,
+}
+
+// 为一个任务预留的队列位置
+// Queue place reserved for one task
+enum Reserved<'a> {
+    // 当前工作线程的本地队列（无界）
+    // Local deque of the current worker (unbounded)
+    Local(Arc<LocalQueue>),
+
+    // 无锁队列中已预留的位置
+    // Place reserved in the lock-free queue
+    LockFree(&'a LockFreeQueue),
+
+    // 已持有锁且确认有空位的加锁队列
+    // Locked queue held under its lock and known to have space
+    Locked(MutexGuard<'a, TaskQueue>),
+}
+
+// 工作线程退出的原因
+// Reason a worker thread exited
+enum WorkerExit {
+    // 空闲超过存活时间后被回收
+    // Reclaimed after staying idle past the keep-alive time
+    Retired,
+
+    // 线程池关闭
+    // The pool was shut down
+    ShutDown,
+}
+
+// panic 处理器类型，接收 panic 载荷和任务元数据
+// Panic handler type, receives the panic payload and task metadata
+pub(crate) type PanicHandler = Arc<dyn Fn(&(dyn Any + Send), &TaskInfo) + Send + Sync + 'static>;
 
 // 获取CPU核心数的函数，使用标准库替代num_cpus
 // Function to get CPU core count, using standard library to replace num_cpus
-fn get_cpu_count() -> usize {
+pub(crate) fn get_cpu_count() -> usize {
     // 使用标准库的available_parallelism()获取可用并行度
     // Use standard library's available_parallelism() to get available parallelism
     // 如果获取失败，默认使用4个线程
@@ -54,45 +199,187 @@ fn get_cpu_count() -> usize {
 // 线程池结构体定义
 // Thread pool struct definition
 pub struct ThreadPool {
-    // 任务队列，使用 Arc 包装的 Mutex 和 Condvar，改用VecDeque提高性能
-    // Task queue, using Arc-wrapped Mutex and Condvar, changed to VecDeque for better performance
-    tasks: Arc<(Mutex<VecDeque<Task>>, Condvar)>, 
-    
-    // 线程集合，使用 Arc 包装的 Mutex 和 HashMap
-    // Thread collection, using Arc-wrapped Mutex and HashMap
-    threads: Arc<Mutex<HashMap<usize, JoinHandle<()>>>>, 
-    
+    // 线程池共享状态，由线程池和所有工作线程通过 Arc 共同持有
+    // Shared pool state, held through Arc by the pool and every worker thread
+    pub(crate) inner: Arc<Inner>,
+}
+
+// 线程池内部共享状态
+// Internal shared state of the thread pool
+pub(crate) struct Inner {
+    // 任务队列，使用 Mutex 和 Condvar，按优先级分级，每级内部使用VecDeque
+    // Task queue, using Mutex and Condvar, split by priority with a VecDeque per level
+    // 使用无锁队列时该队列始终为空，锁只用于关闭和终止的同步
+    // With the lock-free queue this one stays empty and the lock only synchronizes shutdown and termination
+    tasks: (Mutex<TaskQueue>, Condvar),
+
+    // 无锁全局队列，为 None 时使用上面的加锁队列
+    // Lock-free global queue, the locked queue above is used when None
+    lock_free: Option<LockFreeQueue>,
+
+    // "队列有空位" 条件变量，与任务队列的锁配合使用，唤醒等待空位的提交者
+    // "Space available" condition variable, used with the task queue lock to wake submitters waiting for space
+    space_available: Condvar,
+
+    // "线程池已终止" 条件变量，与任务队列的锁配合使用，唤醒 await_termination 的调用者
+    // "Pool terminated" condition variable, used with the task queue lock to wake await_termination callers
+    terminated: Condvar,
+
+    // "任务已结束" 通知：独立的锁和条件变量，唤醒 wait_for_completion / wait_idle 的调用者
+    // "Task finished" notification: a dedicated lock and condition variable waking wait_for_completion / wait_idle callers
+    completion: (Mutex<()>, Condvar),
+
+    // 正在等待任务结束的调用者数量，为0时任务结束无需加锁通知
+    // Number of callers waiting for task completion, finishing tasks skip the lock and notification when it is 0
+    completion_waiters: AtomicUsize,
+
+    // 任务队列容量，为 None 时队列无界
+    // Task queue capacity, the queue is unbounded when None
+    queue_capacity: Option<usize>,
+
+    // 任务在队列中的最长存活时间，入队时为没有截止时间的任务设置截止时间
+    // Maximum time a task may live in the queue, sets a deadline at enqueue for tasks without one
+    queue_ttl: Option<Duration>,
+
+    // 队列已满时使用的拒绝策略，为 None 时 submit 阻塞等待空位
+    // Rejection policy used when the queue is full, submit blocks for space when None
+    rejection_policy: Option<Arc<dyn RejectionPolicy>>,
+
+    // 调度模式：共享全局队列或工作窃取
+    // Scheduling mode: shared global queue or work stealing
+    scheduler: SchedulerMode,
+
+    // 工作窃取模式下所有工作线程的本地队列，窃取者从中随机选择目标；全局模式下为空
+    // Local deques of all workers in work-stealing mode, thieves pick their victims from here at random; empty in global mode
+    locals: RwLock<Vec<Arc<LocalQueue>>>,
+
+    // 所有工作线程的状态槽，用于查看各工作线程正在执行的任务
+    // Status slots of all workers, used to inspect what every worker is executing
+    workers: RwLock<Vec<Arc<WorkerStatus>>>,
+
+    // 排队中的命名任务按名称计数
+    // Queued named tasks counted per name
+    queued_names: QueuedNames,
+
+    // 所有本地队列中的任务总数，在本地队列的锁内更新，空闲线程据此判断是否有可窃取的任务
+    // Total task count across all local deques, updated under the deque locks, idle workers use it to tell whether there is anything to steal
+    local_jobs: AtomicUsize,
+
+    // 线程集合，使用 Mutex 包装的 HashMap
+    // Thread collection, using Mutex-wrapped HashMap
+    threads: Mutex<HashMap<usize, JoinHandle<()>>>,
+
+    // 核心线程数，这些线程空闲时不会被回收
+    // Core thread count, these threads are never reclaimed when idle
+    core_threads: usize,
+
     // 最大线程数
     // Maximum number of threads
-    max_threads: usize, 
-    
+    max_threads: usize,
+
+    // 空闲线程的存活时间，超时后回收非核心线程
+    // Keep-alive time of idle threads, non-core threads are reclaimed after it expires
+    keep_alive: Duration,
+
+    // 线程名前缀，为 None 时线程保持匿名
+    // Thread name prefix, threads stay anonymous when None
+    pub(crate) thread_name_prefix: Option<String>,
+
+    // 线程栈大小（字节），为 None 时使用系统默认值
+    // Thread stack size (bytes), system default is used when None
+    stack_size: Option<usize>,
+
     // 下一个线程 ID，原子无符号整数
     // Next thread ID, atomic unsigned integer
-    next_thread_id: AtomicUsize, 
-    
-    // 退出标志，原子布尔
-    // Exit flag, atomic boolean
-    quit: Arc<AtomicBool>, 
-    
+    next_thread_id: AtomicUsize,
+
+    // 退出标志（即关闭标志），原子布尔；设置后不再接收新任务，队列清空后线程退出
+    // Exit flag (i.e. shutdown flag), atomic boolean; once set no new tasks are accepted and threads exit after draining the queue
+    quit: AtomicBool,
+
     // 当前线程数，原子无符号整数
     // Current thread count, atomic unsigned integer
-    current_threads: Arc<AtomicUsize>, 
-    
+    current_threads: AtomicUsize,
+
     // 空闲线程数，原子无符号整数
     // Idle thread count, atomic unsigned integer
-    idle_threads: Arc<AtomicUsize>, 
-    
+    idle_threads: AtomicUsize,
+
+    // 生命周期内的最大线程数，原子无符号整数
+    // Highest thread count over the lifetime, atomic unsigned integer
+    peak_threads: AtomicUsize,
+
+    // 生命周期内成功创建的线程数，原子无符号整数
+    // Threads successfully spawned over the lifetime, atomic unsigned integer
+    spawned_threads: AtomicUsize,
+
+    // 生命周期内退出的线程数，原子无符号整数
+    // Threads exited over the lifetime, atomic unsigned integer
+    retired_threads: AtomicUsize,
+
+    // 线程池创建时间，用于计算运行时长
+    // Pool creation time, used to compute the uptime
+    started_at: Instant,
+
+    // 排队等待时间直方图（入队到出队）
+    // Queue wait histogram (enqueue to dequeue)
+    pub(crate) queue_wait: LatencyHistogram,
+
+    // 执行时间直方图（开始到结束）
+    // Run time histogram (start to finish)
+    pub(crate) run_time: LatencyHistogram,
+
     // 活跃任务数，原子无符号整数
     // Active task count, atomic unsigned integer
-    active_tasks: Arc<AtomicUsize>, 
-    
+    active_tasks: AtomicUsize,
+
     // 已提交任务数，原子无符号整数（重命名，更准确地表示含义）
     // Submitted task count, atomic unsigned integer (renamed for more accurate representation)
-    submitted_tasks: Arc<AtomicUsize>, 
-    
+    submitted_tasks: AtomicUsize,
+
     // 已完成任务数，改为原子类型避免锁竞争，提高性能
     // Completed task count, changed to atomic type to avoid lock contention and improve performance
-    completed_tasks: Arc<AtomicUsize>, 
+    completed_tasks: AtomicUsize,
+
+    // 发生 panic 的任务数，原子无符号整数
+    // Panicked task count, atomic unsigned integer
+    panicked_tasks: AtomicUsize,
+
+    // 被拒绝的任务数，原子无符号整数
+    // Rejected task count, atomic unsigned integer
+    rejected_tasks: AtomicUsize,
+
+    // 被取消的任务数（出队时已取消而被跳过，或执行结束时令牌已取消），原子无符号整数
+    // Cancelled task count (skipped because cancelled at dequeue, or token cancelled when the task returned), atomic unsigned integer
+    cancelled_tasks: AtomicUsize,
+
+    // 出队时已过期而被丢弃的任务数，原子无符号整数
+    // Count of tasks discarded because they had expired at dequeue, atomic unsigned integer
+    expired_tasks: AtomicUsize,
+
+    // 已结束的任务数（完成、panic、取消、过期或从队列中移除），用于等待所有任务完成
+    // Finished task count (completed, panicked, cancelled, expired, or removed from the queue), used to wait for all tasks
+    finished_tasks: AtomicUsize,
+
+    // 下一个任务编号，原子无符号整数
+    // Next task id, atomic unsigned integer
+    next_task_id: AtomicUsize,
+
+    // 可选的 panic 处理器，使用读写锁以便运行时替换
+    // Optional panic handler, behind a read-write lock so it can be replaced at runtime
+    panic_handler: RwLock<Option<PanicHandler>>,
+
+    // 事件观察者的不可变快照，注册时整体替换；产生事件时只克隆快照的引用计数，无需分配内存
+    // Immutable snapshot of the event observers, replaced as a whole on registration; events only clone the snapshot's reference count and never allocate
+    observers: RwLock<Arc<[Arc<dyn PoolObserver>]>>,
+
+    // 是否注册了观察者，没有观察者时产生事件无需加锁
+    // Whether any observer is registered, events skip the lock when there is none
+    has_observers: AtomicBool,
+
+    // 定时器，保存延迟任务直到到期
+    // Timer, holds delayed tasks until they become due
+    pub(crate) timer: Timer,
 }
 
 impl ThreadPool {
@@ -119,18 +406,62 @@ impl ThreadPool {
     // 使用指定最大线程数创建线程池
     // Create thread pool with specified maximum thread count
     pub fn with_max_threads(max_threads: usize) -> Self {
-        ThreadPool {
-            // 初始化任务队列，使用VecDeque替代自定义LinkedList
-            // Initialize task queue, using VecDeque instead of custom LinkedList
-            tasks: Arc::new((Mutex::new(VecDeque::new()), Condvar::new())), 
+        ThreadPoolBuilder::new().max_threads(max_threads).build()
+    }
+
+    // 返回线程池构建器，用于配置核心线程数、存活时间、线程名等
+    // Return a thread pool builder for configuring core threads, keep-alive, thread names, etc.
+    pub fn builder() -> ThreadPoolBuilder {
+        ThreadPoolBuilder::new()
+    }
+
+    // 根据构建器的配置创建线程池（由 ThreadPoolBuilder::build 调用）
+    // Create thread pool from builder configuration (called by ThreadPoolBuilder::build)
+    pub(crate) fn from_builder(builder: ThreadPoolBuilder) -> Self {
+        let inner = Arc::new(Inner {
+            // 初始化优先级任务队列，并设置老化间隔
+            // Initialize priority task queue and set aging interval
+            tasks: (Mutex::new(TaskQueue::new(builder.priority_aging)), Condvar::new()), 
+
+            // 按构建器的选择创建无锁队列
+            // Create the lock-free queue if the builder selected it
+            lock_free: (builder.queue_kind == QueueKind::LockFree).then(|| LockFreeQueue::new(builder.queue_capacity)),
+
+            // 初始化 "队列有空位" 条件变量，并设置队列容量
+            // Initialize "space available" condition variable and set queue capacity
+            space_available: Condvar::new(),
+            terminated: Condvar::new(),
+
+            // 初始化 "任务已结束" 通知
+            // Initialize "task finished" notification
+            completion: (Mutex::new(()), Condvar::new()),
+            completion_waiters: AtomicUsize::new(0),
+            queue_capacity: builder.queue_capacity,
+            queue_ttl: builder.queue_ttl,
+            rejection_policy: builder.rejection_policy,
+
+            // 设置调度模式，本地队列由工作线程启动时登记
+            // Set scheduling mode, local deques are registered by workers as they start
+            scheduler: builder.scheduler,
+            locals: RwLock::new(Vec::new()),
+            workers: RwLock::new(Vec::new()),
+            queued_names: QueuedNames::new(),
+            local_jobs: AtomicUsize::new(0),
             
             // 初始化线程集合
             // Initialize thread collection
-            threads: Arc::new(Mutex::new(HashMap::new())), 
+            threads: Mutex::new(HashMap::new()), 
             
-            // 设置最大线程数
-            // Set maximum thread count
-            max_threads, 
+            // 设置核心线程数和最大线程数
+            // Set core thread count and maximum thread count
+            core_threads: builder.core_threads, 
+            max_threads: builder.max_threads.unwrap_or_else(get_cpu_count), 
+
+            // 设置空闲存活时间、线程名前缀和栈大小
+            // Set idle keep-alive time, thread name prefix and stack size
+            keep_alive: builder.keep_alive, 
+            thread_name_prefix: builder.thread_name_prefix, 
+            stack_size: builder.stack_size, 
             
             // 初始化下一个线程 ID
             // Initialize next thread ID
@@ -138,365 +469,2074 @@ impl ThreadPool {
             
             // 初始化退出标志
             // Initialize exit flag
-            quit: Arc::new(AtomicBool::new(false)), 
+            quit: AtomicBool::new(false), 
             
             // 初始化当前线程数
             // Initialize current thread count
-            current_threads: Arc::new(AtomicUsize::new(0)), 
+            current_threads: AtomicUsize::new(0), 
             
             // 初始化空闲线程数
             // Initialize idle thread count
-            idle_threads: Arc::new(AtomicUsize::new(0)), 
+            idle_threads: AtomicUsize::new(0), 
+
+            // 初始化线程生命周期计数和创建时间
+            // Initialize thread lifetime counters and creation time
+            peak_threads: AtomicUsize::new(0),
+            spawned_threads: AtomicUsize::new(0),
+            retired_threads: AtomicUsize::new(0),
+            started_at: Instant::now(),
+
+            // 按构建器设置的窗口创建延迟直方图
+            // Create the latency histograms with the window set in the builder
+            queue_wait: LatencyHistogram::new(builder.latency_window),
+            run_time: LatencyHistogram::new(builder.latency_window),
             
             // 初始化活跃任务数
             // Initialize active task count
-            active_tasks: Arc::new(AtomicUsize::new(0)), 
+            active_tasks: AtomicUsize::new(0), 
             
             // 初始化已提交任务数
             // Initialize submitted task count
-            submitted_tasks: Arc::new(AtomicUsize::new(0)), 
+            submitted_tasks: AtomicUsize::new(0), 
             
             // 初始化已完成任务数，改为原子类型
             // Initialize completed task count, changed to atomic type
-            completed_tasks: Arc::new(AtomicUsize::new(0)), 
-        }
-    }
+            completed_tasks: AtomicUsize::new(0), 
 
-    // 原子化的线程创建函数，解决竞态条件问题
-    // Atomic thread creation function to solve race condition problems
-    fn try_spawn_thread(&self) -> bool {
-        // 无限循环，直到成功创建线程或确定不需要创建
-        // Infinite loop until successfully creating a thread or determining no need to create one
-        loop { 
-            // 获取当前线程数
-            // Get current thread count
-            let current = self.current_threads.load(Ordering::SeqCst); 
-            
-            // 如果当前线程数已达到最大值
-            // If current thread count has reached maximum
-            if current >= self.max_threads { 
-                // 返回false，表示不需要创建新线程
-                // Return false, indicating no need to create new thread
-                return false; 
-            }
-            
-            // 使用原子的compare_exchange_weak操作，确保检查-创建的原子性
-            // Use atomic compare_exchange_weak operation to ensure atomicity of check-create operation
-            // compare_exchange_weak: 比较当前值与期望值，如果相等则更新为新值
-            // compare_exchange_weak: Compare current value with expected value, update to new value if equal
-            // 参数: 期望值、新值、成功时的内存序、失败时的内存序
-            // Parameters: expected value, new value, memory order on success, memory order on failure
-            match self.current_threads.compare_exchange_weak(
-                current, // 期望的当前值
-                         // Expected current value
-                current + 1, // 要设置的新值（当前值+1）
-                             // New value to set (current value + 1)
-                Ordering::SeqCst, // 成功时的内存排序（顺序一致性）
-                                  // Memory ordering on success (sequential consistency)
-                Ordering::Relaxed, // 失败时的内存排序（宽松排序）
-                                   // Memory ordering on failure (relaxed ordering)
-            ) {
-                // 如果CAS操作成功
-                // If CAS operation succeeds
-                Ok(_) => { 
-                    // 创建新线程
-                    // Create new thread
-                    self.spawn_thread(); 
-                    
-                    // 返回true，表示成功创建线程
-                    // Return true, indicating successful thread creation
-                    return true; 
-                }
-                // 如果CAS操作失败，重试循环
-                // If CAS operation fails, retry the loop
-                Err(_) => continue, 
+            // 初始化发生 panic 的任务数
+            // Initialize panicked task count
+            panicked_tasks: AtomicUsize::new(0),
+
+            // 初始化被拒绝的任务数和已结束的任务数
+            // Initialize rejected task count and finished task count
+            rejected_tasks: AtomicUsize::new(0),
+            cancelled_tasks: AtomicUsize::new(0),
+            expired_tasks: AtomicUsize::new(0),
+            finished_tasks: AtomicUsize::new(0),
+
+            // 初始化下一个任务编号
+            // Initialize next task id
+            next_task_id: AtomicUsize::new(0),
+
+            // 使用构建器中设置的 panic 处理器
+            // Use the panic handler set in the builder
+            panic_handler: RwLock::new(builder.panic_handler),
+
+            // 使用构建器中注册的观察者
+            // Use the observers registered in the builder
+            has_observers: AtomicBool::new(!builder.observers.is_empty()),
+            observers: RwLock::new(builder.observers.into()),
+
+            // 初始化定时器，首次提交延迟任务时才创建定时器线程
+            // Initialize timer, the timer thread is only created when the first delayed task is submitted
+            timer: Timer::new(),
+        });
+
+        // 如果要求预启动，立即创建所有核心线程
+        // If prestart is requested, create all core threads immediately
+        if builder.prestart {
+            for _ in 0..inner.core_threads {
+                inner.try_spawn_thread();
             }
         }
+
+        ThreadPool { inner }
     }
 
-    // 提交新任务到线程池
-    // Submit new task to thread pool
-    pub fn submit<F>(&self, task: F)
+    // 提交新任务到线程池，队列已满时交给拒绝策略处理，未配置拒绝策略时阻塞等待空位
+    // Submit new task to thread pool, a full queue hands the task to the rejection policy, or blocks for space when none is configured
+    pub fn submit<F>(&self, task: F) -> Result<(), SubmitError>
     where
         F: FnOnce() + Send + 'static, // 任务为无返回值闭包，线程安全且可发送
                                        // Task is a closure with no return value, thread-safe and sendable
     {
-        // 将任务封装为 Box
-        // Wrap task in Box
-        let task = Box::new(task); 
-        
-        // 减少锁持有时间，优化性能
-        // Reduce lock holding time to optimize performance
-        {
-            // 获取任务队列的锁，使用expect提供更好的错误信息
-            // Get task queue lock, use expect for better error information
-            let mut tasks = self.tasks.0.lock().expect("Failed to lock tasks mutex"); 
-            
-            // 将任务加入队列尾部
-            // Add task to the end of queue
-            tasks.push_back(task); 
-        } // 作用域结束，自动释放锁
-          // Scope ends, automatically release lock
-        
-        // 原子性地增加已提交任务数
-        // Atomically increment submitted task count
-        self.submitted_tasks.fetch_add(1, Ordering::SeqCst); 
-        
-        // 通知一个等待的线程
-        // Notify one waiting thread
-        self.tasks.1.notify_one(); 
-        // notify_one 方法用于通知一个等待在该条件变量上的线程，使其从等待状态唤醒
-        // notify_one method is used to notify one thread waiting on this condition variable, waking it from waiting state
+        // 将任务封装为 Box，并分配任务编号
+        // Wrap task in Box and assign a task id
+        self.submit_job(self.inner.new_job(Box::new(task)))
+    }
 
-        // 只在需要时尝试创建线程，避免不必要的检查
-        // Only try to create thread when needed, avoid unnecessary checks
-        // 使用Relaxed排序提高性能，因为这里只是一个提示性检查
-        // Use Relaxed ordering for better performance, as this is just a hint check
-        if self.idle_threads.load(Ordering::Relaxed) == 0 { // 如果没有空闲线程
-                                                             // If no idle threads
-            // 尝试创建新线程（原子化操作）
-            // Try to create new thread (atomic operation)
-            self.try_spawn_thread(); 
-        }
+    // 按指定优先级提交任务，高优先级任务先于低优先级任务执行，同一优先级内先进先出
+    // Submit a task with the given priority, higher priorities run first and FIFO order holds within a priority
+    pub fn submit_with_priority<F>(&self, priority: Priority, task: F) -> Result<(), SubmitError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut job = self.inner.new_job(Box::new(task));
+        job.priority = priority;
+        self.submit_job(job)
     }
 
-    // 创建新线程的具体实现
-    // Specific implementation of creating new thread
-    fn spawn_thread(&self) {
-        // 获取并原子性地增加下一个线程 ID
-        // Get and atomically increment next thread ID
-        let thread_id = self.next_thread_id.fetch_add(1, Ordering::SeqCst); 
-        
-        // 克隆任务队列的 Arc，用于在线程间共享
-        // Clone Arc of task queue for sharing between threads
-        let tasks = Arc::clone(&self.tasks); 
-        
-        // 克隆退出标志的 Arc
-        // Clone Arc of exit flag
-        let quit = Arc::clone(&self.quit); 
-        
-        // 克隆当前线程数的 Arc
-        // Clone Arc of current thread count
-        let current_threads = Arc::clone(&self.current_threads); 
-        
-        // 克隆空闲线程数的 Arc
-        // Clone Arc of idle thread count
-        let idle_threads = Arc::clone(&self.idle_threads); 
-        
-        // 克隆活跃任务数的 Arc
-        // Clone Arc of active task count
-        let active_tasks = Arc::clone(&self.active_tasks); 
-        
-        // 克隆已完成任务数的 Arc
-        // Clone Arc of completed task count
-        let completed_tasks = Arc::clone(&self.completed_tasks); 
-        
-        // 克隆线程集合的 Arc，用于线程自清理
-        // Clone Arc of thread collection for thread self-cleanup
-        let threads = Arc::clone(&self.threads); 
+    // 提交命名任务，名称会出现在 running_tasks 和 queued_tasks 中，便于排查线程池卡住时各线程在做什么
+    // Submit a named task, the name shows up in running_tasks and queued_tasks, helping to see what the workers are doing when the pool gets stuck
+    pub fn submit_named<N, F>(&self, name: N, task: F) -> Result<(), SubmitError>
+    where
+        N: Into<Arc<str>>,
+        F: FnOnce() + Send + 'static,
+    {
+        let mut job = self.inner.new_job(Box::new(task));
+        job.name = Some(name.into());
+        self.submit_job(job)
+    }
 
-        // 创建并启动新线程
-        // Create and start new thread
-        let handle = thread::spawn(move || { // move 关键字将所有克隆的Arc移动到线程闭包中
-                                             // move keyword moves all cloned Arcs into thread closure
-            // 线程的主循环
-            // Main loop of thread
-            loop { 
-                // 声明任务变量
-                // Declare task variable
-                let task: Task; 
-                {
-                    // 解引用Arc，获取任务队列的锁和条件变量
-                    // Dereference Arc to get lock and condition variable of task queue
-                    let (lock, cvar) = &*tasks; 
-                    
-                    // 加锁获取任务队列的可变引用
-                    // Lock to get mutable reference of task queue
-                    let mut task_queue = lock.lock().expect("Failed to lock tasks mutex"); 
-                    
-                    // 原子性地增加空闲线程数
-                    // Atomically increment idle thread count
-                    idle_threads.fetch_add(1, Ordering::SeqCst); 
-                    
-                    // 当任务队列为空且未设置退出标志时，线程等待
-                    // Wait when task queue is empty and exit flag is not set
-                    while task_queue.is_empty() && !quit.load(Ordering::SeqCst) {
-                        // wait_timeout: 在条件变量上等待，最多等待指定时间
-                        // wait_timeout: Wait on condition variable for at most specified time
-                        // 参数: MutexGuard、超时时长
-                        // Parameters: MutexGuard, timeout duration
-                        // 返回: (MutexGuard, WaitTimeoutResult)
-                        // Returns: (MutexGuard, WaitTimeoutResult)
-                        let result = cvar
-                            .wait_timeout(task_queue, Duration::from_secs(10)) // 等待条件变量，最多等待10秒
-                                                                               // Wait on condition variable for at most 10 seconds
-                            .expect("Condvar wait failed"); // 如果等待失败则panic，提供错误信息
-                                                             // If wait fails, panic with error message
-                        
-                        // 更新任务队列的MutexGuard
-                        // Update MutexGuard of task queue
-                        task_queue = result.0; 
-                        
-                        // 如果等待超时
-                        // If wait times out
-                        if result.1.timed_out() { 
-                            // 原子性地减少空闲线程数
-                            // Atomically decrement idle thread count
-                            idle_threads.fetch_sub(1, Ordering::SeqCst); 
-                            
-                            // 原子性地减少当前线程数
-                            // Atomically decrement current thread count
-                            current_threads.fetch_sub(1, Ordering::SeqCst); 
-                            
-                            // 清理线程句柄，避免资源泄漏
-                            // Clean up thread handle to avoid resource leak
-                            // try_lock: 尝试获取锁，如果失败不阻塞
-                            // try_lock: Try to acquire lock, don't block if failed
-                            if let Ok(mut threads) = threads.try_lock() {
-                                // 从HashMap中移除当前线程的句柄
-                                // Remove current thread's handle from HashMap
-                                threads.remove(&thread_id); 
-                            }
-                            // 退出线程
-                            // Exit thread
-                            return; 
-                        }
-                    }
-                    
-                    // 原子性地减少空闲线程数（线程即将执行任务）
-                    // Atomically decrement idle thread count (thread is about to execute task)
-                    idle_threads.fetch_sub(1, Ordering::SeqCst); 
-
-                    // 如果设置了退出标志且任务队列为空，则退出线程
-                    // Exit thread if exit flag is set and task queue is empty
-                    if quit.load(Ordering::SeqCst) && task_queue.is_empty() {
-                        // 原子性地减少当前线程数
-                        // Atomically decrement current thread count
-                        current_threads.fetch_sub(1, Ordering::SeqCst); 
-                        
-                        // 清理线程句柄，避免资源泄漏
-                        // Clean up thread handle to avoid resource leak
-                        if let Ok(mut threads) = threads.try_lock() {
-                            // 从HashMap中移除当前线程的句柄
-                            // Remove current thread's handle from HashMap
-                            threads.remove(&thread_id); 
-                        }
-                        // 退出线程
-                        // Exit thread
-                        return; 
-                    }
+    // 提交任务条目：队列已满时交给拒绝策略或阻塞等待，线程池关闭时返回错误
+    // Submit a task entry: a full queue goes to the rejection policy or blocks, shutdown returns an error
+    pub(crate) fn submit_job(&self, job: Job) -> Result<(), SubmitError> {
+        // 工作窃取模式下，工作线程内提交的任务放入其本地队列，不经过全局队列
+        // In work-stealing mode, tasks submitted from a worker go to its local deque instead of the global queue
+        if let Some(local) = self.inner.current_local() {
+            if self.is_shutdown() {
+                self.inner.reject(SubmitError::ShutDown);
+                return Err(SubmitError::ShutDown);
+            }
+            self.inner.push_local(&local, job);
+            return Ok(());
+        }
 
-                    // 从队列前端获取任务
-                    // Get task from front of queue
-                    task = task_queue.pop_front().expect("Task queue empty when expected task");
-                } // 锁的作用域结束，自动释放锁
-                  // Lock scope ends, automatically release lock
-                
-                // 原子性地增加活跃任务数
-                // Atomically increment active task count
-                active_tasks.fetch_add(1, Ordering::SeqCst); 
-                
-                // 执行任务
-                // Execute task
-                task(); 
-                
-                // 原子性地减少活跃任务数
-                // Atomically decrement active task count
-                active_tasks.fetch_sub(1, Ordering::SeqCst); 
-                
-                // 原子性地增加已完成任务数
-                // Atomically increment completed task count
-                completed_tasks.fetch_add(1, Ordering::SeqCst); 
+        // 无锁队列：先不等待地预留位置，队列已满时交给拒绝策略，没有策略时才等待空位；
+        // 先检查是否已满再预留会与其他提交者竞争，配置了策略的提交也可能阻塞
+        // Lock-free queue: first reserve a place without waiting, a full queue goes to the rejection policy and only waits for space without one;
+        // checking fullness before reserving would race with other submitters and could block a submit that has a policy
+        if let Some(queue) = &self.inner.lock_free {
+            if self.inner.reserve_lock_free(queue, Some(Instant::now())) {
+                self.inner.enqueue_lock_free(queue, job);
+                return Ok(());
+            }
+            if !self.is_shutdown() {
+                if let Some(policy) = &self.inner.rejection_policy {
+                    return policy.reject(RejectedTask::new(job, &self.inner), self);
+                }
             }
-        });
 
-        // 将新线程的句柄插入线程集合
-        // Insert new thread's handle into thread collection
-        self.threads
-            .lock()
-            .expect("Failed to lock threads mutex") // 获取线程集合的锁
-                                                     // Get lock of thread collection
-            .insert(thread_id, handle); // 插入线程ID和对应的JoinHandle
-                                        // Insert thread ID and corresponding JoinHandle
-    }
+            // 没有截止时间的预留只会因线程池关闭而失败
+            // A reservation without a deadline only fails because the pool was shut down
+            if !self.inner.reserve_lock_free(queue, None) {
+                self.inner.reject(SubmitError::ShutDown);
+                return Err(SubmitError::ShutDown);
+            }
+            self.inner.enqueue_lock_free(queue, job);
+            return Ok(());
+        }
 
-    // 返回当前线程数
-    // Return current thread count
-    pub fn threads_num(&self) -> usize {
-        // 原子性地加载当前线程数
-        // Atomically load current thread count
-        self.current_threads.load(Ordering::SeqCst) 
-    }
+        // 获取任务队列的锁，使用expect提供更好的错误信息
+        // Get task queue lock, use expect for better error information
+        let queue = self.inner.tasks.0.lock().expect("Failed to lock tasks mutex"); 
 
-    // 等待所有任务完成（修复了逻辑缺陷）
-    // Wait for all tasks to complete (fixed logic flaws)
-    pub fn wait_for_completion(&self) {
-        // 快照机制：获取当前已提交的任务数作为目标，避免不一致性问题
-        // Snapshot mechanism: Get current submitted task count as target to avoid inconsistency issues
-        let target = self.submitted_tasks.load(Ordering::SeqCst); 
-        
-        // 使用自旋等待，避免条件变量的复杂性，适合短期使用场景
-        // Use spin waiting to avoid complexity of condition variables, suitable for short-term usage scenarios
-        while self.completed_tasks.load(Ordering::SeqCst) < target { // 当已完成任务数小于目标数时循环
-                                                                       // Loop while completed task count is less than target
-            // 主动让出线程执行权，给其他线程运行机会
-            // Voluntarily yield thread execution, giving other threads a chance to run
-            std::thread::yield_now(); 
-            // yield_now 方法用于主动让出当前线程的执行权，使其他线程有机会运行
-            // yield_now method is used to voluntarily yield current thread's execution, allowing other threads to run
+        // 队列已满且配置了拒绝策略，释放锁后交给策略处理
+        // Queue is full and a rejection policy is configured, release the lock and hand over to the policy
+        if self.inner.is_full(&queue) && !self.is_shutdown() {
+            if let Some(policy) = &self.inner.rejection_policy {
+                drop(queue);
+                return policy.reject(RejectedTask::new(job, &self.inner), self);
+            }
+        }
+
+        // 没有截止时间的等待不会超时
+        // Waiting without a deadline never times out
+        let queue = self.inner.wait_for_space(queue, None).expect("Unbounded wait for space timed out");
+
+        // 线程池已关闭，拒绝新任务（在锁内检查，保证已入队的任务一定会被执行）
+        // Pool has been shut down, reject the new task (checked under the lock so queued tasks are always executed)
+        if self.is_shutdown() {
+            drop(queue);
+            self.inner.reject(SubmitError::ShutDown);
+            return Err(SubmitError::ShutDown);
         }
+
+        // 放入队列并按需创建线程
+        // Push into the queue and create a thread if needed
+        self.inner.enqueue(queue, job);
+        Ok(())
     }
 
-    // 获取最大线程数（新增方法，用于外部查询）
-    // Get maximum thread count (new method for external queries)
-    pub fn get_max_threads(&self) -> usize {
-        // 返回最大线程数
-        // Return maximum thread count
-        self.max_threads 
+    // 提交可取消的任务：令牌在任务开始前被取消时任务会被跳过，运行中的任务可通过传入的令牌轮询取消状态或注册回调
+    // Submit a cancellable task: it is skipped if the token is cancelled before it starts, a running task can poll the passed token or register callbacks
+    pub fn submit_cancellable<F>(&self, token: &CancellationToken, task: F) -> Result<(), SubmitError>
+    where
+        F: FnOnce(&CancellationToken) + Send + 'static,
+    {
+        let task_token = token.clone();
+        let mut job = self.inner.new_job(Box::new(move || task(&task_token)));
+        job.token = Some(token.clone());
+        self.submit_job(job)
     }
-}
 
-// 线程池的析构函数，确保在销毁前完成所有任务
-// Destructor for thread pool, ensures all tasks are completed before destruction
-impl Drop for ThreadPool {
-    fn drop(&mut self) {
-        // 原子性地设置退出标志为true
-        // Atomically set exit flag to true
-        self.quit.store(true, Ordering::SeqCst); 
-        
-        // 通知所有等待在条件变量上的线程
-        // Notify all threads waiting on condition variable
-        self.tasks.1.notify_all(); 
-        // notify_all 方法用于通知所有等待在该条件变量上的线程，使它们从等待状态唤醒
-        // notify_all method is used to notify all threads waiting on this condition variable, waking them from waiting state
+    // 提交带截止时间的任务：出队时已超过截止时间的任务会被丢弃而不执行
+    // Submit a task with a deadline: a task whose deadline has passed at dequeue is discarded instead of executed
+    pub fn submit_with_deadline<F>(&self, deadline: Instant, task: F) -> Result<(), SubmitError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut job = self.inner.new_job(Box::new(task));
+        job.deadline = Some(deadline);
+        self.submit_job(job)
+    }
 
-        // 获取线程集合的锁
-        // Get lock of thread collection
-        let mut threads = self.threads.lock().expect("Failed to lock threads mutex"); 
-        
-        // drain(): 移除HashMap中的所有元素并返回迭代器
-        // drain(): Remove all elements from HashMap and return iterator
-        // map(): 将每个(key, value)对映射为value（即JoinHandle）
-        // map(): Map each (key, value) pair to value (i.e., JoinHandle)
-        for handle in threads.drain().map(|(_, handle)| handle) { // 获取所有线程句柄
-                                                                   // Get all thread handles
-            // 等待线程结束，如果线程panic则传播panic
-            // Wait for thread to finish, propagate panic if thread panics
-            handle.join().expect("Thread join failed"); 
-            // join 方法用于阻塞当前线程，直到被调用的线程执行结束
-            // join method is used to block current thread until the called thread finishes execution
-        }
+    // 提交带截止时间的任务，任务过期被丢弃时在工作线程上执行 on_expired 回调
+    // Submit a task with a deadline, the on_expired callback runs on the worker when the task expires and is discarded
+    pub fn submit_with_deadline_or_else<F, E>(&self, deadline: Instant, task: F, on_expired: E) -> Result<(), SubmitError>
+    where
+        F: FnOnce() + Send + 'static,
+        E: FnOnce() + Send + 'static,
+    {
+        let mut job = self.inner.new_job(Box::new(task));
+        job.deadline = Some(deadline);
+        job.on_expired = Some(Box::new(on_expired));
+        self.submit_job(job)
+    }
 
-        // 等待所有活跃任务完成
-        // Wait for all active tasks to complete
-        while self.active_tasks.load(Ordering::SeqCst) > 0 { // 当还有活跃任务时循环等待
-                                                              // Loop while there are active tasks
-            // 主动让出线程执行权以等待任务完成
-            // Voluntarily yield thread execution to wait for task completion
-            std::thread::yield_now(); 
-            // yield_now 方法用于主动让出当前线程的执行权，使其他线程有机会运行
-            // yield_now method is used to voluntarily yield current thread's execution, allowing other threads to run
+    // 尝试提交任务，队列已满或线程池已关闭时立即将任务原样返回，并计入被拒绝的任务数
+    // Try to submit a task, returns the task back immediately when the queue is full or the pool is shut down, counting it as rejected
+    pub fn try_submit<F>(&self, task: F) -> Result<(), F>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        match self.try_reserve() {
+            Some(reserved) => {
+                self.inner.enqueue_reserved(reserved, self.inner.new_job(Box::new(task)));
+                Ok(())
+            }
+            None => {
+                self.reject_returned();
+                Err(task)
+            }
         }
     }
-}
 
+    // 记录一个原样返回给提交方的任务被拒绝，区分线程池已关闭和队列已满
+    // Record the rejection of a task handed back to the submitter, telling a shut down pool from a full queue
+    fn reject_returned(&self) {
+        let error = if self.is_shutdown() { SubmitError::ShutDown } else { SubmitError::Rejected };
+        self.inner.reject(error);
+    }
+
+    // 不等待地尝试提交任务条目，队列已满或线程池已关闭时原样返回；用于重新提交被拒绝的任务
+    // Try to submit a task entry without waiting, returned as is when the queue is full or the pool is shut down; used to resubmit rejected tasks
+    pub(crate) fn try_submit_job(&self, job: Job) -> Result<(), Job> {
+        match self.try_reserve() {
+            Some(reserved) => {
+                self.inner.enqueue_reserved(reserved, job);
+                Ok(())
+            }
+            None => Err(job),
+        }
+    }
+
+    // 不等待地为一个任务预留队列位置，队列已满或线程池已关闭时返回 None；不记录拒绝，由调用方决定是否计数
+    // Reserve a queue place for one task without waiting, None when the queue is full or the pool is shut down; records no rejection, the caller decides whether to count one
+    fn try_reserve(&self) -> Option<Reserved<'_>> {
+        // 本地队列无界，工作线程内提交时只在关闭后失败
+        // Local deques are unbounded, so submitting from a worker only fails after shutdown
+        if let Some(local) = self.inner.current_local() {
+            if self.is_shutdown() {
+                return None;
+            }
+            return Some(Reserved::Local(local));
+        }
+
+        // 无锁队列：以当前时刻为截止时间预留位置，队列已满时不等待
+        // Lock-free queue: reserve a place with the current instant as deadline, so a full queue is not waited for
+        if let Some(queue) = &self.inner.lock_free {
+            if !self.inner.reserve_lock_free(queue, Some(Instant::now())) {
+                return None;
+            }
+            return Some(Reserved::LockFree(queue));
+        }
+
+        let queue = self.inner.tasks.0.lock().expect("Failed to lock tasks mutex");
+
+        // 线程池已关闭，不预留位置
+        // Pool has been shut down, reserve nothing
+        if self.is_shutdown() {
+            return None;
+        }
+
+        // 队列已满，不等待
+        // Queue is full, do not wait
+        if self.inner.is_full(&queue) {
+            return None;
+        }
+
+        Some(Reserved::Locked(queue))
+    }
+
+    // 提交任务，队列已满时最多等待指定时间，超时或线程池已关闭时将任务原样返回，并计入被拒绝的任务数
+    // Submit a task, waits at most the given duration when the queue is full, returns the task back on timeout or shutdown, counting it as rejected
+    pub fn submit_timeout<F>(&self, task: F, timeout: Duration) -> Result<(), F>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        // 本地队列无界，工作线程内提交时无需等待
+        // Local deques are unbounded, so submitting from a worker never waits
+        if let Some(local) = self.inner.current_local() {
+            if self.is_shutdown() {
+                self.inner.reject(SubmitError::ShutDown);
+                return Err(task);
+            }
+            self.inner.push_local(&local, self.inner.new_job(Box::new(task)));
+            return Ok(());
+        }
+
+        // 无锁队列：预留位置直到截止时间，超时或线程池已关闭时返回任务
+        // Lock-free queue: reserve a place until the deadline, returning the task on timeout or shutdown
+        if let Some(queue) = &self.inner.lock_free {
+            if !self.inner.reserve_lock_free(queue, Some(Instant::now() + timeout)) {
+                self.reject_returned();
+                return Err(task);
+            }
+            self.inner.enqueue_lock_free(queue, self.inner.new_job(Box::new(task)));
+            return Ok(());
+        }
+
+        let queue = self.inner.tasks.0.lock().expect("Failed to lock tasks mutex");
+
+        // 等待空位直到截止时间
+        // Wait for space until the deadline
+        match self.inner.wait_for_space(queue, Some(Instant::now() + timeout)) {
+            // 线程池已关闭，返回任务
+            // Pool has been shut down, return the task
+            Some(queue) if self.is_shutdown() => {
+                drop(queue);
+                self.inner.reject(SubmitError::ShutDown);
+                Err(task)
+            }
+            Some(queue) => {
+                self.inner.enqueue(queue, self.inner.new_job(Box::new(task)));
+                Ok(())
+            }
+            None => {
+                self.inner.reject(SubmitError::Rejected);
+                Err(task)
+            }
+        }
+    }
+
+    // 在指定延迟后执行任务，延迟期间不占用工作线程；返回的句柄可在到期前取消任务
+    // Run a task after the given delay without occupying a worker meanwhile; the returned handle can cancel it before it is due
+    pub fn submit_after<F>(&self, delay: Duration, task: F) -> Result<ScheduledTask, SubmitError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.submit_at(Instant::now() + delay, task)
+    }
+
+    // 在指定时间点执行任务，到期后才放入任务队列（不受队列容量限制）；线程池已关闭或无法创建定时器线程时返回错误
+    // Run a task at the given point in time, it enters the task queue only once due (ignoring queue capacity); returns an error once the pool is shut down or when the timer thread cannot be created
+    pub fn submit_at<F>(&self, deadline: Instant, task: F) -> Result<ScheduledTask, SubmitError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let job = self.inner.new_job(Box::new(task));
+        self.inner.timer.schedule(&self.inner, deadline, job).inspect_err(|error| self.inner.reject(*error))
+    }
+
+    // 按固定频率周期执行任务：首次在 initial_delay 后执行，之后每隔 period 执行一次；
+    // 某次执行超过周期时下一次立即开始，不会并发执行，也不会补执行错过的次数；某次执行 panic 后停止后续执行
+    // Run a task periodically at a fixed rate: first after initial_delay, then every period;
+    // a run longer than the period makes the next one start immediately, runs never overlap and missed runs are not replayed; a panicking run stops all further runs
+    pub fn schedule_at_fixed_rate<F>(&self, initial_delay: Duration, period: Duration, task: F) -> Result<PeriodicTask, SubmitError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        // 周期为0时任务会不停地重新调度
+        // With a zero period the task would reschedule itself endlessly
+        assert!(!period.is_zero(), "Period must be greater than 0");
+        self.schedule_periodic(initial_delay, Schedule::FixedRate(period), task)
+    }
+
+    // 按固定延迟周期执行任务：首次在 initial_delay 后执行，之后每次执行结束后等待 delay 再执行；某次执行 panic 后停止后续执行
+    // Run a task periodically with a fixed delay: first after initial_delay, then delay after each run finishes; a panicking run stops all further runs
+    pub fn schedule_with_fixed_delay<F>(&self, initial_delay: Duration, delay: Duration, task: F) -> Result<PeriodicTask, SubmitError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        // 延迟为0时任务会不停地重新调度
+        // With a zero delay the task would reschedule itself endlessly
+        assert!(!delay.is_zero(), "Delay must be greater than 0");
+        self.schedule_periodic(initial_delay, Schedule::FixedDelay(delay), task)
+    }
+
+    // 创建周期任务，线程池已关闭或无法创建定时器线程时返回错误
+    // Create a periodic task, returns an error once the pool is shut down or when the timer thread cannot be created
+    fn schedule_periodic<F>(&self, initial_delay: Duration, schedule: Schedule, task: F) -> Result<PeriodicTask, SubmitError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        Periodic::start(&self.inner, initial_delay, schedule, task).inspect_err(|error| self.inner.reject(*error))
+    }
+
+    // 提交带返回值的任务，返回可用于获取结果的任务句柄
+    // Submit a task with a return value, returns a task handle used to retrieve the result
+    pub fn submit_with_result<F, T>(&self, task: F) -> TaskHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static, // 任务为有返回值的闭包
+                                           // Task is a closure with a return value
+        T: Send + 'static,                 // 返回值需要能够跨线程发送
+                                           // Return value must be sendable across threads
+    {
+        // 创建结果写入器和任务句柄
+        // Create result writer and task handle
+        let (completer, handle) = task_handle::channel();
+
+        // 包装为普通任务，复用现有任务队列
+        // Wrap as a plain task, reusing the existing task queue
+        // 任务被拒绝时闭包随之丢弃，句柄会得到 TaskError::Cancelled，因此忽略提交错误
+        // A rejected task drops the closure and the handle receives TaskError::Cancelled, so the submit error is ignored
+        let _ = self.submit(move || {
+            // 捕获任务 panic，将其作为错误报告给句柄，而不是让等待者永远阻塞
+            // Catch task panic and report it to the handle as an error instead of blocking waiters forever
+            match panic::catch_unwind(AssertUnwindSafe(task)) {
+                // 写入结果并唤醒等待者
+                // Write result and wake waiters
+                Ok(value) => completer.complete(Ok(value)),
+                Err(payload) => {
+                    completer.complete(Err(TaskError::Panicked(task_handle::panic_message(&*payload))));
+
+                    // 继续传播 panic，交由工作线程统计并调用 panic 处理器
+                    // Keep propagating the panic so the worker counts it and calls the panic handler
+                    panic::resume_unwind(payload);
+                }
+            }
+        });
+
+        handle
+    }
+
+    // 关闭线程池：不再接收新任务，已入队的任务会继续执行完毕，尚未到期的延迟任务被丢弃
+    // Shut down the pool: no new tasks are accepted, already queued tasks still run to completion, delayed tasks not yet due are discarded
+    pub fn shutdown(&self) {
+        // 先停止定时器，使到期的延迟任务不再入队
+        // Stop the timer first so no more delayed tasks get queued
+        drop(self.inner.timer.stop());
+
+        // 在队列锁内设置退出标志，与提交路径中的检查互斥
+        // Set the exit flag under the queue lock, mutually exclusive with the check in the submit path
+        let queue = self.inner.tasks.0.lock().expect("Failed to lock tasks mutex");
+        self.inner.quit.store(true, Ordering::SeqCst);
+
+        // 队列中还有任务却没有线程时（例如线程创建失败），补充一个线程负责清空队列
+        // If tasks are queued but no thread exists (e.g. thread creation failed), add one thread to drain the queue
+        let needs_thread = !self.inner.injector_is_empty(&queue) && self.inner.current_threads.load(Ordering::SeqCst) == 0;
+        drop(queue);
+        if needs_thread {
+            self.inner.try_spawn_thread();
+        }
+
+        // 唤醒所有空闲线程以便退出，唤醒所有等待空位的提交者以便返回错误
+        // Wake all idle threads so they can exit, and all submitters waiting for space so they can return an error
+        self.inner.wake_all();
+    }
+
+    // 立即关闭线程池：不再接收新任务，并返回所有尚未开始执行的任务（包括未到期的延迟任务）；正在执行的任务不会被中断
+    // Shut down the pool immediately: no new tasks are accepted and all tasks that have not started (including delayed tasks not yet due) are returned; running tasks are not interrupted
+    pub fn shutdown_now(&self) -> Vec<Task> {
+        let delayed = self.inner.timer.stop();
+
+        let mut drained: Vec<Task> = {
+            let mut queue = self.inner.tasks.0.lock().expect("Failed to lock tasks mutex");
+            self.inner.quit.store(true, Ordering::SeqCst);
+            let mut jobs = queue.drain();
+
+            // 使用无锁队列时从中取出所有任务并释放其位置
+            // With the lock-free queue, take all tasks out of it and release their places
+            if let Some(lock_free) = &self.inner.lock_free {
+                let start = jobs.len();
+                jobs.extend(std::iter::from_fn(|| lock_free.pop()));
+                lock_free.release(jobs.len() - start);
+            }
+
+            // 工作窃取模式下还要取出各本地队列中的任务
+            // In work-stealing mode the local deques are drained as well
+            jobs.extend(self.inner.drain_locals());
+            jobs.into_iter().map(|job| self.inner.unqueue(job).task).collect()
+        };
+
+        // 被取出的任务不会再执行，视为已结束，避免 wait_for_completion 永远等待
+        // Drained tasks will never run, count them as finished so wait_for_completion does not wait forever
+        self.inner.finish_tasks(drained.len());
+
+        self.inner.wake_all();
+
+        // 延迟任务尚未计入已提交任务数，追加在队列任务之后返回
+        // Delayed tasks were never counted as submitted, return them after the queued tasks
+        drained.extend(delayed.into_iter().map(|job| job.task));
+        drained
+    }
+
+    // 等待线程池终止（关闭后所有线程退出），最多等待指定时间，返回是否已终止
+    // Wait for the pool to terminate (all threads exited after shutdown) for at most the given duration, returns whether it terminated
+    pub fn await_termination(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.inner.tasks.0.lock().expect("Failed to lock tasks mutex");
+
+        // 线程退出时在队列锁内减少线程数并通知，因此这里不会错过通知
+        // Threads decrement the count and notify under the queue lock on exit, so no notification is missed here
+        while !self.is_terminated() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return false;
+            }
+            queue = self
+                .inner
+                .terminated
+                .wait_timeout(queue, remaining)
+                .expect("Condvar wait failed")
+                .0;
+        }
+        true
+    }
+
+    // 线程池是否已关闭（不再接收新任务）
+    // Whether the pool has been shut down (no longer accepts new tasks)
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.quit.load(Ordering::SeqCst)
+    }
+
+    // 线程池是否已终止（已关闭且所有线程都已退出）
+    // Whether the pool has terminated (shut down and all threads have exited)
+    pub fn is_terminated(&self) -> bool {
+        self.is_shutdown() && self.inner.current_threads.load(Ordering::SeqCst) == 0
+    }
+
+    // 移除并返回最低优先级中最旧的任务，供拒绝策略腾出空位；被移除的任务计入被拒绝的任务数
+    // Remove and return the oldest task of the lowest priority, used by rejection policies to make room; the removed task counts as rejected
+    pub fn take_oldest(&self) -> Option<Task> {
+        let job = match &self.inner.lock_free {
+            Some(queue) => {
+                let job = queue.pop_oldest()?;
+                queue.release(1);
+                job
+            }
+            None => self.inner.tasks.0.lock().expect("Failed to lock tasks mutex").pop_oldest()?,
+        };
+        let job = self.inner.unqueue(job);
+
+        // 被移除的任务不会再执行，视为已结束，避免 wait_for_completion 永远等待，并计为被拒绝
+        // The removed task will never run, count it as finished so wait_for_completion does not wait forever, and as rejected
+        self.inner.finish_tasks(1);
+        self.inner.reject(SubmitError::Rejected);
+
+        // 队列腾出了一个空位，唤醒一个等待空位的提交者
+        // A slot was freed in the queue, wake one submitter waiting for space
+        self.inner.space_available.notify_one();
+        Some(job.task)
+    }
+
+    // 当前线程是本线程池的工作线程时，取出一个待执行的任务并就地执行，返回是否执行了任务；用于等待其他任务时帮助推进
+    // When the current thread is a worker of this pool, take one waiting task and run it in place, returns whether a task ran; used to make progress while waiting on other tasks
+    pub(crate) fn help_one(&self) -> bool {
+        let (status, local) = match work_stealing::current_worker(self.inner.pool_id()) {
+            Some(worker) => worker,
+            None => return false,
+        };
+        match self.inner.take_job(local.as_ref()) {
+            Some(job) => {
+                self.inner.run_job(job, &status);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // 设置 panic 处理器，任务 panic 时以 panic 载荷和任务元数据调用
+    // Set the panic handler, called with the panic payload and task metadata when a task panics
+    pub fn set_panic_handler<F>(&self, handler: F)
+    where
+        F: Fn(&(dyn Any + Send), &TaskInfo) + Send + Sync + 'static,
+    {
+        *self.inner.panic_handler.write().expect("Failed to lock panic handler") = Some(Arc::new(handler));
+    }
+
+    // 注册事件观察者，可以注册多个，按注册顺序调用
+    // Register an event observer, several may be registered and they are called in registration order
+    pub fn add_observer<O>(&self, observer: O)
+    where
+        O: PoolObserver + 'static,
+    {
+        // 复制现有列表并追加，正在通知的线程继续使用旧的快照
+        // Copy the current list and append, threads in the middle of notifying keep using the old snapshot
+        let mut observers = self.inner.observers.write().expect("Failed to lock observers");
+        let mut updated = observers.to_vec();
+        updated.push(Arc::new(observer));
+        *observers = updated.into();
+        drop(observers);
+        self.inner.has_observers.store(true, Ordering::SeqCst);
+    }
+
+    // 返回已提交的任务数
+    // Return submitted task count
+    pub fn submitted_tasks(&self) -> usize {
+        self.inner.submitted_tasks.load(Ordering::SeqCst)
+    }
+
+    // 返回已完成的任务数
+    // Return completed task count
+    pub fn completed_tasks(&self) -> usize {
+        self.inner.completed_tasks.load(Ordering::SeqCst)
+    }
+
+    // 返回被拒绝的任务数：被拒绝策略丢弃或在调用者线程上执行的任务、被丢弃最旧策略移除的任务、try_submit 和 submit_timeout 原样返回的任务，以及关闭后提交的任务
+    // Return count of rejected tasks: tasks dropped or run on the caller by the rejection policy, tasks removed by the discard-oldest policy, tasks handed back by try_submit and submit_timeout, and tasks submitted after shutdown
+    pub fn rejected_tasks(&self) -> usize {
+        self.inner.rejected_tasks.load(Ordering::SeqCst)
+    }
+
+    // 返回被取消的任务数，不计入已完成的任务数
+    // Return cancelled task count, not included in the completed task count
+    pub fn cancelled_tasks(&self) -> usize {
+        self.inner.cancelled_tasks.load(Ordering::SeqCst)
+    }
+
+    // 返回因过期而被丢弃的任务数
+    // Return count of tasks discarded because they expired
+    pub fn expired_tasks(&self) -> usize {
+        self.inner.expired_tasks.load(Ordering::SeqCst)
+    }
+
+    // 返回发生 panic 的任务数
+    // Return panicked task count
+    pub fn panicked_tasks(&self) -> usize {
+        self.inner.panicked_tasks.load(Ordering::SeqCst)
+    }
+
+    // 返回当前线程数
+    // Return current thread count
+    pub fn threads_num(&self) -> usize {
+        // 原子性地加载当前线程数
+        // Atomically load current thread count
+        self.inner.current_threads.load(Ordering::SeqCst) 
+    }
+
+    // 等待所有任务完成（修复了逻辑缺陷）
+    // Wait for all tasks to complete (fixed logic flaws)
+    pub fn wait_for_completion(&self) {
+        // 没有截止时间的等待不会超时
+        // Waiting without a deadline never times out
+        self.wait_for_completion_until(None);
+    }
+
+    // 等待所有任务完成，最多等待指定时间，返回是否在超时前全部完成
+    // Wait for all tasks to complete for at most the given duration, returns whether they all finished before the timeout
+    pub fn wait_for_completion_timeout(&self, timeout: Duration) -> bool {
+        self.wait_for_completion_until(Some(Instant::now() + timeout))
+    }
+
+    // 等待线程池空闲：队列为空且没有正在执行的任务
+    // Wait until the pool is idle: the queue is empty and no task is executing
+    pub fn wait_idle(&self) {
+        self.inner.wait_until(None, || {
+            // 工作线程在队列锁内增加活跃任务数，因此不会看到 "队列空且无活跃任务" 的中间状态
+            // Workers increment the active count under the queue lock, so the "queue empty and nothing active" gap is never observed
+            let queue = self.inner.tasks.0.lock().expect("Failed to lock tasks mutex");
+            // 从本地队列出队时先增加活跃任务数再减少本地任务数，因此要先读本地任务数
+            // Popping a local deque increments the active count before decrementing the local count, so the local count is read first
+            self.inner.injector_is_empty(&queue)
+                && self.inner.local_jobs.load(Ordering::SeqCst) == 0
+                && self.inner.active_tasks.load(Ordering::SeqCst) == 0
+        });
+    }
+
+    // 等待调用时已提交的所有任务结束，直到截止时间
+    // Wait until all tasks submitted at call time have finished, until the deadline
+    fn wait_for_completion_until(&self, deadline: Option<Instant>) -> bool {
+        // 快照机制：获取当前已提交的任务数作为目标，避免不一致性问题
+        // Snapshot mechanism: Get current submitted task count as target to avoid inconsistency issues
+        let target = self.inner.submitted_tasks.load(Ordering::SeqCst); 
+        
+        // 使用条件变量阻塞等待，不再自旋占用CPU
+        // Block on the condition variable instead of spinning on the CPU
+        // 发生 panic 或被移出队列的任务同样视为已结束，否则会永远等待
+        // Panicked tasks and tasks removed from the queue also count as finished, otherwise waiting would never end
+        self.inner.wait_until(deadline, || self.inner.finished_tasks.load(Ordering::SeqCst) >= target)
+    }
+
+    // 获取最大线程数（新增方法，用于外部查询）
+    // Get maximum thread count (new method for external queries)
+    pub fn get_max_threads(&self) -> usize {
+        // 返回最大线程数
+        // Return maximum thread count
+        self.inner.max_threads 
+    }
+
+    // 返回当前排队的任务总数（包括工作窃取模式下各本地队列中的任务）
+    // Return total number of queued tasks (including tasks in the local deques in work-stealing mode)
+    pub fn queue_len(&self) -> usize {
+        let injector = match &self.inner.lock_free {
+            Some(queue) => queue.len(),
+            None => self.inner.tasks.0.lock().expect("Failed to lock tasks mutex").len(),
+        };
+        injector + self.inner.local_jobs.load(Ordering::SeqCst)
+    }
+
+    // 返回每个工作线程正在执行的任务（任务名、开始时间和已执行时长），按工作线程 ID 排列；空闲的工作线程同样列出，其任务字段为 None
+    // Return the task every worker is executing (name, start time and elapsed time), ordered by worker ID; idle workers are listed too, with their task fields set to None
+    pub fn running_tasks(&self) -> Vec<RunningTask> {
+        let mut running: Vec<RunningTask> = self
+            .inner
+            .workers
+            .read()
+            .expect("Failed to lock worker statuses")
+            .iter()
+            .map(|status| status.snapshot())
+            .collect();
+        running.sort_by_key(|task| task.worker_id);
+        running
+    }
+
+    // 按任务名汇总排队中的任务数，未命名的任务归入 None，按数量从多到少排列
+    // Summarize queued tasks by name, unnamed tasks are grouped under None, ordered from the most to the fewest
+    pub fn queued_tasks(&self) -> Vec<(Option<Arc<str>>, usize)> {
+        let named = self.inner.queued_names.snapshot();
+
+        // 未命名的任务数由总排队数推算，两者分别读取，并发入队时只是近似值
+        // The unnamed count is derived from the total queue length, both are read separately so it is approximate under concurrent submission
+        let named_total: usize = named.iter().map(|(_, count)| count).sum();
+        let unnamed = self.queue_len().saturating_sub(named_total);
+
+        let mut summary: Vec<(Option<Arc<str>>, usize)> = named.into_iter().map(|(name, count)| (Some(name), count)).collect();
+        if unnamed > 0 {
+            summary.push((None, unnamed));
+        }
+        summary.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        summary
+    }
+
+    // 返回每个优先级当前排队的任务数（老化提升后按实际级别统计），从高到低排列
+    // Return the number of tasks queued at each priority (by effective level after aging), ordered from high to low
+    pub fn queue_depths(&self) -> Vec<(Priority, usize)> {
+        match &self.inner.lock_free {
+            Some(queue) => queue.depths(),
+            None => self.inner.tasks.0.lock().expect("Failed to lock tasks mutex").depths(),
+        }
+    }
+
+    // 获取任务队列容量，为 None 时队列无界
+    // Get task queue capacity, None means the queue is unbounded
+    pub fn get_queue_capacity(&self) -> Option<usize> {
+        self.inner.queue_capacity
+    }
+
+    // 获取任务在队列中的最长存活时间，为 None 时不限制
+    // Get maximum time a task may live in the queue, None means unlimited
+    pub fn get_queue_ttl(&self) -> Option<Duration> {
+        self.inner.queue_ttl
+    }
+
+    // 获取全局队列的实现方式
+    // Get the implementation of the global queue
+    pub fn get_queue_kind(&self) -> QueueKind {
+        match self.inner.lock_free {
+            Some(_) => QueueKind::LockFree,
+            None => QueueKind::Locked,
+        }
+    }
+
+    // 获取调度模式
+    // Get scheduling mode
+    pub fn get_scheduler(&self) -> SchedulerMode {
+        self.inner.scheduler
+    }
+
+    // 获取核心线程数
+    // Get core thread count
+    pub fn get_core_threads(&self) -> usize {
+        self.inner.core_threads
+    }
+
+    // 获取空闲线程存活时间
+    // Get idle thread keep-alive time
+    pub fn get_keep_alive(&self) -> Duration {
+        self.inner.keep_alive
+    }
+
+    // 返回线程池当前状态的快照
+    // Return a snapshot of the current pool state
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            threads: self.threads_num(),
+            idle_threads: self.inner.idle_threads.load(Ordering::SeqCst),
+            peak_threads: self.inner.peak_threads.load(Ordering::SeqCst),
+            queued_tasks: self.queue_len(),
+            active_tasks: self.inner.active_tasks.load(Ordering::SeqCst),
+            submitted_tasks: self.submitted_tasks(),
+            completed_tasks: self.completed_tasks(),
+            panicked_tasks: self.panicked_tasks(),
+            rejected_tasks: self.rejected_tasks(),
+            cancelled_tasks: self.cancelled_tasks(),
+            expired_tasks: self.expired_tasks(),
+            spawned_threads: self.inner.spawned_threads.load(Ordering::SeqCst),
+            retired_threads: self.inner.retired_threads.load(Ordering::SeqCst),
+            uptime: self.inner.started_at.elapsed(),
+            queue_wait: self.inner.queue_wait.summary(),
+            run_time: self.inner.run_time.summary(),
+        }
+    }
+
+    // 清空排队等待时间和执行时间直方图
+    // Clear the queue wait and run time histograms
+    pub fn reset_latency(&self) {
+        self.inner.queue_wait.reset();
+        self.inner.run_time.reset();
+    }
+}
+
+impl Inner {
+    // 记录若干任务已结束，并唤醒等待任务结束的调用者
+    // Record that some tasks have finished and wake callers waiting for task completion
+    fn finish_tasks(&self, count: usize) {
+        self.finished_tasks.fetch_add(count, Ordering::SeqCst);
+        self.notify_completion();
+    }
+
+    // 唤醒等待任务结束的调用者，没有等待者时不加锁
+    // Wake callers waiting for task completion, without locking when nobody waits
+    fn notify_completion(&self) {
+        // 等待者先登记再检查条件，因此这里看到0时等待者一定能看到最新的计数
+        // Waiters register before checking their condition, so when 0 is seen here the waiter is sure to see the latest counters
+        if self.completion_waiters.load(Ordering::SeqCst) > 0 {
+            // 先获取再释放锁，保证通知不会发生在等待者检查条件和开始等待之间
+            // Acquire and release the lock first so the notification cannot land between a waiter's check and its wait
+            drop(self.completion.0.lock().expect("Failed to lock completion mutex"));
+            self.completion.1.notify_all();
+        }
+    }
+
+    // 在 "任务已结束" 条件变量上等待，直到条件成立或超过截止时间，返回条件是否成立
+    // Wait on the "task finished" condition variable until the condition holds or the deadline passes, returns whether it holds
+    fn wait_until<C>(&self, deadline: Option<Instant>, condition: C) -> bool
+    where
+        C: Fn() -> bool,
+    {
+        // 登记为等待者，使任务结束时发出通知
+        // Register as a waiter so finishing tasks send notifications
+        self.completion_waiters.fetch_add(1, Ordering::SeqCst);
+        let mut guard = self.completion.0.lock().expect("Failed to lock completion mutex");
+
+        let satisfied = loop {
+            if condition() {
+                break true;
+            }
+            guard = match deadline {
+                // 没有截止时间，一直等待
+                // No deadline, wait indefinitely
+                None => self.completion.1.wait(guard).expect("Condvar wait failed"),
+
+                // 有截止时间，只等待剩余时间
+                // With a deadline, only wait for the remaining time
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        break false;
+                    }
+                    self.completion
+                        .1
+                        .wait_timeout(guard, remaining)
+                        .expect("Condvar wait failed")
+                        .0
+                }
+            };
+        };
+
+        drop(guard);
+        self.completion_waiters.fetch_sub(1, Ordering::SeqCst);
+        satisfied
+    }
+
+    // 将任务封装为队列条目，并分配任务编号
+    // Wrap a task into a queue entry and assign a task id
+    pub(crate) fn new_job(&self, task: Task) -> Job {
+        self.job_with_id(self.next_task_id(), task)
+    }
+
+    // 分配一个任务编号
+    // Allocate a task id
+    pub(crate) fn next_task_id(&self) -> usize {
+        self.next_task_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    // 使用已分配的任务编号将任务封装为队列条目
+    // Wrap a task into a queue entry using an already allocated task id
+    pub(crate) fn job_with_id(&self, id: usize, task: Task) -> Job {
+        Job {
+            id,
+            priority: Priority::Normal,
+            token: None,
+            deadline: None,
+            on_expired: None,
+            enqueued_at: Instant::now(),
+            name: None,
+            repoll: false,
+            task,
+        }
+    }
+
+    // 队列是否已达到容量上限
+    // Whether the queue has reached its capacity
+    fn is_full(&self, queue: &TaskQueue) -> bool {
+        self.queue_capacity.is_some_and(|capacity| queue.len() >= capacity)
+    }
+
+    // 在 "队列有空位" 条件变量上等待，直到队列有空位或超过截止时间（超时返回 None）
+    // Wait on the "space available" condition variable until the queue has space or the deadline passes (None on timeout)
+    fn wait_for_space<'a>(
+        &self,
+        mut queue: MutexGuard<'a, TaskQueue>,
+        deadline: Option<Instant>,
+    ) -> Option<MutexGuard<'a, TaskQueue>> {
+        // 线程池关闭时停止等待，由调用方检查关闭状态
+        // Stop waiting once the pool is shut down, the caller checks the shutdown state
+        while self.is_full(&queue) && !self.quit.load(Ordering::SeqCst) {
+            queue = match deadline {
+                // 没有截止时间，一直等待
+                // No deadline, wait indefinitely
+                None => self.space_available.wait(queue).expect("Condvar wait failed"),
+
+                // 有截止时间，只等待剩余时间
+                // With a deadline, only wait for the remaining time
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return None;
+                    }
+                    self.space_available
+                        .wait_timeout(queue, remaining)
+                        .expect("Condvar wait failed")
+                        .0
+                }
+            };
+        }
+        Some(queue)
+    }
+
+    // 依次调用所有观察者，回调中的 panic 被捕获；没有观察者时不加锁
+    // Call every observer in turn, catching panics inside the callbacks; skips the lock when there is no observer
+    pub(crate) fn notify<E>(&self, event: E)
+    where
+        E: Fn(&dyn PoolObserver),
+    {
+        if !self.has_observers.load(Ordering::SeqCst) {
+            return;
+        }
+
+        // 先取得快照再释放读锁，回调中可以安全地注册新的观察者
+        // Take the snapshot before releasing the read lock, so callbacks may safely register new observers
+        let observers = Arc::clone(&self.observers.read().expect("Failed to lock observers"));
+        for observer in observers.iter() {
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| event(observer.as_ref())));
+        }
+    }
+
+    // 记录任务已入队并通知观察者
+    // Record that a task was queued and notify observers
+    fn record_submitted(&self, task_id: usize, priority: Priority) {
+        self.submitted_tasks.fetch_add(1, Ordering::SeqCst);
+        self.notify(|observer| observer.on_task_submitted(task_id, priority));
+    }
+
+    // 记录任务被拒绝并通知观察者，调用时不能持有队列锁
+    // Record that a task was rejected and notify observers, must not be called while holding the queue lock
+    pub(crate) fn reject(&self, error: SubmitError) {
+        self.rejected_tasks.fetch_add(1, Ordering::SeqCst);
+        self.notify(|observer| observer.on_task_rejected(&error));
+    }
+
+    // 任务离开队列（被取出执行或被移除）时减少其名称的排队计数
+    // Decrement the queued count of the task's name when it leaves the queue (taken to run or removed)
+    fn unqueue(&self, job: Job) -> Job {
+        if let Some(name) = &job.name {
+            self.queued_names.remove(name);
+        }
+        job
+    }
+
+    // 记录任务的入队时间；配置了队列存活时间时，为没有截止时间的任务从入队时刻开始计时
+    // Stamp the task's enqueue time; with a queue TTL configured, tasks without a deadline get one counted from the moment they are queued
+    fn stamp_enqueued(&self, job: &mut Job) {
+        // 在放入队列之前计数，保证取出时的减少不会先于增加
+        // Count before the job becomes visible in a queue, so the decrement at dequeue never comes first
+        if let Some(name) = &job.name {
+            self.queued_names.add(name);
+        }
+        job.enqueued_at = Instant::now();
+
+        // 再次轮询不是新的提交，不受队列存活时间限制，否则过期丢弃后 Future 再也不会被轮询
+        // A re-poll is not a new submission and ignores the queue TTL, otherwise discarding it would leave the future never polled again
+        if job.deadline.is_none() && !job.repoll {
+            job.deadline = self.queue_ttl.map(|ttl| job.enqueued_at + ttl);
+        }
+    }
+
+    // 在已持有锁且确认有空位的队列中加入任务，并在没有空闲线程时尝试创建新线程
+    // Push a task into the locked queue known to have space, and try to create a new thread when none is idle
+    fn enqueue(self: &Arc<Self>, mut queue: MutexGuard<'_, TaskQueue>, mut job: Job) {
+        self.stamp_enqueued(&mut job);
+
+        // 将任务加入对应优先级的队列尾部
+        // Add task to the end of the queue for its priority
+        let (task_id, priority, repoll) = (job.id, job.priority, job.repoll);
+        queue.push(job); 
+
+        // 减少锁持有时间，优化性能，尽早释放锁
+        // Reduce lock holding time to optimize performance, release lock as early as possible
+        drop(queue);
+        
+        // 原子性地增加已提交任务数并通知观察者
+        // Atomically increment submitted task count and notify observers
+        if !repoll {
+            self.record_submitted(task_id, priority); 
+        }
+        
+        // 通知一个等待的线程
+        // Notify one waiting thread
+        self.tasks.1.notify_one(); 
+        // notify_one 方法用于通知一个等待在该条件变量上的线程，使其从等待状态唤醒
+        // notify_one method is used to notify one thread waiting on this condition variable, waking it from waiting state
+
+        // 只在需要时尝试创建线程，避免不必要的检查
+        // Only try to create thread when needed, avoid unnecessary checks
+        // 使用Relaxed排序提高性能，因为这里只是一个提示性检查
+        // Use Relaxed ordering for better performance, as this is just a hint check
+        if self.idle_threads.load(Ordering::Relaxed) == 0 { // 如果没有空闲线程
+                                                             // If no idle threads
+            // 尝试创建新线程（原子化操作）
+            // Try to create new thread (atomic operation)
+            self.try_spawn_thread(); 
+        }
+    }
+
+    // 将任务放入已预留的队列位置
+    // Push a task into a reserved queue place
+    fn enqueue_reserved(self: &Arc<Self>, reserved: Reserved<'_>, job: Job) {
+        match reserved {
+            Reserved::Local(local) => self.push_local(&local, job),
+            Reserved::LockFree(queue) => self.enqueue_lock_free(queue, job),
+            Reserved::Locked(queue) => self.enqueue(queue, job),
+        }
+    }
+
+    // 将到期的延迟任务或被唤醒的 Future 放入队列（由定时器线程或唤醒者调用），不受队列容量限制以免阻塞调用者；
+    // 线程池已关闭时丢弃任务，返回任务是否已入队
+    // Push a due delayed task or a woken future into the queue (called by the timer thread or a waker), ignoring capacity so the caller never blocks;
+    // the task is dropped once the pool is shut down, returns whether it was queued
+    pub(crate) fn enqueue_due(self: &Arc<Self>, job: Job) -> bool {
+        // 无锁队列：强制预留位置后再检查关闭标志，与提交路径的顺序一致
+        // Lock-free queue: force a reservation and then check the exit flag, in the same order as the submit path
+        if let Some(queue) = &self.lock_free {
+            queue.force_reserve();
+            if self.quit.load(Ordering::SeqCst) {
+                self.release_closed(queue);
+                drop(job);
+                return false;
+            }
+            self.enqueue_lock_free(queue, job);
+            return true;
+        }
+
+        let queue = self.tasks.0.lock().expect("Failed to lock tasks mutex");
+        if self.quit.load(Ordering::SeqCst) {
+            drop(queue);
+            drop(job);
+            return false;
+        }
+        self.enqueue(queue, job);
+        true
+    }
+
+    // 在无锁队列中预留一个位置，队列已满时等待直到有空位或超过截止时间；线程池已关闭或超时时返回 false
+    // Reserve a place in the lock-free queue, waiting for space until the deadline when full; returns false on shutdown or timeout
+    fn reserve_lock_free(&self, queue: &LockFreeQueue, deadline: Option<Instant>) -> bool {
+        loop {
+            if self.quit.load(Ordering::SeqCst) {
+                return false;
+            }
+            if queue.try_reserve() {
+                // 预留之后再检查关闭标志：工作线程看到关闭标志后，只在位置数为0时退出，因此这里看到未关闭时任务一定会被执行
+                // Check the exit flag after reserving: once workers see the flag they only exit with zero places, so seeing it unset here guarantees the task runs
+                if self.quit.load(Ordering::SeqCst) {
+                    self.release_closed(queue);
+                    return false;
+                }
+                return true;
+            }
+
+            // 登记为等待者后再次检查，出队者先释放位置再通知，因此不会丢失唤醒
+            // Recheck after registering as a waiter, poppers release the place before notifying, so no wakeup is lost
+            let key = queue.space.prepare_wait();
+            if !queue.is_full() || self.quit.load(Ordering::SeqCst) {
+                queue.space.cancel_wait();
+                continue;
+            }
+            let timeout = match deadline {
+                None => None,
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        queue.space.cancel_wait();
+                        return false;
+                    }
+                    Some(remaining)
+                }
+            };
+            queue.space.wait(key, timeout);
+        }
+    }
+
+    // 关闭后释放刚预留的位置，并唤醒可能在等待位置数归零后退出的工作线程
+    // Release a place just reserved after shutdown, and wake workers that may be waiting for zero places before exiting
+    fn release_closed(&self, queue: &LockFreeQueue) {
+        queue.release(1);
+        queue.workers.notify_all();
+    }
+
+    // 将任务放入已预留的无锁队列位置，并唤醒一个空闲线程，没有空闲线程时尝试创建新线程
+    // Push a task into a reserved place of the lock-free queue and wake an idle worker, or try to create a thread when none is idle
+    fn enqueue_lock_free(self: &Arc<Self>, queue: &LockFreeQueue, mut job: Job) {
+        self.stamp_enqueued(&mut job);
+        let (task_id, priority, repoll) = (job.id, job.priority, job.repoll);
+        queue.push(job);
+        if !repoll {
+            self.record_submitted(task_id, priority);
+        }
+        queue.workers.notify_one();
+
+        if self.idle_threads.load(Ordering::Relaxed) == 0 {
+            self.try_spawn_thread();
+        }
+    }
+
+    // 从无锁队列取出一个任务，先增加活跃任务数再释放位置，使 wait_idle 不会在出队和执行之间误判为空闲
+    // Take a task from the lock-free queue, incrementing the active count before releasing the place so wait_idle never sees idle between dequeue and execution
+    fn pop_lock_free(&self, queue: &LockFreeQueue) -> Option<Job> {
+        let job = queue.pop()?;
+        self.active_tasks.fetch_add(1, Ordering::SeqCst);
+        queue.release(1);
+        Some(job)
+    }
+
+    // 全局队列是否为空（根据所用的实现检查加锁队列或无锁队列）
+    // Whether the global queue is empty (checks the locked or the lock-free queue, whichever is in use)
+    fn injector_is_empty(&self, queue: &TaskQueue) -> bool {
+        match &self.lock_free {
+            Some(lock_free) => lock_free.is_empty(),
+            None => queue.is_empty(),
+        }
+    }
+
+    // 唤醒所有空闲线程和所有等待空位的提交者
+    // Wake all idle workers and all submitters waiting for space
+    fn wake_all(&self) {
+        self.tasks.1.notify_all();
+        self.space_available.notify_all();
+        if let Some(queue) = &self.lock_free {
+            queue.workers.notify_all();
+            queue.space.notify_all();
+        }
+    }
+
+    // 通知等待终止的调用者；先获取再释放队列锁，保证通知不会发生在 await_termination 检查和开始等待之间
+    // Notify callers awaiting termination; acquire and release the queue lock first so the notification cannot land between await_termination's check and its wait
+    fn notify_terminated(&self) {
+        drop(self.tasks.0.lock().expect("Failed to lock tasks mutex"));
+        self.terminated.notify_all();
+    }
+
+    // 线程池在线程局部上下文中的标识，用于区分当前线程属于哪个线程池
+    // Identifier of the pool in the thread-local context, used to tell which pool the current thread belongs to
+    fn pool_id(&self) -> usize {
+        self as *const Inner as usize
+    }
+
+    // 工作窃取模式下，当前线程是本线程池的工作线程时返回其本地队列
+    // In work-stealing mode, return the local deque when the current thread is a worker of this pool
+    fn current_local(&self) -> Option<Arc<LocalQueue>> {
+        match self.scheduler {
+            SchedulerMode::Global => None,
+            SchedulerMode::WorkStealing => work_stealing::current_local(self.pool_id()),
+        }
+    }
+
+    // 将任务放入本地队列尾部（不受队列容量限制），并唤醒一个空闲线程来窃取，没有空闲线程时尝试创建新线程
+    // Push a task to the back of a local deque (ignoring queue capacity), and wake an idle worker to steal it, or try to create a thread when none is idle
+    fn push_local(self: &Arc<Self>, local: &LocalQueue, mut job: Job) {
+        self.stamp_enqueued(&mut job);
+        let (task_id, priority) = (job.id, job.priority);
+
+        {
+            let mut jobs = local.jobs.lock().expect("Failed to lock local queue mutex");
+            jobs.push_back(job);
+            self.local_jobs.fetch_add(1, Ordering::SeqCst);
+        }
+        self.record_submitted(task_id, priority);
+
+        // 使用无锁队列时空闲线程在事件计数器上休眠
+        // With the lock-free queue idle workers sleep on the event count
+        if let Some(queue) = &self.lock_free {
+            queue.workers.notify_one();
+            if self.idle_threads.load(Ordering::Relaxed) == 0 {
+                self.try_spawn_thread();
+            }
+            return;
+        }
+
+        // 空闲线程先增加空闲线程数再检查本地任务数，这里先增加本地任务数再检查空闲线程数，两者至少有一方能看到对方
+        // Idle workers increment the idle count before checking the local count, and this side does the reverse, so at least one sees the other
+        if self.idle_threads.load(Ordering::SeqCst) > 0 {
+            // 先获取再释放队列锁，保证通知不会发生在空闲线程检查条件和开始等待之间
+            // Acquire and release the queue lock first so the notification cannot land between an idle worker's check and its wait
+            drop(self.tasks.0.lock().expect("Failed to lock tasks mutex"));
+            self.tasks.1.notify_one();
+        } else {
+            self.try_spawn_thread();
+        }
+    }
+
+    // 从本地队列取出一个任务：所有者从尾部取（后进先出），窃取者从头部取（先进先出）
+    // Take a task from a local deque: the owner takes from the back (LIFO), thieves take from the front (FIFO)
+    fn pop_local(&self, local: &LocalQueue, owner: bool) -> Option<Job> {
+        let mut jobs = local.jobs.lock().expect("Failed to lock local queue mutex");
+        let job = if owner { jobs.pop_back() } else { jobs.pop_front() }?;
+
+        // 先增加活跃任务数再减少本地任务数，使 wait_idle 不会在出队和执行之间误判为空闲
+        // Increment the active count before decrementing the local count so wait_idle never sees idle between dequeue and execution
+        self.active_tasks.fetch_add(1, Ordering::SeqCst);
+        self.local_jobs.fetch_sub(1, Ordering::SeqCst);
+        Some(job)
+    }
+
+    // 从随机选择的起点开始依次尝试窃取其他工作线程的本地队列
+    // Try to steal from the other workers' local deques, starting at a randomly chosen one
+    fn steal(&self, thief: Option<&Arc<LocalQueue>>) -> Option<Job> {
+        let locals = self.locals.read().expect("Failed to lock local queues");
+        if locals.is_empty() {
+            return None;
+        }
+
+        let start = work_stealing::random_index(locals.len());
+        (0..locals.len())
+            .map(|offset| &locals[(start + offset) % locals.len()])
+            .filter(|victim| !thief.is_some_and(|thief| Arc::ptr_eq(thief, victim)))
+            .find_map(|victim| self.pop_local(victim, false))
+    }
+
+    // 取出所有本地队列中的任务
+    // Take all tasks out of the local deques
+    fn drain_locals(&self) -> Vec<Job> {
+        let locals = self.locals.read().expect("Failed to lock local queues");
+        let mut drained = Vec::new();
+        for local in locals.iter() {
+            let mut jobs = local.jobs.lock().expect("Failed to lock local queue mutex");
+            self.local_jobs.fetch_sub(jobs.len(), Ordering::SeqCst);
+            drained.extend(jobs.drain(..));
+        }
+        drained
+    }
+
+    // 是否有待执行的任务（全局队列或任一本地队列非空）
+    // Whether there is any task waiting to run (the global queue or any local deque is non-empty)
+    fn has_work(&self, queue: &TaskQueue) -> bool {
+        !self.injector_is_empty(queue) || self.local_jobs.load(Ordering::SeqCst) > 0
+    }
+
+    // 将当前线程登记为本线程池的工作线程并登记其状态槽，工作窃取模式下同时创建并登记本地队列
+    // Register the current thread as a worker of this pool along with its status slot, also creating and registering a local deque in work-stealing mode
+    fn register_worker(&self, thread_id: usize) -> (Option<Arc<LocalQueue>>, Arc<WorkerStatus>) {
+        let local = match self.scheduler {
+            SchedulerMode::Global => None,
+            SchedulerMode::WorkStealing => {
+                let local = Arc::new(LocalQueue::new(thread_id));
+                self.locals
+                    .write()
+                    .expect("Failed to lock local queues")
+                    .push(Arc::clone(&local));
+                Some(local)
+            }
+        };
+        let status = Arc::new(WorkerStatus::new(thread_id));
+        self.workers
+            .write()
+            .expect("Failed to lock worker statuses")
+            .push(Arc::clone(&status));
+        work_stealing::enter_worker(self.pool_id(), Arc::clone(&status), local.clone());
+        (local, status)
+    }
+
+    // 从加锁队列取出一个任务，在队列锁内增加活跃任务数，并唤醒一个等待空位的提交者
+    // Take a task from the locked queue, incrementing the active count under the queue lock, and wake one submitter waiting for space
+    fn pop_locked(&self) -> Option<Job> {
+        let mut queue = self.tasks.0.lock().expect("Failed to lock tasks mutex");
+        let job = queue.pop()?;
+        self.active_tasks.fetch_add(1, Ordering::SeqCst);
+        drop(queue);
+        self.space_available.notify_one();
+        Some(job)
+    }
+
+    // 不等待地取出一个待执行的任务：依次尝试本地队列、全局队列和其他工作线程的本地队列
+    // Take a task waiting to run without blocking: tries the local deque, the global queue and other workers' deques in turn
+    fn take_job(&self, local: Option<&Arc<LocalQueue>>) -> Option<Job> {
+        local
+            .and_then(|local| self.pop_local(local, true))
+            .or_else(|| match &self.lock_free {
+                Some(queue) => self.pop_lock_free(queue),
+                None => self.pop_locked(),
+            })
+            .or_else(|| {
+                if self.local_jobs.load(Ordering::SeqCst) > 0 {
+                    self.steal(local)
+                } else {
+                    None
+                }
+            })
+    }
+
+    // 工作线程退出时注销本地队列（所有者只在本地队列为空时才会等待和退出）并清理线程句柄
+    // Unregister the local deque when a worker exits (the owner only waits and exits with an empty deque) and clean up the thread handle
+    fn exit_worker(&self, thread_id: usize) {
+        if self.scheduler == SchedulerMode::WorkStealing {
+            self.locals
+                .write()
+                .expect("Failed to lock local queues")
+                .retain(|local| local.worker_id != thread_id);
+        }
+        self.workers
+            .write()
+            .expect("Failed to lock worker statuses")
+            .retain(|status| status.worker_id != thread_id);
+        work_stealing::leave_worker();
+        self.remove_thread(thread_id);
+        self.retired_threads.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // 原子化的线程创建函数，解决竞态条件问题
+    // Atomic thread creation function to solve race condition problems
+    fn try_spawn_thread(self: &Arc<Self>) -> bool {
+        // 无限循环，直到成功创建线程或确定不需要创建
+        // Infinite loop until successfully creating a thread or determining no need to create one
+        loop { 
+            // 获取当前线程数
+            // Get current thread count
+            let current = self.current_threads.load(Ordering::SeqCst); 
+            
+            // 如果当前线程数已达到最大值
+            // If current thread count has reached maximum
+            if current >= self.max_threads { 
+                // 返回false，表示不需要创建新线程
+                // Return false, indicating no need to create new thread
+                return false; 
+            }
+            
+            // 使用原子的compare_exchange_weak操作，确保检查-创建的原子性
+            // Use atomic compare_exchange_weak operation to ensure atomicity of check-create operation
+            // compare_exchange_weak: 比较当前值与期望值，如果相等则更新为新值
+            // compare_exchange_weak: Compare current value with expected value, update to new value if equal
+            // 参数: 期望值、新值、成功时的内存序、失败时的内存序
+            // Parameters: expected value, new value, memory order on success, memory order on failure
+            match self.current_threads.compare_exchange_weak(
+                current, // 期望的当前值
+                         // Expected current value
+                current + 1, // 要设置的新值（当前值+1）
+                             // New value to set (current value + 1)
+                Ordering::SeqCst, // 成功时的内存排序（顺序一致性）
+                                  // Memory ordering on success (sequential consistency)
+                Ordering::Relaxed, // 失败时的内存排序（宽松排序）
+                                   // Memory ordering on failure (relaxed ordering)
+            ) {
+                // 如果CAS操作成功，创建新线程
+                // If CAS operation succeeds, create new thread
+                // 返回是否成功创建线程
+                // Return whether the thread was created successfully
+                Ok(_) => return self.spawn_thread(), 
+                // 如果CAS操作失败，重试循环
+                // If CAS operation fails, retry the loop
+                Err(_) => continue, 
+            }
+        }
+    }
+
+    // 创建新线程的具体实现，调用前 current_threads 已经加一
+    // Specific implementation of creating new thread, current_threads has already been incremented by the caller
+    fn spawn_thread(self: &Arc<Self>) -> bool {
+        // 获取并原子性地增加下一个线程 ID
+        // Get and atomically increment next thread ID
+        let thread_id = self.next_thread_id.fetch_add(1, Ordering::SeqCst); 
+        
+        // 使用 thread::Builder 配置线程名和栈大小
+        // Use thread::Builder to configure thread name and stack size
+        let mut builder = thread::Builder::new();
+        if let Some(prefix) = &self.thread_name_prefix {
+            // 线程名格式为 "前缀-线程ID"
+            // Thread name format is "prefix-threadID"
+            builder = builder.name(format!("{}-{}", prefix, thread_id));
+        }
+        if let Some(stack_size) = self.stack_size {
+            builder = builder.stack_size(stack_size);
+        }
+
+        // 克隆共享状态的 Arc，用于在线程间共享
+        // Clone Arc of shared state for sharing between threads
+        let inner = Arc::clone(self); 
+
+        // 创建并启动新线程
+        // Create and start new thread
+        // move 关键字将克隆的Arc移动到线程闭包中
+        // move keyword moves the cloned Arc into thread closure
+        // 工作线程退出后在不持有任何锁时通知观察者
+        // Notify observers once the worker has exited, holding no locks
+        let worker = move || match inner.run_worker(thread_id) {
+            WorkerExit::Retired => inner.notify(|observer| observer.on_worker_retired(thread_id)),
+            WorkerExit::ShutDown => inner.notify(|observer| observer.on_worker_exited(thread_id)),
+        };
+
+        match builder.spawn(worker) {
+            Ok(handle) => {
+                // 将新线程的句柄插入线程集合
+                // Insert new thread's handle into thread collection
+                self.threads
+                    .lock()
+                    .expect("Failed to lock threads mutex") // 获取线程集合的锁
+                                                             // Get lock of thread collection
+                    .insert(thread_id, handle); // 插入线程ID和对应的JoinHandle
+                                                // Insert thread ID and corresponding JoinHandle
+
+                // 记录创建的线程数并更新最大线程数
+                // Record the spawned thread and update the peak thread count
+                self.spawned_threads.fetch_add(1, Ordering::SeqCst);
+                self.peak_threads.fetch_max(self.current_threads.load(Ordering::SeqCst), Ordering::SeqCst);
+                self.notify(|observer| observer.on_worker_spawned(thread_id));
+                true
+            }
+            Err(_) => {
+                // 系统无法创建线程，回滚当前线程数，任务留在队列中等待已有线程处理
+                // System failed to create the thread, roll back current thread count, the task stays queued for existing threads
+                self.current_threads.fetch_sub(1, Ordering::SeqCst);
+                false
+            }
+        }
+    }
+
+    // 尝试回收当前线程，只有线程数超过核心线程数时才会成功
+    // Try to reclaim the current thread, only succeeds while thread count exceeds core thread count
+    fn try_retire(&self) -> bool {
+        // fetch_update 原子地检查并减少线程数，避免多个线程同时超时导致低于核心线程数
+        // fetch_update atomically checks and decrements thread count, so simultaneous timeouts cannot drop below core count
+        self.current_threads
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                if current > self.core_threads {
+                    Some(current - 1)
+                } else {
+                    None
+                }
+            })
+            .is_ok()
+    }
+
+    // 清理线程句柄，避免资源泄漏
+    // Clean up thread handle to avoid resource leak
+    fn remove_thread(&self, thread_id: usize) {
+        // try_lock: 尝试获取锁，如果失败不阻塞
+        // try_lock: Try to acquire lock, don't block if failed
+        if let Ok(mut threads) = self.threads.try_lock() {
+            // 从HashMap中移除当前线程的句柄
+            // Remove current thread's handle from HashMap
+            threads.remove(&thread_id); 
+        }
+    }
+
+    // 工作线程的主循环
+    // Main loop of worker thread
+    fn run_worker(&self, thread_id: usize) -> WorkerExit {
+        // 工作窃取模式下登记本地队列，全局模式下为 None
+        // Register a local deque in work-stealing mode, None in global mode
+        let (local, status) = self.register_worker(thread_id);
+
+        // 使用无锁队列时进入不加锁的主循环
+        // With the lock-free queue, run the lock-free main loop instead
+        if let Some(queue) = &self.lock_free {
+            return self.run_lock_free_worker(queue, local, &status);
+        }
+
+        loop { 
+            // 优先从自己的本地队列尾部取任务（后进先出），无需获取全局队列的锁
+            // First take from the back of the own local deque (LIFO), without taking the global queue lock
+            if let Some(job) = local.as_ref().and_then(|local| self.pop_local(local, true)) {
+                self.run_job(job, &status);
+                continue;
+            }
+
+            // 声明任务变量
+            // Declare task variable
+            let job: Job; 
+            {
+                // 获取任务队列的锁和条件变量
+                // Get lock and condition variable of task queue
+                let (lock, cvar) = &self.tasks; 
+                
+                // 加锁获取任务队列的可变引用
+                // Lock to get mutable reference of task queue
+                let mut task_queue = lock.lock().expect("Failed to lock tasks mutex"); 
+                
+                // 原子性地增加空闲线程数
+                // Atomically increment idle thread count
+                self.idle_threads.fetch_add(1, Ordering::SeqCst); 
+                
+                // 当全局队列和所有本地队列都为空且未设置退出标志时，线程等待
+                // Wait when the global queue and all local deques are empty and exit flag is not set
+                while !self.has_work(&task_queue) && !self.quit.load(Ordering::SeqCst) {
+                    // wait_timeout: 在条件变量上等待，最多等待指定时间
+                    // wait_timeout: Wait on condition variable for at most specified time
+                    // 参数: MutexGuard、超时时长
+                    // Parameters: MutexGuard, timeout duration
+                    // 返回: (MutexGuard, WaitTimeoutResult)
+                    // Returns: (MutexGuard, WaitTimeoutResult)
+                    let result = cvar
+                        .wait_timeout(task_queue, self.keep_alive) // 等待条件变量，最多等待存活时间
+                                                                   // Wait on condition variable for at most the keep-alive time
+                        .expect("Condvar wait failed"); // 如果等待失败则panic，提供错误信息
+                                                         // If wait fails, panic with error message
+                    
+                    // 更新任务队列的MutexGuard
+                    // Update MutexGuard of task queue
+                    task_queue = result.0; 
+                    
+                    // 如果等待超时且队列仍为空，尝试回收非核心线程
+                    // If wait times out and the queue is still empty, try to reclaim a non-core thread
+                    if result.1.timed_out() && !self.has_work(&task_queue) && self.try_retire() { 
+                        // 原子性地减少空闲线程数
+                        // Atomically decrement idle thread count
+                        self.idle_threads.fetch_sub(1, Ordering::SeqCst); 
+
+                        // 关闭过程中可能是最后一个线程，通知等待终止的调用者
+                        // This may be the last thread during shutdown, notify callers awaiting termination
+                        self.terminated.notify_all();
+                        
+                        // 注销本地队列、清理线程句柄并退出线程
+                        // Unregister the local deque, clean up thread handle and exit thread
+                        self.exit_worker(thread_id);
+                        return WorkerExit::Retired; 
+                    }
+                }
+                
+                // 原子性地减少空闲线程数（线程即将执行任务）
+                // Atomically decrement idle thread count (thread is about to execute task)
+                self.idle_threads.fetch_sub(1, Ordering::SeqCst); 
+
+                // 如果设置了退出标志且没有待执行的任务，则退出线程
+                // Exit thread if exit flag is set and no task is waiting to run
+                if self.quit.load(Ordering::SeqCst) && !self.has_work(&task_queue) {
+                    // 原子性地减少当前线程数
+                    // Atomically decrement current thread count
+                    self.current_threads.fetch_sub(1, Ordering::SeqCst); 
+
+                    // 通知等待终止的调用者（仍持有队列锁，不会丢失通知）
+                    // Notify callers awaiting termination (still holding the queue lock, so the notification is not lost)
+                    self.terminated.notify_all();
+                    
+                    // 注销本地队列、清理线程句柄并退出线程
+                    // Unregister the local deque, clean up thread handle and exit thread
+                    self.exit_worker(thread_id);
+                    return WorkerExit::ShutDown; 
+                }
+
+                // 全局队列为空，说明任务在其他工作线程的本地队列中，释放锁后随机窃取；被抢先取走时重新等待
+                // The global queue is empty, so the work sits in other workers' local deques: steal at random after releasing the lock, and wait again if someone got there first
+                if task_queue.is_empty() {
+                    drop(task_queue);
+                    if let Some(stolen) = self.steal(local.as_ref()) {
+                        self.run_job(stolen, &status);
+                    }
+                    continue;
+                }
+
+                // 从最高优先级队列的前端获取任务
+                // Get task from the front of the highest-priority queue
+                job = task_queue.pop().expect("Task queue empty when expected task");
+
+                // 在队列锁内增加活跃任务数，使 wait_idle 不会在出队和执行之间误判为空闲
+                // Increment active count under the queue lock so wait_idle never sees idle between dequeue and execution
+                self.active_tasks.fetch_add(1, Ordering::SeqCst); 
+
+                // 队列腾出了一个空位，唤醒一个等待空位的提交者
+                // A slot was freed in the queue, wake one submitter waiting for space
+                self.space_available.notify_one();
+            } // 锁的作用域结束，自动释放锁
+              // Lock scope ends, automatically release lock
+            
+            // 执行任务并更新计数
+            // Execute task and update counters
+            self.run_job(job, &status);
+        }
+    }
+
+    // 使用无锁队列时工作线程的主循环：依次从本地队列、无锁全局队列和其他工作线程的本地队列取任务，都没有时在事件计数器上休眠
+    // Main loop of a worker with the lock-free queue: take work from the local deque, the lock-free global queue and other workers' deques in turn, sleeping on the event count when there is none
+    fn run_lock_free_worker(&self, queue: &LockFreeQueue, local: Option<Arc<LocalQueue>>, status: &WorkerStatus) -> WorkerExit {
+        let thread_id = status.worker_id;
+
+        // 是否有待执行的任务（无锁全局队列或任一本地队列非空）
+        // Whether there is any task waiting to run (the lock-free global queue or any local deque is non-empty)
+        let has_work = || !queue.is_empty() || self.local_jobs.load(Ordering::SeqCst) > 0;
+
+        loop {
+            if let Some(job) = self.take_job(local.as_ref()) {
+                self.run_job(job, status);
+                continue;
+            }
+
+            // 没有可执行的任务，登记为空闲线程和事件计数器的等待者
+            // No task to run, register as an idle thread and as a waiter on the event count
+            self.idle_threads.fetch_add(1, Ordering::SeqCst);
+            let key = queue.workers.prepare_wait();
+
+            // 先读关闭标志再检查任务：提交者预留位置后才检查关闭标志，因此关闭后看到没有任务时不会再有任务入队
+            // Read the exit flag before checking for work: submitters check the flag only after reserving, so seeing no work after shutdown means no task can still arrive
+            let quit = self.quit.load(Ordering::SeqCst);
+            if has_work() {
+                queue.workers.cancel_wait();
+                self.idle_threads.fetch_sub(1, Ordering::SeqCst);
+                continue;
+            }
+
+            // 已关闭且没有待执行的任务，退出线程
+            // Shut down with no task waiting to run, exit the thread
+            if quit {
+                queue.workers.cancel_wait();
+                self.idle_threads.fetch_sub(1, Ordering::SeqCst);
+                self.current_threads.fetch_sub(1, Ordering::SeqCst);
+                self.notify_terminated();
+                self.exit_worker(thread_id);
+                return WorkerExit::ShutDown;
+            }
+
+            // 休眠直到有新任务或超过存活时间，超时后尝试回收非核心线程
+            // Sleep until new work arrives or the keep-alive time elapses, then try to reclaim a non-core thread
+            let timed_out = queue.workers.wait(key, Some(self.keep_alive));
+            if timed_out && !has_work() && self.try_retire() {
+                self.idle_threads.fetch_sub(1, Ordering::SeqCst);
+                self.notify_terminated();
+                self.exit_worker(thread_id);
+                return WorkerExit::Retired;
+            }
+            self.idle_threads.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    // 在工作线程上执行从队列取出的任务（调用方已在出队时增加活跃任务数）
+    // Execute a task taken from a queue on a worker (the caller already incremented the active count at dequeue)
+    fn run_job(&self, job: Job, worker: &WorkerStatus) {
+        let job = self.unqueue(job);
+        self.execute(job, Some(worker));
+    }
+
+    // 在提交者线程上执行被拒绝的任务（调用者执行策略），计入已提交任务数后与工作线程走相同的执行和统计流程；
+    // 调用者本身是本线程池的工作线程时，任务同样出现在其正在执行的任务中
+    // Run a rejected task on the submitting thread (caller-runs policy), counting it as submitted and then going through the same execution and accounting as a worker;
+    // when the caller is itself a worker of this pool, the task also shows up as what that worker is running
+    pub(crate) fn run_on_caller(&self, job: Job) {
+        self.record_submitted(job.id, job.priority);
+        self.active_tasks.fetch_add(1, Ordering::SeqCst);
+        match work_stealing::current_worker(self.pool_id()) {
+            Some((status, _)) => self.execute(job, Some(&status)),
+            None => self.execute(job, None),
+        }
+    }
+
+    // 执行已出队的任务：检查令牌和截止时间、捕获 panic 并更新统计；调用前 active_tasks 已经增加
+    // Execute a dequeued task: check the token and deadline, catch panics and update the counters; active_tasks was incremented before the call
+    fn execute(&self, job: Job, worker: Option<&WorkerStatus>) {
+        let info = TaskInfo {
+            task_id: job.id,
+            priority: job.priority,
+            worker_id: worker.map(|worker| worker.worker_id),
+            name: job.name.clone(),
+        };
+
+        // 记录从入队到出队的等待时间，被跳过或丢弃的任务同样计入
+        // Record the wait from enqueue to dequeue, skipped and discarded tasks count as well
+        let queue_wait = job.enqueued_at.elapsed();
+        self.queue_wait.record(queue_wait);
+        self.notify(|observer| observer.on_task_dequeued(&info, queue_wait));
+
+        // 任务开始前令牌已被取消，跳过执行
+        // The token was cancelled before the task started, skip it
+        if job.token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            self.active_tasks.fetch_sub(1, Ordering::SeqCst);
+            self.finish_job(job.repoll, &self.cancelled_tasks);
+            return;
+        }
+
+        // 出队时已超过截止时间，丢弃任务并执行过期回调
+        // The deadline has passed at dequeue, discard the task and run the expiry callback
+        if job.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            drop(job.task);
+            if let Some(on_expired) = job.on_expired {
+                // 回调中的 panic 被捕获，保证工作线程存活
+                // Panics inside the callback are caught, keeping the worker alive
+                let _ = panic::catch_unwind(AssertUnwindSafe(on_expired));
+            }
+            self.active_tasks.fetch_sub(1, Ordering::SeqCst);
+            self.finish_job(job.repoll, &self.expired_tasks);
+            return;
+        }
+
+        // 执行任务，捕获 panic 以免工作线程退出导致计数失衡
+        // Execute task, catching panics so the worker does not die and leave counters unbalanced
+        self.notify(|observer| observer.on_task_started(&info));
+        let started = Instant::now();
+        let previous = worker.map(|worker| worker.begin(job.id, job.name.clone(), started));
+        let result = panic::catch_unwind(AssertUnwindSafe(job.task)); 
+        let run_time = started.elapsed();
+        if let (Some(worker), Some(previous)) = (worker, previous) {
+            worker.end(previous);
+        }
+        self.run_time.record(run_time);
+        
+        // 原子性地减少活跃任务数
+        // Atomically decrement active task count
+        self.active_tasks.fetch_sub(1, Ordering::SeqCst); 
+        
+        if result.is_ok() {
+            self.notify(|observer| observer.on_task_finished(&info, run_time));
+        }
+
+        let counter = match result {
+            // 任务返回时令牌已被取消，视为响应取消提前结束，计入被取消的任务数
+            // The token was cancelled when the task returned, treat it as having stopped early and count it as cancelled
+            Ok(()) if job.token.as_ref().is_some_and(CancellationToken::is_cancelled) => &self.cancelled_tasks,
+
+            // 任务正常结束，原子性地增加已完成任务数
+            // Task finished normally, atomically increment completed task count
+            Ok(()) => &self.completed_tasks,
+            // 任务发生 panic，调用 panic 处理器后增加 panic 任务数
+            // Task panicked, call the panic handler and then increment panicked task count
+            Err(payload) => {
+                // 先克隆处理器再释放读锁，避免处理器执行期间持有锁
+                // Clone the handler before releasing the read lock so it is not held while the handler runs
+                let handler = self
+                    .panic_handler
+                    .read()
+                    .expect("Failed to lock panic handler")
+                    .clone();
+
+                if let Some(handler) = handler {
+                    // 处理器自身的 panic 同样被捕获，保证工作线程存活
+                    // Panics inside the handler are caught as well, keeping the worker alive
+                    let _ = panic::catch_unwind(AssertUnwindSafe(|| handler(&*payload, &info)));
+                }
+
+                let message = task_handle::panic_message(&*payload);
+                self.notify(|observer| observer.on_task_panicked(&info, &message));
+
+                &self.panicked_tasks
+            }
+        };
+
+        // 无论成功还是 panic，任务都已结束
+        // Whether it succeeded or panicked, the task has finished
+        self.finish_job(job.repoll, counter);
+    }
+
+    // 记录任务已结束并增加对应的结果计数；Future 的再次轮询与首次轮询同属一个任务，不再计数，使已提交与已结束的任务数保持平衡
+    // Record that a task finished and increment the counter of its outcome; a future's re-polls belong to the same task as its first poll and are not counted again, keeping submitted and finished counts balanced
+    fn finish_job(&self, repoll: bool, counter: &AtomicUsize) {
+        if !repoll {
+            counter.fetch_add(1, Ordering::SeqCst);
+            self.finish_tasks(1);
+        }
+    }
+}
+
+// 线程池的析构函数，确保在销毁前完成所有任务
+// Destructor for thread pool, ensures all tasks are completed before destruction
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // 设置退出标志并通知所有等待在条件变量上的线程
+        // Set exit flag and notify all threads waiting on condition variables
+        self.shutdown(); 
+
+        // 线程池可能在自身的工作线程中被 drop（例如任务持有最后一个 Arc<ThreadPool>），此时不能等待自己
+        // The pool may be dropped on one of its own workers (e.g. a task holds the last Arc<ThreadPool>), which must not wait for itself
+        let current = thread::current().id();
+        let mut on_worker = false;
+
+        // 等待定时器线程退出（定时器已在 shutdown 中停止）
+        // Wait for the timer thread to exit (the timer was stopped in shutdown)
+        if let Some(handle) = self.inner.timer.take_thread() {
+            if handle.thread().id() != current {
+                handle.join().expect("Timer thread join failed");
+            }
+        }
+
+        // 获取线程集合的锁
+        // Get lock of thread collection
+        let mut threads = self.inner.threads.lock().expect("Failed to lock threads mutex"); 
+        
+        // drain(): 移除HashMap中的所有元素并返回迭代器
+        // drain(): Remove all elements from HashMap and return iterator
+        // map(): 将每个(key, value)对映射为value（即JoinHandle）
+        // map(): Map each (key, value) pair to value (i.e., JoinHandle)
+        for handle in threads.drain().map(|(_, handle)| handle) { // 获取所有线程句柄
+                                                                   // Get all thread handles
+            // 跳过当前线程自身
+            // Skip the current thread itself
+            if handle.thread().id() == current {
+                on_worker = true;
+                continue;
+            }
+
+            // 等待线程结束，如果线程panic则传播panic
+            // Wait for thread to finish, propagate panic if thread panics
+            handle.join().expect("Thread join failed"); 
+            // join 方法用于阻塞当前线程，直到被调用的线程执行结束
+            // join method is used to block current thread until the called thread finishes execution
+        }
+
+        // 等待所有活跃任务完成（在工作线程中 drop 时，当前任务本身就是活跃任务，不能等待）
+        // Wait for all active tasks to complete (when dropped on a worker, the current task is itself active and cannot be waited for)
+        // 使用 "任务已结束" 条件变量阻塞等待，替代原先的自旋
+        // Block on the "task finished" condition variable instead of the former spin loop
+        if !on_worker {
+            drop(threads);
+            self.inner.wait_until(None, || self.inner.active_tasks.load(Ordering::SeqCst) == 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::blocked_pool;
+    use std::sync::mpsc;
+
+    #[test]
+    fn try_submit_returns_the_task_when_the_queue_is_full() {
+        let (pool, release) = blocked_pool(ThreadPool::builder().queue_capacity(2));
+
+        assert!(pool.try_submit(|| {}).is_ok());
+        assert!(pool.try_submit(|| {}).is_ok());
+        let ran = Arc::new(AtomicBool::new(false));
+        let task_ran = Arc::clone(&ran);
+        let returned = pool
+            .try_submit(move || task_ran.store(true, Ordering::SeqCst))
+            .expect_err("Full queue accepted the task");
+
+        // 返回的正是被拒绝的任务
+        // The returned value is the rejected task itself
+        returned();
+        assert!(ran.load(Ordering::SeqCst));
+        assert_eq!(pool.queue_len(), 2);
+        assert_eq!(pool.rejected_tasks(), 1);
+
+        release.send(()).expect("Failed to release worker");
+        pool.wait_for_completion();
+        assert!(pool.try_submit(|| {}).is_ok());
+    }
+
+    #[test]
+    fn submit_timeout_waits_for_space_until_the_timeout() {
+        let (pool, release) = blocked_pool(ThreadPool::builder().queue_capacity(1));
+        pool.submit(|| {}).expect("Failed to submit task");
+
+        let start = Instant::now();
+        assert!(pool.submit_timeout(|| {}, Duration::from_millis(50)).is_err());
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(pool.rejected_tasks(), 1);
+
+        // 等待期间放出空位，提交成功
+        // Space frees up while waiting, so the submission succeeds
+        let releaser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            release.send(()).expect("Failed to release worker");
+        });
+        assert!(pool.submit_timeout(|| {}, Duration::from_secs(10)).is_ok());
+        releaser.join().expect("Releaser panicked");
+        pool.wait_for_completion();
+        assert_eq!(pool.completed_tasks(), 3);
+    }
+
+    #[test]
+    fn submit_blocks_until_the_queue_has_space() {
+        let (pool, release) = blocked_pool(ThreadPool::builder().queue_capacity(1));
+        let pool = Arc::new(pool);
+        pool.submit(|| {}).expect("Failed to submit task");
+
+        let (submitted_tx, submitted_rx) = mpsc::channel();
+        let submitter_pool = Arc::clone(&pool);
+        let submitter = thread::spawn(move || {
+            submitter_pool.submit(|| {}).expect("Failed to submit task");
+            submitted_tx.send(()).expect("Failed to signal submission");
+        });
+
+        assert!(submitted_rx.recv_timeout(Duration::from_millis(50)).is_err());
+        release.send(()).expect("Failed to release worker");
+        submitted_rx.recv_timeout(Duration::from_secs(10)).expect("Submit did not return after space freed up");
+        submitter.join().expect("Submitter panicked");
+    }
+
+    #[test]
+    #[should_panic(expected = "Queue capacity must be greater than 0")]
+    fn zero_queue_capacity_panics() {
+        ThreadPool::builder().queue_capacity(0);
+    }
+
+    #[test]
+    fn shutdown_runs_queued_tasks_and_rejects_new_ones() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        let ran = Arc::new(AtomicUsize::new(0));
+        for _ in 0..3 {
+            let ran = Arc::clone(&ran);
+            pool.submit(move || {
+                ran.fetch_add(1, Ordering::SeqCst);
+            })
+            .expect("Failed to submit task");
+        }
+
+        pool.shutdown();
+        assert!(pool.is_shutdown());
+        assert_eq!(pool.submit(|| {}), Err(SubmitError::ShutDown));
+
+        release.send(()).expect("Failed to release worker");
+        assert!(pool.await_termination(Duration::from_secs(10)));
+        assert_eq!(ran.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn shutdown_now_returns_queued_tasks() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        let ran = Arc::new(AtomicUsize::new(0));
+        for _ in 0..3 {
+            let ran = Arc::clone(&ran);
+            pool.submit(move || {
+                ran.fetch_add(1, Ordering::SeqCst);
+            })
+            .expect("Failed to submit task");
+        }
+
+        let drained = pool.shutdown_now();
+        assert_eq!(drained.len(), 3);
+        assert_eq!(pool.queue_len(), 0);
+
+        release.send(()).expect("Failed to release worker");
+        assert!(pool.await_termination(Duration::from_secs(10)));
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+
+        // 返回的任务仍可由调用方执行
+        // The returned tasks can still be run by the caller
+        drained.into_iter().for_each(|task| task());
+        assert_eq!(ran.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn await_termination_times_out_while_a_task_is_running() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+
+        pool.shutdown();
+        assert!(!pool.await_termination(Duration::from_millis(50)));
+        assert!(pool.is_shutdown());
+        assert!(!pool.is_terminated());
+
+        release.send(()).expect("Failed to release worker");
+        assert!(pool.await_termination(Duration::from_secs(10)));
+        assert!(pool.is_terminated());
+        assert_eq!(pool.threads_num(), 0);
+    }
+
+    #[test]
+    fn wait_for_completion_timeout_returns_false_while_a_task_is_blocked() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        pool.submit(|| {}).expect("Failed to submit task");
+
+        let start = Instant::now();
+        assert!(!pool.wait_for_completion_timeout(Duration::from_millis(50)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        release.send(()).expect("Failed to release worker");
+        assert!(pool.wait_for_completion_timeout(Duration::from_secs(10)));
+        assert_eq!(pool.completed_tasks(), 2);
+    }
+
+    #[test]
+    fn wait_idle_waits_for_queued_and_running_tasks() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        let ran = Arc::new(AtomicUsize::new(0));
+        for _ in 0..3 {
+            let ran = Arc::clone(&ran);
+            pool.submit(move || {
+                ran.fetch_add(1, Ordering::SeqCst);
+            })
+            .expect("Failed to submit task");
+        }
+
+        let releaser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            release.send(()).expect("Failed to release worker");
+        });
+        pool.wait_idle();
+
+        assert_eq!(ran.load(Ordering::SeqCst), 3);
+        assert_eq!(pool.queue_len(), 0);
+        releaser.join().expect("Releaser panicked");
+    }
+
+    #[test]
+    fn tasks_past_their_deadline_are_discarded_and_call_on_expired() {
+        let (pool, release) = blocked_pool(ThreadPool::builder());
+        let ran = Arc::new(AtomicUsize::new(0));
+        let expired = Arc::new(AtomicUsize::new(0));
+
+        let (expired_ran, on_expired) = (Arc::clone(&ran), Arc::clone(&expired));
+        pool.submit_with_deadline_or_else(
+            Instant::now(),
+            move || {
+                expired_ran.fetch_add(1, Ordering::SeqCst);
+            },
+            move || {
+                on_expired.fetch_add(1, Ordering::SeqCst);
+            },
+        )
+        .expect("Failed to submit task");
+        let late_ran = Arc::clone(&ran);
+        pool.submit_with_deadline(Instant::now(), move || {
+            late_ran.fetch_add(1, Ordering::SeqCst);
+        })
+        .expect("Failed to submit task");
+        let timely_ran = Arc::clone(&ran);
+        pool.submit_with_deadline(Instant::now() + Duration::from_secs(60), move || {
+            timely_ran.fetch_add(1, Ordering::SeqCst);
+        })
+        .expect("Failed to submit task");
+
+        release.send(()).expect("Failed to release worker");
+        pool.wait_for_completion();
+
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+        assert_eq!(expired.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.expired_tasks(), 2);
+        assert_eq!(pool.completed_tasks(), 2);
+    }
+
+    #[test]
+    fn tasks_queued_longer_than_the_ttl_expire() {
+        let (pool, release) = blocked_pool(ThreadPool::builder().queue_ttl(Duration::from_millis(20)));
+        let ran = Arc::new(AtomicUsize::new(0));
+
+        let stale_ran = Arc::clone(&ran);
+        pool.submit(move || {
+            stale_ran.fetch_add(1, Ordering::SeqCst);
+        })
+        .expect("Failed to submit task");
+
+        // 显式截止时间优先于队列存活时间
+        // An explicit deadline takes precedence over the queue TTL
+        let explicit_ran = Arc::clone(&ran);
+        pool.submit_with_deadline(Instant::now() + Duration::from_secs(60), move || {
+            explicit_ran.fetch_add(1, Ordering::SeqCst);
+        })
+        .expect("Failed to submit task");
+
+        thread::sleep(Duration::from_millis(50));
+        release.send(()).expect("Failed to release worker");
+        pool.wait_for_completion();
+
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.expired_tasks(), 1);
+    }
+}
@@ -0,0 +1,635 @@
+// 导入 cmp（比较）、mem（取出堆）和 BinaryHeap（二叉堆，用作定时器最小堆）
+// Import cmp (comparison), mem (taking the heap out) and BinaryHeap (binary heap, used as the timer min-heap)
+use std::cmp;
+use std::collections::BinaryHeap;
+use std::mem;
+
+// 导入 Arc（原子引用计数）、Weak（弱引用）、Mutex（互斥锁）和 Condvar（条件变量）
+// Import Arc (atomic reference counting), Weak (weak reference), Mutex (mutual exclusion lock), and Condvar (condition variable)
+use std::sync::{Arc, Condvar, Mutex, Weak};
+
+// 导入 AtomicU8（原子状态）、AtomicUsize（原子计数）和 Ordering（内存排序）
+// Import AtomicU8 (atomic state), AtomicUsize (atomic counter), and Ordering (memory ordering)
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+// 导入 panic 模块和 AssertUnwindSafe（用于捕获周期任务 panic）
+// Import panic module and AssertUnwindSafe (used to catch periodic task panics)
+use std::panic::{self, AssertUnwindSafe};
+
+// 导入线程模块和 JoinHandle（用于定时器线程句柄）
+// Import thread module and JoinHandle (for the timer thread handle)
+use std::thread::{self, JoinHandle};
+
+// 导入 Duration（时间段）和 Instant（时间点）
+// Import Duration (time duration) and Instant (point in time)
+use std::time::{Duration, Instant};
+
+// 导入线程池内部状态和任务条目
+// Import thread pool internal state and task entry
+use crate::thread_pool::{Inner, Job};
+
+// 导入提交错误类型
+// Import submit error type
+use crate::rejection::SubmitError;
+
+// 定时任务状态：等待到期
+// Scheduled task state: waiting to become due
+const PENDING: u8 = 0;
+
+// 定时任务状态：已到期并放入任务队列
+// Scheduled task state: became due and was moved into the task queue
+const FIRED: u8 = 1;
+
+// 定时任务状态：已取消
+// Scheduled task state: cancelled
+const CANCELLED: u8 = 2;
+
+// 周期任务状态：运行中
+// Periodic task state: running
+const ACTIVE: u8 = 0;
+
+// 周期任务状态：因 panic 而停止
+// Periodic task state: stopped by a panic
+const FAILED: u8 = 3;
+
+// 定时任务句柄，可在任务到期前取消
+// Scheduled task handle, can cancel the task before it becomes due
+#[derive(Debug, Clone)]
+pub struct ScheduledTask {
+    // 与定时器共享的任务状态
+    // Task state shared with the timer
+    state: Arc<AtomicU8>,
+
+    // 任务到期时间
+    // Time the task becomes due
+    deadline: Instant,
+
+    // 线程池内部状态的弱引用，取消时通知定时器清理已取消的条目
+    // Weak reference to the pool internals, cancelling tells the timer to purge cancelled entries
+    inner: Weak<Inner>,
+}
+
+impl ScheduledTask {
+    // 取消任务，返回是否在到期前成功取消；已进入任务队列的任务无法取消
+    // Cancel the task, returns whether it was cancelled before becoming due; tasks already moved into the queue cannot be cancelled
+    pub fn cancel(&self) -> bool {
+        match self.inner.upgrade() {
+            Some(inner) => inner.timer.cancel(&self.state),
+
+            // 线程池已被销毁，定时器中的条目已随之释放
+            // The pool is gone, and the timer entries were released with it
+            None => self
+                .state
+                .compare_exchange(PENDING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok(),
+        }
+    }
+
+    // 任务是否已被取消
+    // Whether the task has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == CANCELLED
+    }
+
+    // 任务是否已到期并放入任务队列
+    // Whether the task became due and was moved into the task queue
+    pub fn is_fired(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == FIRED
+    }
+
+    // 返回任务到期时间
+    // Return the time the task becomes due
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+}
+
+// 定时器堆中的条目
+// Entry in the timer heap
+struct TimerEntry {
+    // 到期时间
+    // Due time
+    deadline: Instant,
+
+    // 调度序号，到期时间相同的任务按调度顺序执行
+    // Scheduling sequence number, tasks with the same due time run in scheduling order
+    seq: u64,
+
+    // 与句柄共享的任务状态
+    // Task state shared with the handle
+    state: Arc<AtomicU8>,
+
+    // 到期后放入任务队列的任务
+    // Task moved into the task queue once due
+    job: Job,
+}
+
+// BinaryHeap 是最大堆，反转比较结果使最早到期的条目位于堆顶
+// BinaryHeap is a max-heap, the comparison is reversed so the earliest due entry sits on top
+impl Ord for TimerEntry {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (other.deadline, other.seq).cmp(&(self.deadline, self.seq))
+    }
+}
+
+impl PartialOrd for TimerEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for TimerEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl Eq for TimerEntry {}
+
+// 定时器的可变状态，由互斥锁保护
+// Mutable timer state, protected by the mutex
+struct TimerState {
+    // 按到期时间排序的待执行任务
+    // Pending tasks ordered by due time
+    heap: BinaryHeap<TimerEntry>,
+
+    // 下一个调度序号
+    // Next scheduling sequence number
+    next_seq: u64,
+
+    // 堆中已取消但尚未移除的条目数
+    // Number of cancelled entries still in the heap
+    cancelled: usize,
+
+    // 定时器线程句柄，首次调度时才创建线程
+    // Timer thread handle, the thread is only created on the first schedule
+    thread: Option<JoinHandle<()>>,
+
+    // 停止标志，设置后定时器线程退出且不再接收新任务
+    // Stop flag, once set the timer thread exits and no new tasks are accepted
+    stopped: bool,
+}
+
+// 定时器：在单独的线程中等待最早到期的任务，到期后才将其放入线程池的任务队列
+// Timer: waits for the earliest due task on a dedicated thread and only moves it into the pool's task queue once due
+pub(crate) struct Timer {
+    // 定时器状态
+    // Timer state
+    state: Mutex<TimerState>,
+
+    // 堆顶变化或停止时唤醒定时器线程
+    // Wakes the timer thread when the heap top changes or the timer stops
+    wakeup: Condvar,
+}
+
+impl Timer {
+    // 创建空定时器
+    // Create an empty timer
+    pub(crate) fn new() -> Self {
+        Timer {
+            state: Mutex::new(TimerState {
+                heap: BinaryHeap::new(),
+                next_seq: 0,
+                cancelled: 0,
+                thread: None,
+                stopped: false,
+            }),
+            wakeup: Condvar::new(),
+        }
+    }
+
+    // 调度任务在指定时间到期；定时器已停止时返回 SubmitError::ShutDown，无法创建定时器线程时返回 SubmitError::Rejected（下次调度时重试创建）
+    // Schedule a task to become due at the given time; returns SubmitError::ShutDown once the timer has stopped and SubmitError::Rejected when the timer thread cannot be created (creation is retried on the next schedule)
+    pub(crate) fn schedule(&self, inner: &Arc<Inner>, deadline: Instant, job: Job) -> Result<ScheduledTask, SubmitError> {
+        let mut state = self.state.lock().expect("Failed to lock timer mutex");
+        if state.stopped {
+            return Err(SubmitError::ShutDown);
+        }
+
+        // 首次调度时启动定时器线程
+        // Start the timer thread on the first schedule
+        if state.thread.is_none() {
+            let mut builder = thread::Builder::new();
+            if let Some(prefix) = &inner.thread_name_prefix {
+                builder = builder.name(format!("{}-timer", prefix));
+            }
+            let timer_inner = Arc::clone(inner);
+            match builder.spawn(move || timer_inner.timer.run(&timer_inner)) {
+                Ok(handle) => state.thread = Some(handle),
+                Err(_) => return Err(SubmitError::Rejected),
+            }
+        }
+
+        let task_state = Arc::new(AtomicU8::new(PENDING));
+        let seq = state.next_seq;
+        state.next_seq += 1;
+
+        // 新任务成为堆顶时需要唤醒定时器线程，重新计算等待时间
+        // The timer thread must be woken to recompute its wait when the new task becomes the heap top
+        let earliest = state.heap.peek().is_none_or(|top| deadline < top.deadline);
+        state.heap.push(TimerEntry {
+            deadline,
+            seq,
+            state: Arc::clone(&task_state),
+            job,
+        });
+        drop(state);
+        if earliest {
+            self.wakeup.notify_one();
+        }
+
+        Ok(ScheduledTask { state: task_state, deadline, inner: Arc::downgrade(inner) })
+    }
+
+    // 取消尚未到期的任务，返回是否成功取消；在定时器锁内修改状态，使已取消条目的计数与堆保持一致；
+    // 已取消的条目超过堆的一半时一次性移除，使其捕获的数据尽早释放，频繁调度和取消也不会让堆无限增长
+    // Cancel a task not yet due, returns whether it was cancelled; the state changes under the timer lock so the cancelled count matches the heap;
+    // once cancelled entries exceed half the heap they are removed in one pass, releasing captured data early and keeping schedule/cancel churn from growing the heap without bound
+    fn cancel(&self, task_state: &AtomicU8) -> bool {
+        let mut state = self.state.lock().expect("Failed to lock timer mutex");
+        if task_state
+            .compare_exchange(PENDING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return false;
+        }
+
+        // 定时器停止后堆已被取出，无需清理
+        // The heap was taken out when the timer stopped, nothing to purge
+        if state.stopped {
+            return true;
+        }
+
+        state.cancelled += 1;
+        if state.cancelled * 2 > state.heap.len() {
+            let (pending, cancelled): (Vec<TimerEntry>, Vec<TimerEntry>) = mem::take(&mut state.heap)
+                .into_vec()
+                .into_iter()
+                .partition(|entry| entry.state.load(Ordering::SeqCst) == PENDING);
+            state.heap = BinaryHeap::from(pending);
+            state.cancelled = 0;
+
+            // 释放锁后再丢弃任务，任务捕获的数据在析构时可能再次调度延迟任务
+            // Drop the tasks after releasing the lock, data captured by them may schedule delayed tasks again when destroyed
+            drop(state);
+            drop(cancelled);
+        }
+        true
+    }
+
+    // 堆中的条目数（包括已取消但尚未移除的条目）
+    // Number of entries in the heap (including cancelled entries not yet removed)
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.state.lock().expect("Failed to lock timer mutex").heap.len()
+    }
+
+    // 停止定时器并取出所有未到期且未取消的任务
+    // Stop the timer and take out all pending tasks that are neither due nor cancelled
+    pub(crate) fn stop(&self) -> Vec<Job> {
+        let mut state = self.state.lock().expect("Failed to lock timer mutex");
+        state.stopped = true;
+        state.cancelled = 0;
+        let entries = mem::take(&mut state.heap);
+        drop(state);
+        self.wakeup.notify_all();
+
+        // 抢先将状态置为取消，使句柄能观察到任务不会再执行
+        // Mark each entry cancelled first so handles can observe that the task will never run
+        entries
+            .into_iter()
+            .filter(|entry| {
+                entry
+                    .state
+                    .compare_exchange(PENDING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+            })
+            .map(|entry| entry.job)
+            .collect()
+    }
+
+    // 取出定时器线程句柄，用于在线程池销毁时等待其退出
+    // Take the timer thread handle, used to wait for it to exit when the pool is destroyed
+    pub(crate) fn take_thread(&self) -> Option<JoinHandle<()>> {
+        self.state.lock().expect("Failed to lock timer mutex").thread.take()
+    }
+
+    // 定时器线程主循环：等待堆顶到期，将到期任务放入任务队列，直到定时器停止
+    // Timer thread main loop: wait for the heap top to become due and move due tasks into the queue, until the timer stops
+    fn run(&self, inner: &Arc<Inner>) {
+        let mut state = self.state.lock().expect("Failed to lock timer mutex");
+        loop {
+            if state.stopped {
+                return;
+            }
+
+            let now = Instant::now();
+            match state.heap.peek().map(|top| top.deadline) {
+                // 堆为空，等待新任务
+                // Heap is empty, wait for new tasks
+                None => {
+                    state = self.wakeup.wait(state).expect("Condvar wait failed");
+                }
+
+                // 堆顶尚未到期，等待到期或被新的堆顶唤醒
+                // Heap top is not due yet, wait until it is or a new heap top wakes us
+                Some(deadline) if deadline > now => {
+                    state = self
+                        .wakeup
+                        .wait_timeout(state, deadline - now)
+                        .expect("Condvar wait failed")
+                        .0;
+                }
+
+                // 取出所有已到期的任务，释放定时器锁后再放入任务队列
+                // Take out all due tasks, then move them into the task queue after releasing the timer lock
+                Some(_) => {
+                    // 在锁内将到期任务标记为已触发，与 cancel 互斥，保证已取消条目的计数准确
+                    // Mark due tasks fired under the lock, mutually exclusive with cancel, so the cancelled count stays accurate
+                    let mut due = Vec::new();
+                    let mut dropped = Vec::new();
+                    while state.heap.peek().is_some_and(|top| top.deadline <= now) {
+                        let entry = state.heap.pop().expect("Due timer entry disappeared");
+                        if entry
+                            .state
+                            .compare_exchange(PENDING, FIRED, Ordering::SeqCst, Ordering::SeqCst)
+                            .is_ok()
+                        {
+                            due.push(entry);
+                        } else {
+                            state.cancelled -= 1;
+                            dropped.push(entry);
+                        }
+                    }
+                    drop(state);
+
+                    // 已取消的任务直接丢弃
+                    // Cancelled tasks are simply dropped
+                    drop(dropped);
+                    for entry in due {
+                        inner.enqueue_due(entry.job);
+                    }
+
+                    state = self.state.lock().expect("Failed to lock timer mutex");
+                }
+            }
+        }
+    }
+}
+
+// 周期任务的调度方式
+// How a periodic task is rescheduled
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Schedule {
+    // 固定频率：按计划时间间隔执行，上一次执行超时时下一次立即开始
+    // Fixed rate: runs at planned intervals, the next run starts immediately after an overrun
+    FixedRate(Duration),
+
+    // 固定延迟：上一次执行结束后等待指定时间再执行
+    // Fixed delay: waits the given time after the previous run finishes
+    FixedDelay(Duration),
+}
+
+// 周期任务共享状态，每次执行都作为一个普通延迟任务提交，两次执行之间不占用工作线程
+// Shared periodic task state, every run is submitted as an ordinary delayed task so no worker is held between runs
+pub(crate) struct Periodic {
+    // 周期执行的任务
+    // Task executed periodically
+    task: Mutex<Box<dyn FnMut() + Send + 'static>>,
+
+    // 调度方式
+    // Schedule kind
+    schedule: Schedule,
+
+    // 周期任务状态：运行中、已取消或因 panic 停止
+    // Periodic task state: running, cancelled, or stopped by a panic
+    state: AtomicU8,
+
+    // 已执行次数
+    // Number of completed runs
+    runs: AtomicUsize,
+
+    // 下一次执行对应的定时任务句柄，取消时一并取消
+    // Scheduled task handle of the next run, cancelled together with the schedule
+    pending: Mutex<Option<ScheduledTask>>,
+
+    // 线程池内部状态的弱引用，避免定时器中的任务让线程池无法释放
+    // Weak reference to the pool internals, so tasks held by the timer never keep the pool alive
+    inner: Weak<Inner>,
+}
+
+// 周期任务句柄，可取消后续执行
+// Periodic task handle, can cancel all future runs
+#[derive(Clone)]
+pub struct PeriodicTask {
+    // 与调度共享的状态
+    // State shared with the schedule
+    periodic: Arc<Periodic>,
+}
+
+impl PeriodicTask {
+    // 取消周期任务，正在执行的一次不会被中断；返回是否由本次调用取消
+    // Cancel the periodic task, a run in progress is not interrupted; returns whether this call cancelled it
+    pub fn cancel(&self) -> bool {
+        let cancelled = self
+            .periodic
+            .state
+            .compare_exchange(ACTIVE, CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok();
+
+        // 取消已排入定时器的下一次执行
+        // Cancel the next run already registered with the timer
+        if let Some(pending) = self.periodic.pending.lock().expect("Failed to lock periodic mutex").take() {
+            pending.cancel();
+        }
+        cancelled
+    }
+
+    // 周期任务是否已被取消（包括线程池关闭导致的取消）
+    // Whether the periodic task has been cancelled (including cancellation by pool shutdown)
+    pub fn is_cancelled(&self) -> bool {
+        self.periodic.state.load(Ordering::SeqCst) == CANCELLED
+            || self
+                .periodic
+                .pending
+                .lock()
+                .expect("Failed to lock periodic mutex")
+                .as_ref()
+                .is_some_and(ScheduledTask::is_cancelled)
+    }
+
+    // 周期任务是否因某次执行 panic 而停止
+    // Whether the periodic task stopped because a run panicked
+    pub fn is_failed(&self) -> bool {
+        self.periodic.state.load(Ordering::SeqCst) == FAILED
+    }
+
+    // 返回已执行的次数（包括发生 panic 的一次）
+    // Return the number of runs so far (including a run that panicked)
+    pub fn run_count(&self) -> usize {
+        self.periodic.runs.load(Ordering::SeqCst)
+    }
+}
+
+impl Periodic {
+    // 创建周期任务并调度第一次执行，线程池已关闭或无法创建定时器线程时返回错误
+    // Create a periodic task and schedule its first run, returns an error once the pool is shut down or when the timer thread cannot be created
+    pub(crate) fn start<F>(inner: &Arc<Inner>, initial_delay: Duration, schedule: Schedule, task: F) -> Result<PeriodicTask, SubmitError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let periodic = Arc::new(Periodic {
+            task: Mutex::new(Box::new(task)),
+            schedule,
+            state: AtomicU8::new(ACTIVE),
+            runs: AtomicUsize::new(0),
+            pending: Mutex::new(None),
+            inner: Arc::downgrade(inner),
+        });
+
+        Periodic::schedule_next(&periodic, inner, Instant::now() + initial_delay)?;
+        Ok(PeriodicTask { periodic })
+    }
+
+    // 将下一次执行登记到定时器，周期任务已停止时不登记；定时器拒绝登记时返回错误
+    // Register the next run with the timer, nothing is registered once the periodic task has stopped; returns an error when the timer refuses the run
+    fn schedule_next(periodic: &Arc<Periodic>, inner: &Arc<Inner>, deadline: Instant) -> Result<(), SubmitError> {
+        // 持有 pending 锁检查取消状态，保证与 cancel 互斥，不会遗留已取消调度的下一次执行
+        // Check the cancelled state while holding the pending lock, mutually exclusive with cancel, so no run outlives a cancelled schedule
+        let mut pending = periodic.pending.lock().expect("Failed to lock periodic mutex");
+        if periodic.state.load(Ordering::SeqCst) != ACTIVE {
+            return Ok(());
+        }
+
+        let next = Arc::clone(periodic);
+        let job = inner.new_job(Box::new(move || next.run(deadline)));
+        *pending = Some(inner.timer.schedule(inner, deadline, job)?);
+        Ok(())
+    }
+
+    // 执行一次任务并调度下一次；panic 时停止调度并继续传播，交由工作线程统计
+    // Run the task once and schedule the next run; a panic stops the schedule and keeps propagating so the worker counts it
+    fn run(self: Arc<Self>, planned: Instant) {
+        if self.state.load(Ordering::SeqCst) != ACTIVE {
+            return;
+        }
+
+        let result = {
+            let mut task = self.task.lock().expect("Failed to lock periodic task");
+            panic::catch_unwind(AssertUnwindSafe(|| (*task)()))
+        };
+        self.runs.fetch_add(1, Ordering::SeqCst);
+
+        if let Err(payload) = result {
+            let _ = self.state.compare_exchange(ACTIVE, FAILED, Ordering::SeqCst, Ordering::SeqCst);
+            panic::resume_unwind(payload);
+        }
+
+        let now = Instant::now();
+        let next = match self.schedule {
+            // 上一次执行超过周期时不补执行错过的次数，下一次立即开始并以此为新的基准
+            // An overrunning run does not cause missed runs to be replayed, the next run starts immediately and becomes the new anchor
+            Schedule::FixedRate(period) => (planned + period).max(now),
+            Schedule::FixedDelay(delay) => now + delay,
+        };
+
+        // 线程池已被销毁时周期任务随之结束；线程池已关闭时定时器拒绝登记，周期任务同样结束
+        // The periodic task ends together with the pool once the pool is gone; once the pool is shut down the timer refuses the run and the task ends as well
+        if let Some(inner) = self.inner.upgrade() {
+            let _ = Periodic::schedule_next(&self, &inner, next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rejection::SubmitError;
+    use crate::thread_pool::ThreadPool;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn cancelled_tasks_release_captured_data() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        let captured = Arc::new(());
+
+        let handles: Vec<_> = (0..1000)
+            .map(|_| {
+                let captured = Arc::clone(&captured);
+                pool.submit_after(Duration::from_secs(3600), move || drop(captured)).unwrap()
+            })
+            .collect();
+        assert_eq!(Arc::strong_count(&captured), 1001);
+
+        for handle in &handles {
+            assert!(handle.cancel());
+        }
+
+        // 所有条目都已取消时堆被清空，捕获的数据不再等到原定的到期时间才释放
+        // With every entry cancelled the heap is emptied, captured data is not kept until the original due time
+        assert_eq!(pool.inner.timer.len(), 0);
+        assert_eq!(Arc::strong_count(&captured), 1);
+    }
+
+    #[test]
+    fn schedule_cancel_churn_keeps_heap_bounded() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        let kept = pool.submit_after(Duration::from_secs(3600), || {}).unwrap();
+
+        for _ in 0..10_000 {
+            let handle = pool.submit_after(Duration::from_secs(3600), || {}).unwrap();
+            assert!(handle.cancel());
+            assert!(pool.inner.timer.len() <= 2);
+        }
+
+        assert!(!kept.is_cancelled());
+        assert!(kept.cancel());
+    }
+
+    #[test]
+    fn cancelled_periodic_task_releases_captured_data() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        let captured = Arc::new(());
+
+        let task_captured = Arc::clone(&captured);
+        let periodic = pool
+            .schedule_at_fixed_rate(Duration::from_secs(3600), Duration::from_secs(60), move || {
+                let _ = &task_captured;
+            })
+            .unwrap();
+        assert!(periodic.cancel());
+        drop(periodic);
+
+        assert_eq!(pool.inner.timer.len(), 0);
+        assert_eq!(Arc::strong_count(&captured), 1);
+    }
+
+    #[test]
+    fn cancelled_tasks_do_not_fire() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        let cancelled = pool.submit_after(Duration::from_millis(20), || panic!("cancelled task ran")).unwrap();
+        let fired = pool.submit_after(Duration::from_millis(20), || {}).unwrap();
+        assert!(cancelled.cancel());
+
+        std::thread::sleep(Duration::from_millis(100));
+        pool.wait_for_completion();
+        assert!(fired.is_fired());
+        assert!(!cancelled.is_fired());
+        assert!(!fired.cancel());
+        assert_eq!(pool.panicked_tasks(), 0);
+    }
+
+    #[test]
+    fn scheduling_after_shutdown_returns_an_error() {
+        let pool = ThreadPool::builder().max_threads(1).build();
+        pool.shutdown();
+
+        // 定时器已停止，延迟任务和周期任务都返回错误而不是 panic，并计入被拒绝的任务数
+        // The timer has stopped, delayed and periodic tasks both return an error instead of panicking and count as rejected
+        assert_eq!(pool.submit_after(Duration::from_millis(10), || {}).err(), Some(SubmitError::ShutDown));
+        let periodic = pool.schedule_with_fixed_delay(Duration::ZERO, Duration::from_millis(10), || {});
+        assert_eq!(periodic.err(), Some(SubmitError::ShutDown));
+        assert_eq!(pool.rejected_tasks(), 2);
+        assert_eq!(pool.inner.timer.len(), 0);
+    }
+}
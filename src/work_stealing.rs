@@ -0,0 +1,286 @@
+// 导入 RefCell 和 Cell（线程局部的可变状态）
+// Import RefCell and Cell (mutable thread-local state)
+use std::cell::{Cell, RefCell};
+
+// 导入 VecDeque（双端队列，用作工作线程的本地队列）
+// Import VecDeque (double-ended queue, used as the worker-local deque)
+use std::collections::VecDeque;
+
+// 导入 RandomState、BuildHasher 和 Hasher（用于生成随机种子）
+// Import RandomState, BuildHasher, and Hasher (used to generate random seeds)
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+// 导入 Arc（原子引用计数）和 Mutex（互斥锁）
+// Import Arc (atomic reference counting) and Mutex (mutual exclusion lock)
+use std::sync::{Arc, Mutex};
+
+// 导入队列中的任务条目
+// Import task entry stored in the queue
+use crate::thread_pool::Job;
+
+// 导入工作线程状态槽
+// Import worker status slot
+use crate::introspect::WorkerStatus;
+
+// 线程池的调度模式
+// Scheduling mode of the thread pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchedulerMode {
+    // 所有工作线程共享一个全局任务队列
+    // All workers share a single global task queue
+    #[default]
+    Global,
+
+    // 工作窃取：每个工作线程拥有本地队列，工作线程内提交的任务进入本地队列（所有者后进先出），
+    // 外部提交的任务进入全局注入队列，本地队列为空时随机从其他工作线程的队列头部窃取
+    // Work stealing: every worker owns a local deque, tasks submitted from a worker go to its local deque (LIFO for the owner),
+    // external submissions go to the global injector queue, and a worker with an empty deque steals from the front of a random other deque
+    WorkStealing,
+}
+
+// 工作线程的本地队列
+// Local deque of a worker thread
+pub(crate) struct LocalQueue {
+    // 所属工作线程 ID
+    // ID of the owning worker thread
+    pub(crate) worker_id: usize,
+
+    // 本地任务：所有者从尾部存取，窃取者从头部取
+    // Local tasks: the owner pushes and pops at the back, thieves take from the front
+    pub(crate) jobs: Mutex<VecDeque<Job>>,
+}
+
+impl LocalQueue {
+    // 创建空的本地队列
+    // Create an empty local deque
+    pub(crate) fn new(worker_id: usize) -> Self {
+        LocalQueue {
+            worker_id,
+            jobs: Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+// 当前线程作为工作线程时的上下文
+// Context of the current thread when it is a worker
+struct WorkerContext {
+    // 所属线程池的标识（线程池内部状态的地址）
+    // Identifier of the owning pool (address of the pool internals)
+    pool: usize,
+
+    // 工作线程的状态槽，包含工作线程 ID
+    // Status slot of the worker, holding the worker ID
+    status: Arc<WorkerStatus>,
+
+    // 当前工作线程的本地队列，只在工作窃取模式下存在
+    // Local deque of the current worker, only present in work-stealing mode
+    local: Option<Arc<LocalQueue>>,
+}
+
+thread_local! {
+    // 当前线程的工作线程上下文，非工作线程为 None
+    // Worker context of the current thread, None for non-worker threads
+    static WORKER: RefCell<Option<WorkerContext>> = const { RefCell::new(None) };
+
+    // 窃取时选择起点的随机数状态（xorshift），首次使用时随机初始化
+    // Random state (xorshift) used to pick where stealing starts, randomly seeded on first use
+    static RNG: Cell<u64> = const { Cell::new(0) };
+}
+
+// 将当前线程登记为指定线程池的工作线程
+// Register the current thread as a worker of the given pool
+pub(crate) fn enter_worker(pool: usize, status: Arc<WorkerStatus>, local: Option<Arc<LocalQueue>>) {
+    WORKER.with(|worker| *worker.borrow_mut() = Some(WorkerContext { pool, status, local }));
+}
+
+// 清除当前线程的工作线程登记
+// Clear the worker registration of the current thread
+pub(crate) fn leave_worker() {
+    WORKER.with(|worker| *worker.borrow_mut() = None);
+}
+
+// 当前线程是指定线程池的工作线程时，返回其本地队列
+// Return the local deque when the current thread is a worker of the given pool
+pub(crate) fn current_local(pool: usize) -> Option<Arc<LocalQueue>> {
+    WORKER.with(|worker| {
+        worker
+            .borrow()
+            .as_ref()
+            .filter(|context| context.pool == pool)
+            .and_then(|context| context.local.clone())
+    })
+}
+
+// 当前线程是指定线程池的工作线程时，返回其状态槽和本地队列
+// Return the status slot and local deque when the current thread is a worker of the given pool
+pub(crate) fn current_worker(pool: usize) -> Option<(Arc<WorkerStatus>, Option<Arc<LocalQueue>>)> {
+    WORKER.with(|worker| {
+        worker
+            .borrow()
+            .as_ref()
+            .filter(|context| context.pool == pool)
+            .map(|context| (Arc::clone(&context.status), context.local.clone()))
+    })
+}
+
+// 返回 [0, len) 范围内的随机下标，用于分散窃取起点
+// Return a random index in [0, len), used to spread out where stealing starts
+pub(crate) fn random_index(len: usize) -> usize {
+    RNG.with(|rng| {
+        let mut x = rng.get();
+
+        // 首次使用时以随机哈希种子初始化，种子不能为0
+        // Seed from a random hash on first use, the seed must not be 0
+        if x == 0 {
+            x = RandomState::new().build_hasher().finish() | 1;
+        }
+
+        // xorshift64
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        rng.set(x);
+        (x % len as u64) as usize
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thread_pool::ThreadPool;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    // 单个工作线程的工作窃取线程池：工作线程内提交若干本地任务后阻塞，直到放行
+    // Single-worker work-stealing pool: the worker submits some local tasks and then blocks until released
+    fn pool_with_local_jobs(count: usize, ran: &Arc<AtomicUsize>) -> (Arc<ThreadPool>, mpsc::Sender<()>) {
+        let pool = Arc::new(
+            ThreadPool::builder()
+                .core_threads(1)
+                .max_threads(1)
+                .scheduler(SchedulerMode::WorkStealing)
+                .build(),
+        );
+        let (queued_tx, queued_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        let worker_pool = Arc::clone(&pool);
+        let worker_ran = Arc::clone(ran);
+        pool.submit(move || {
+            for _ in 0..count {
+                let ran = Arc::clone(&worker_ran);
+                worker_pool
+                    .submit(move || {
+                        ran.fetch_add(1, Ordering::SeqCst);
+                    })
+                    .expect("Failed to submit local task");
+            }
+            queued_tx.send(()).expect("Failed to report queued tasks");
+            let _ = release_rx.recv();
+        })
+        .expect("Failed to submit task");
+
+        queued_rx.recv_timeout(Duration::from_secs(10)).expect("Local tasks were not queued");
+        (pool, release_tx)
+    }
+
+    #[test]
+    fn tasks_submitted_from_workers_run() {
+        let pool = Arc::new(ThreadPool::builder().max_threads(2).scheduler(SchedulerMode::WorkStealing).build());
+        let ran = Arc::new(AtomicUsize::new(0));
+
+        let worker_pool = Arc::clone(&pool);
+        let worker_ran = Arc::clone(&ran);
+        pool.submit(move || {
+            for _ in 0..100 {
+                let ran = Arc::clone(&worker_ran);
+                worker_pool
+                    .submit(move || {
+                        ran.fetch_add(1, Ordering::SeqCst);
+                    })
+                    .expect("Failed to submit local task");
+            }
+        })
+        .expect("Failed to submit task");
+
+        pool.wait_idle();
+        assert_eq!(ran.load(Ordering::SeqCst), 100);
+        assert_eq!(pool.completed_tasks(), 101);
+    }
+
+    #[test]
+    fn idle_worker_steals_from_a_busy_one() {
+        let pool = Arc::new(
+            ThreadPool::builder()
+                .core_threads(2)
+                .max_threads(2)
+                .prestart(true)
+                .scheduler(SchedulerMode::WorkStealing)
+                .build(),
+        );
+        let (first_tx, first_rx) = mpsc::channel();
+        let (second_tx, second_rx) = mpsc::channel();
+        let (done_tx, done_rx) = mpsc::channel();
+
+        // 两个本地任务互相等待，只有另一个工作线程窃取其中之一时才能同时完成
+        // The two local tasks wait for each other, so they can only finish if the other worker steals one of them
+        let worker_pool = Arc::clone(&pool);
+        pool.submit(move || {
+            let done = done_tx.clone();
+            worker_pool
+                .submit(move || {
+                    first_tx.send(thread::current().id()).expect("Failed to signal");
+                    let other = second_rx.recv_timeout(Duration::from_secs(10)).expect("Second task was not stolen");
+                    done.send(other != thread::current().id()).expect("Failed to report");
+                })
+                .expect("Failed to submit local task");
+            worker_pool
+                .submit(move || {
+                    second_tx.send(thread::current().id()).expect("Failed to signal");
+                    let other = first_rx.recv_timeout(Duration::from_secs(10)).expect("First task was not stolen");
+                    done_tx.send(other != thread::current().id()).expect("Failed to report");
+                })
+                .expect("Failed to submit local task");
+        })
+        .expect("Failed to submit task");
+
+        for _ in 0..2 {
+            assert!(done_rx.recv_timeout(Duration::from_secs(20)).expect("Local task did not finish"));
+        }
+    }
+
+    #[test]
+    fn wait_idle_and_queue_len_count_local_jobs() {
+        let ran = Arc::new(AtomicUsize::new(0));
+        let (pool, release) = pool_with_local_jobs(5, &ran);
+
+        assert_eq!(pool.queue_len(), 5);
+        assert_eq!(pool.stats().queued_tasks, 5);
+
+        release.send(()).expect("Failed to release worker");
+        pool.wait_idle();
+        assert_eq!(ran.load(Ordering::SeqCst), 5);
+        assert_eq!(pool.queue_len(), 0);
+    }
+
+    #[test]
+    fn shutdown_now_returns_local_jobs() {
+        let ran = Arc::new(AtomicUsize::new(0));
+        let (pool, release) = pool_with_local_jobs(5, &ran);
+
+        let drained = pool.shutdown_now();
+        assert_eq!(drained.len(), 5);
+        assert_eq!(pool.queue_len(), 0);
+
+        release.send(()).expect("Failed to release worker");
+        assert!(pool.await_termination(Duration::from_secs(10)));
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+
+        // 被取出的任务视为已结束，等待完成不会卡住
+        // Drained tasks count as finished, so waiting for completion does not hang
+        assert!(pool.wait_for_completion_timeout(Duration::from_secs(10)));
+    }
+}